@@ -0,0 +1,183 @@
+//! A product catalog going through the main features of the SDK, from the import of the documents to their deletion.
+//!
+//! It targets MeiliSearch v1.3 or newer, the first version with facet search. It needs a running server
+//! at `MEILISEARCH_URL` (default: `http://localhost:7700`) with the key `MEILISEARCH_API_KEY` (default: `masterKey`).
+//! It runs as a test with the `smoke-test` feature:
+//!
+//! ```bash
+//! cargo run --example ecommerce --features smoke-test
+//! cargo test --example ecommerce --features smoke-test
+//! ```
+
+use meilisearch_sdk::{
+    client::{Capability, Client, Method},
+    document::Document,
+    errors::Error,
+    indexes::{DocumentDeletionParams, Index},
+    progress::{Progress, WaitPolicy},
+    search::{escape_value, SearchResults, Selectors},
+    settings::{MinWordSizeForTypos, Settings, TypoToleranceSettings},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Product {
+    id: usize,
+    name: String,
+    brand: String,
+    category: String,
+    price: f64,
+    rating: u8,
+    in_stock: bool,
+}
+
+impl Document for Product {
+    type UIDType = usize;
+
+    fn get_uid(&self) -> &Self::UIDType {
+        &self.id
+    }
+}
+
+const BRANDS: [&str; 4] = ["Acme", "Globex", "Initech", "Umbrella"];
+const CATEGORIES: [&str; 3] = ["home & garden", "electronics", "outdoor"];
+const ITEMS: [&str; 5] = ["lamp", "kettle", "headphones", "tent", "backpack"];
+
+fn catalog() -> Vec<Product> {
+    (0..60)
+        .map(|id| Product {
+            id,
+            name: format!("{} {} {}", BRANDS[id % 4], ITEMS[id % 5], id),
+            brand: BRANDS[id % 4].to_string(),
+            category: CATEGORIES[id % 3].to_string(),
+            price: 5.0 + (id * 37 % 100) as f64,
+            rating: (id % 5 + 1) as u8,
+            in_stock: id % 7 != 0,
+        })
+        .collect()
+}
+
+/// Wait for an update, retrying the status checks that fail while the server is busy.
+async fn wait(progress: Progress) -> Result<(), Error> {
+    let policy = WaitPolicy { timeout: Duration::from_secs(30), max_poll_retries: 5, ..WaitPolicy::default() };
+    progress.wait_with_policy(&policy).await.map(|_| ())
+}
+
+async fn configure(index: &Index) -> Result<(), Error> {
+    let mut settings = Settings::new()
+        .with_searchable_attributes(["name", "brand"])
+        .with_filterable_attributes(["brand", "category", "price", "in_stock"])
+        .with_sortable_attributes(["price", "rating"])
+        .with_typo_tolerance(
+            TypoToleranceSettings::new().with_min_word_size_for_typos(MinWordSizeForTypos { one_typo: Some(4), two_typos: Some(8) }),
+        );
+    // Settings unknown to the SDK are sent as they are
+    settings.extra.insert("faceting".to_string(), json!({ "maxValuesPerFacet": 20 }));
+    wait(index.set_settings(&settings).await?).await
+}
+
+/// Import the catalog as newline-delimited JSON, in batches.
+async fn import(index: &Index, products: &[Product]) -> Result<(), Error> {
+    let mut updates = Vec::new();
+    for batch in products.chunks(25) {
+        let mut payload = Vec::new();
+        for product in batch {
+            payload.extend(serde_json::to_vec(product).map_err(Error::ParseError)?);
+            payload.push(b'\n');
+        }
+        updates.push(index.add_documents_ndjson(&payload, Some("id")).await?);
+    }
+    for update in updates {
+        wait(update).await?;
+    }
+    Ok(())
+}
+
+async fn search(client: &Client, index: &Index, uid: &str, products: &[Product]) -> Result<(), Error> {
+    // Facet search isn't wrapped by the SDK yet, so it goes through a raw request
+    let brands: Value = client
+        .raw_request(&format!("indexes/{}/facet-search", uid), Method::Post(json!({ "facetName": "brand", "facetQuery": "glo" })), (), 200)
+        .await?;
+    assert_eq!(brands["facetHits"][0]["value"], "Globex", "{}", brands);
+
+    // A typo, a filter on a value with special characters, a sort and highlights
+    let filter = format!("category = {} AND in_stock = true", escape_value("home & garden"));
+    let lamps: SearchResults<Product> = index
+        .search()
+        .with_query("lmap")
+        .with_filter(&filter)
+        .with_sort(&["price:asc"])
+        .with_attributes_to_highlight(Selectors::Some(&["name"]))
+        .with_facets(Selectors::Some(&["brand"]))
+        .execute()
+        .await?;
+    let expected = products.iter().filter(|product| product.name.contains("lamp") && product.category == "home & garden" && product.in_stock).count();
+    assert!(expected > 0);
+    assert_eq!(lamps.hits.len(), expected);
+    assert!(lamps.hits.windows(2).all(|pair| pair[0].result.price <= pair[1].result.price));
+    assert!(lamps.hits.iter().all(|hit| matches!(hit.formatted_field("name"), Some(name) if name.contains("<em>lamp</em>"))));
+    assert!(matches!(&lamps.facet_distribution, Some(distribution) if distribution.contains_key("brand")));
+
+    // Going through all the pages of the results
+    let mut seen = Vec::new();
+    let mut page = 1;
+    loop {
+        let results: SearchResults<Product> = index.search().with_sort(&["rating:desc"]).with_hits_per_page(20).with_page(page).execute().await?;
+        seen.extend(results.hits.into_iter().map(|hit| hit.result.id));
+        if page >= results.total_pages.unwrap_or(0) {
+            break;
+        }
+        page += 1;
+    }
+    seen.sort_unstable();
+    assert_eq!(seen, products.iter().map(|product| product.id).collect::<Vec<_>>());
+    Ok(())
+}
+
+async fn run() -> Result<(), Error> {
+    let host = std::env::var("MEILISEARCH_URL").unwrap_or_else(|_| "http://localhost:7700".to_string());
+    let api_key = std::env::var("MEILISEARCH_API_KEY").unwrap_or_else(|_| "masterKey".to_string());
+    let client = Client::new(host, api_key);
+    // Fail early on older servers, instead of in the middle of the example
+    let report = client.check_compatibility(&[Capability::FacetSearch, Capability::DeleteDocumentsByFilter]).await?;
+    if let Some(&capability) = report.missing.first() {
+        return Err(Error::UnsupportedCapability { capability });
+    }
+    let uid = format!("ecommerce_example_{}", std::process::id());
+    let index = client.create_index(&uid, Some("id")).await?;
+
+    let products = catalog();
+    let result = async {
+        configure(&index).await?;
+        import(&index, &products).await?;
+        search(&client, &index, &uid, &products).await?;
+
+        // The products out of stock are removed at once
+        let params = DocumentDeletionParams { filter: "in_stock = false".to_string() };
+        wait(index.delete_documents_with_params(&params).await?).await?;
+        let stats = index.get_stats().await?;
+        assert_eq!(stats.number_of_documents, products.iter().filter(|product| product.in_stock).count());
+        Ok(())
+    }
+    .await;
+
+    client.delete_index(&uid).await?;
+    result
+}
+
+fn main() {
+    if let Err(error) = futures::executor::block_on(run()) {
+        eprintln!("The example failed: {}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_ecommerce() {
+        futures::executor::block_on(super::run()).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use meilisearch_sdk::document::Document;
+use meilisearch_sdk::{document::Document, search::SearchResult};
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
@@ -23,7 +23,25 @@ impl Document for Crate {
 }
 
 impl Crate {
-    
+    /// Build a crate whose text fields are replaced by their highlighted version.
+    /// Non-string fields (like `downloads`) can't be read from the formatted result because MeiliSearch turns them into strings.
+    pub fn highlighted(result: &SearchResult<Crate>) -> Crate {
+        let field = |name: &str, original: &String| {
+            result.formatted_field(name).map(String::from).unwrap_or_else(|| original.clone())
+        };
+        let original = &result.result;
+
+        Crate {
+            name: field("name", &original.name),
+            downloads: original.downloads,
+            description: field("description", &original.description),
+            keywords: original.keywords.clone(),
+            categories: original.categories.clone(),
+            readme: field("readme", &original.readme),
+            version: field("version", &original.version),
+        }
+    }
+
     pub fn get_readable_download_count(&self) -> String {
         if let Some(downloads) = self.downloads {
             if downloads < 1000 {
@@ -82,7 +82,7 @@ impl Component for Model {
 
                     let mut fresh_formatted_results = Vec::new();
                     for result in fresh_results.hits {
-                        fresh_formatted_results.push(result.formatted_result.unwrap());
+                        fresh_formatted_results.push(Crate::highlighted(&result));
                     }
 
                     // We send a new event with the up-to-date data so that we can update the results and display them.
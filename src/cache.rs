@@ -0,0 +1,280 @@
+use crate::{errors::Error, request::*, Rc};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{collections::{BTreeSet, HashMap}, sync::Mutex, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+type Timestamp = std::time::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> Timestamp {
+    std::time::Instant::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn elapsed_since(timestamp: &Timestamp) -> Duration {
+    timestamp.elapsed()
+}
+
+// `std::time::Instant` is not available in browsers, so we rely on the JS clock (in milliseconds).
+#[cfg(target_arch = "wasm32")]
+type Timestamp = f64;
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> Timestamp {
+    js_sys::Date::now()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn elapsed_since(timestamp: &Timestamp) -> Duration {
+    Duration::from_secs_f64((js_sys::Date::now() - timestamp).max(0.0) / 1000.0)
+}
+
+/// A bounded in-memory cache of JSON responses, keyed by URL and API key.
+///
+/// The API key is part of the key so that an [index with another key](../indexes/struct.Index.html#method.with_key)
+/// can share the cache of its client, and invalidate it on writes, without ever reading the responses obtained with the other key.
+///
+/// Only idempotent metadata reads (settings, index information and stats) go through this cache.
+/// Entries are dropped after `ttl`, and all the entries related to an index are dropped as soon as
+/// a write is sent to that index through the same [Client](../client/struct.Client.html).
+///
+/// The updates enqueued through the same client are tracked until they are known to be processed,
+/// and nothing related to their index is cached in the meantime.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<(String, String), (Timestamp, Value)>>,
+    pending_updates: Mutex<HashMap<(String, String), BTreeSet<u64>>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(ttl: Duration, max_entries: usize) -> ResponseCache {
+        ResponseCache {
+            ttl,
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+            pending_updates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), (Timestamp, Value)>> {
+        // A panic while holding the lock cannot leave the map in an invalid state
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn pending_updates(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), BTreeSet<u64>>> {
+        self.pending_updates.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn get(&self, url: &str, api_key: &str) -> Option<Value> {
+        let mut entries = self.entries();
+        let key = (url.to_string(), api_key.to_string());
+        match entries.get(&key) {
+            Some((inserted_at, value)) if elapsed_since(inserted_at) < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, url: String, api_key: String, value: Value) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries();
+        let key = (url, api_key);
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted_at, _)| elapsed_since(inserted_at) < ttl);
+        }
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .max_by(|(_, (a, _)), (_, (b, _))| elapsed_since(a).cmp(&elapsed_since(b)))
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (now(), value));
+    }
+
+    /// Drop every entry related to the index, as well as the global stats which include it.
+    pub(crate) fn invalidate(&self, host: &str, index_uid: &str) {
+        let index_url = format!("{}/indexes/{}", host, index_uid);
+        let stats_url = format!("{}/stats", host);
+        self.entries().retain(|(url, _), _| {
+            let related_to_index = url
+                .strip_prefix(&index_url)
+                .map(|rest| rest.is_empty() || rest.starts_with('/'))
+                .unwrap_or(false);
+            !related_to_index && url != &stats_url
+        });
+    }
+
+    /// Drop every entry, when a write may have changed any index.
+    pub(crate) fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Invalidate the index and stop caching its responses until the update is [finished](#method.finish_update).
+    pub(crate) fn start_update(&self, host: &str, index_uid: &str, update_id: u64) {
+        self.pending_updates()
+            .entry((host.to_string(), index_uid.to_string()))
+            .or_default()
+            .insert(update_id);
+        self.invalidate(host, index_uid);
+    }
+
+    /// Record that an update was processed, dropping the responses cached while it was pending.
+    pub(crate) fn finish_update(&self, host: &str, index_uid: &str, update_id: u64) {
+        let mut pending_updates = self.pending_updates();
+        let key = (host.to_string(), index_uid.to_string());
+        if let Some(ids) = pending_updates.get_mut(&key) {
+            ids.remove(&update_id);
+            if ids.is_empty() {
+                pending_updates.remove(&key);
+            }
+        }
+        drop(pending_updates);
+        self.invalidate(host, index_uid);
+    }
+
+    /// Record that every update of the index was processed.
+    pub(crate) fn finish_all_updates(&self, host: &str, index_uid: &str) {
+        self.pending_updates().remove(&(host.to_string(), index_uid.to_string()));
+        self.invalidate(host, index_uid);
+    }
+
+    /// Whether the response may change because of an update that is not processed yet.
+    fn is_pending(&self, url: &str) -> bool {
+        self.pending_updates().keys().any(|(host, index_uid)| {
+            let related_to_index = url
+                .strip_prefix(&format!("{}/indexes/{}", host, index_uid))
+                .map(|rest| rest.is_empty() || rest.starts_with('/'))
+                .unwrap_or(false);
+            related_to_index || url == format!("{}/stats", host)
+        })
+    }
+}
+
+/// Send a GET request, going through the cache if there is one.
+pub(crate) async fn cached_request<Output: 'static + DeserializeOwned>(
+    cache: &Option<Rc<ResponseCache>>,
+    url: &str,
+    apikey: &str,
+    max_response_bytes: Option<usize>,
+) -> Result<Output, Error> {
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return request::<(), Output>(url, apikey, Method::Get, 200, max_response_bytes).await,
+    };
+
+    if cache.is_pending(url) {
+        return request::<(), Output>(url, apikey, Method::Get, 200, max_response_bytes).await;
+    }
+
+    let value = match cache.get(url, apikey) {
+        Some(value) => value,
+        None => {
+            let value = request::<(), Value>(url, apikey, Method::Get, 200, max_response_bytes).await?;
+            // An update may have been enqueued while the request was in flight
+            if !cache.is_pending(url) {
+                cache.insert(url.to_string(), apikey.to_string(), value.clone());
+            }
+            value
+        }
+    };
+
+    serde_json::from_value(value).map_err(Error::ParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::thread::sleep;
+
+    const KEY: &str = "masterKey";
+
+    #[test]
+    fn test_cache_hit() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 16);
+        assert!(cache.get("http://localhost:7700/indexes/movies/settings", KEY).is_none());
+
+        cache.insert("http://localhost:7700/indexes/movies/settings".to_string(), KEY.to_string(), json!({"stopWords": ["the"]}));
+        assert_eq!(
+            cache.get("http://localhost:7700/indexes/movies/settings", KEY),
+            Some(json!({"stopWords": ["the"]}))
+        );
+        // The responses obtained with a key are not served to another one
+        assert!(cache.get("http://localhost:7700/indexes/movies/settings", "searchKey").is_none());
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = ResponseCache::new(Duration::from_millis(10), 16);
+        cache.insert("http://localhost:7700/stats".to_string(), KEY.to_string(), json!({}));
+        sleep(Duration::from_millis(20));
+        assert!(cache.get("http://localhost:7700/stats", KEY).is_none());
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 16);
+        cache.insert("http://localhost:7700/indexes/movies".to_string(), KEY.to_string(), json!({}));
+        cache.insert("http://localhost:7700/indexes/movies/settings".to_string(), KEY.to_string(), json!({}));
+        cache.insert("http://localhost:7700/indexes/movies_2/settings".to_string(), KEY.to_string(), json!({}));
+        cache.insert("http://localhost:7700/stats".to_string(), KEY.to_string(), json!({}));
+        cache.insert("http://localhost:7700/indexes/movies/settings".to_string(), "adminKey".to_string(), json!({}));
+
+        cache.invalidate("http://localhost:7700", "movies");
+
+        assert!(cache.get("http://localhost:7700/indexes/movies", KEY).is_none());
+        assert!(cache.get("http://localhost:7700/indexes/movies/settings", KEY).is_none());
+        assert!(cache.get("http://localhost:7700/indexes/movies/settings", "adminKey").is_none());
+        assert!(cache.get("http://localhost:7700/stats", KEY).is_none());
+        assert!(cache.get("http://localhost:7700/indexes/movies_2/settings", KEY).is_some());
+    }
+
+    #[test]
+    fn test_cache_bypassed_while_update_pending() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 16);
+        cache.insert("http://localhost:7700/indexes/movies/settings".to_string(), KEY.to_string(), json!({}));
+
+        cache.start_update("http://localhost:7700", "movies", 1);
+        cache.start_update("http://localhost:7700", "movies", 2);
+        assert!(cache.get("http://localhost:7700/indexes/movies/settings", KEY).is_none());
+        assert!(cache.is_pending("http://localhost:7700/indexes/movies/settings"));
+        assert!(cache.is_pending("http://localhost:7700/stats"));
+        assert!(!cache.is_pending("http://localhost:7700/indexes/movies_2/settings"));
+
+        cache.finish_update("http://localhost:7700", "movies", 1);
+        assert!(cache.is_pending("http://localhost:7700/indexes/movies/settings"));
+        cache.finish_update("http://localhost:7700", "movies", 2);
+        assert!(!cache.is_pending("http://localhost:7700/indexes/movies/settings"));
+        assert!(!cache.is_pending("http://localhost:7700/stats"));
+
+        cache.start_update("http://localhost:7700", "movies", 3);
+        cache.finish_all_updates("http://localhost:7700", "movies");
+        assert!(!cache.is_pending("http://localhost:7700/indexes/movies"));
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2);
+        cache.insert("a".to_string(), KEY.to_string(), json!(1));
+        sleep(Duration::from_millis(2));
+        cache.insert("b".to_string(), KEY.to_string(), json!(2));
+        sleep(Duration::from_millis(2));
+        cache.insert("c".to_string(), KEY.to_string(), json!(3));
+
+        assert!(cache.get("a", KEY).is_none());
+        assert_eq!(cache.get("b", KEY), Some(json!(2)));
+        assert_eq!(cache.get("c", KEY), Some(json!(3)));
+    }
+}
@@ -1,13 +1,25 @@
-use crate::{errors::*, indexes::*, request::*, Rc};
-use serde_json::{json, Value};
-use serde::{Deserialize};
-use std::collections::HashMap;
+use crate::{cache::*, errors::*, indexes::*, progress::PendingWaits, request::*, search::MultiSearchQuery, Rc};
+use serde_json::{json, Map, Value};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+
+pub use crate::request::{Compression, Method, RequestMetrics};
 
 /// The top-level struct of the SDK, representing a client containing [indexes](../indexes/struct.Index.html).
-#[derive(Debug)]
+///
+/// Cloning a client is cheap: the clones share the same host, API key and [cache](#method.with_cache).
+/// They also share the [waits](../progress/struct.Progress.html#method.wait_for_pending_update) on updates, so that concurrent waits on the same update poll it only once.
+#[derive(Debug, Clone)]
 pub struct Client {
     pub(crate) host: Rc<String>,
     pub(crate) api_key: Rc<String>,
+    pub(crate) cache: Option<Rc<ResponseCache>>,
+    pub(crate) waits: Rc<PendingWaits>,
+    pub(crate) read_only: bool,
+    pub(crate) max_response_bytes: Option<usize>,
+    pub(crate) check_primary_key: bool,
+    pub(crate) check_vector_dimensions: bool,
+    pub(crate) compression: Option<Compression>,
 }
 
 impl Client {
@@ -17,7 +29,7 @@ impl Client {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// // create the client
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -25,7 +37,173 @@ impl Client {
     pub fn new(host: impl Into<String>, api_key: impl Into<String>) -> Client {
         Client {
             host: Rc::new(host.into()),
-            api_key: Rc::new(api_key.into())
+            api_key: Rc::new(api_key.into()),
+            cache: None,
+            waits: Rc::new(PendingWaits::default()),
+            read_only: false,
+            max_response_bytes: None,
+            check_primary_key: false,
+            check_vector_dimensions: false,
+            compression: None,
+        }
+    }
+
+    /// Enable a short-lived in-memory cache for metadata reads (settings, index information and stats).
+    ///
+    /// Responses are kept for `ttl` and at most `max_entries` responses are stored.
+    /// Cached responses related to an index are automatically dropped when this client (or any [Index](../indexes/struct.Index.html) obtained from it) sends a write to that index.
+    /// Until such a write is known to be processed, through the [status of its update](../progress/struct.Progress.html#method.get_status),
+    /// a wait for it or [Index::get_update](../indexes/struct.Index.html#method.get_update), the responses related to its index are not cached.
+    /// The writes sent with [raw_request](#method.raw_request) only drop the cached responses when they are sent, since their updates are not tracked.
+    /// Writes made by other clients are only visible once the cached responses expire, so keep `ttl` short.
+    /// See also [invalidate_cache](#method.invalidate_cache).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// # use std::time::Duration;
+    /// #
+    /// let client = Client::new("http://localhost:7700", "masterKey")
+    ///     .with_cache(Duration::from_secs(5), 100);
+    /// ```
+    pub fn with_cache(self, ttl: Duration, max_entries: usize) -> Client {
+        Client {
+            cache: Some(Rc::new(ResponseCache::new(ttl, max_entries))),
+            ..self
+        }
+    }
+
+    /// Make the client (and the [indexes](../indexes/struct.Index.html) obtained from it) refuse every write operation.
+    ///
+    /// Writes (documents and settings updates, index creation and deletion) return [Error::ReadOnlyClient](../errors/enum.Error.html#variant.ReadOnlyClient) without sending anything,
+    /// while searches and other reads work as usual. This is useful when the server is a read-only replica.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey").with_read_only(true);
+    /// let movies = client.assume_index("movies");
+    ///
+    /// assert!(matches!(movies.delete_all_documents().await, Err(Error::ReadOnlyClient { .. })));
+    /// # });
+    /// ```
+    pub fn with_read_only(self, read_only: bool) -> Client {
+        Client { read_only, ..self }
+    }
+
+    /// Limit the size of the responses accepted by the client (and the [indexes](../indexes/struct.Index.html) obtained from it).
+    ///
+    /// A larger response is dropped as soon as it exceeds the limit, and the request fails with [Error::ResponseTooLarge](../errors/enum.Error.html#variant.ResponseTooLarge).
+    /// This protects against running out of memory, when fetching a lot of large documents for example.
+    /// In the browser, the limit can only be enforced when the server announces the size of the response with a `Content-Length` header.
+    ///
+    /// By default, the size of the responses is unlimited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// let client = Client::new("http://localhost:7700", "masterKey").with_max_response_bytes(16 * 1024 * 1024);
+    /// ```
+    pub fn with_max_response_bytes(self, max_response_bytes: usize) -> Client {
+        Client { max_response_bytes: Some(max_response_bytes), ..self }
+    }
+
+    /// Check the primary key passed when adding documents against the primary key of the index, before sending the documents.
+    ///
+    /// Without this check, MeiliSearch accepts documents sent with another primary key than the one of the index, and the update fails later.
+    /// With it, [add_or_replace](../indexes/struct.Index.html#method.add_or_replace), [add_or_update](../indexes/struct.Index.html#method.add_or_update)
+    /// and their variants first fetch the index (through the [cache](#method.with_cache) if it is enabled),
+    /// and return [Error::PrimaryKeyMismatch](../errors/enum.Error.html#variant.PrimaryKeyMismatch) without sending the documents if the keys differ.
+    /// Nothing is checked when no primary key is passed, or when the index has no primary key yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// let client = Client::new("http://localhost:7700", "masterKey").with_primary_key_check(true);
+    /// ```
+    pub fn with_primary_key_check(self, check_primary_key: bool) -> Client {
+        Client { check_primary_key, ..self }
+    }
+
+    /// Check the vectors of the documents before sending them: all the vectors of an embedder must have the same number of dimensions within a batch.
+    ///
+    /// With this check, [add_or_replace](../indexes/struct.Index.html#method.add_or_replace), [add_or_update](../indexes/struct.Index.html#method.add_or_update)
+    /// and their variants return [Error::VectorDimensionMismatch](../errors/enum.Error.html#variant.VectorDimensionMismatch) without sending the documents
+    /// if the `_vectors` fields of the documents, built with [Vectors](../document/struct.Vectors.html) or not, are inconsistent.
+    /// The dimensions expected by the embedders of the index are not fetched: MeiliSearch still rejects a batch whose vectors all have the wrong size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// let client = Client::new("http://localhost:7700", "masterKey").with_vector_dimension_check(true);
+    /// ```
+    pub fn with_vector_dimension_check(self, check_vector_dimensions: bool) -> Client {
+        Client { check_vector_dimensions, ..self }
+    }
+
+    /// Compress the documents sent to MeiliSearch, to save bandwidth on large imports.
+    ///
+    /// The documents sent by [add_or_replace](../indexes/struct.Index.html#method.add_or_replace), [add_or_update](../indexes/struct.Index.html#method.add_or_update),
+    /// [add_documents_ndjson](../indexes/struct.Index.html#method.add_documents_ndjson), [add_documents_csv](../indexes/struct.Index.html#method.add_documents_csv)
+    /// and their variants are compressed, unless they are smaller than 1 KiB. The other requests are sent uncompressed.
+    /// The WASM http client can't compress the requests: these methods return
+    /// [Error::UnsupportedCompression](../errors/enum.Error.html#variant.UnsupportedCompression) instead, without sending anything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// let client = Client::new("http://localhost:7700", "masterKey").with_request_compression(Compression::Gzip);
+    /// ```
+    #[cfg(feature = "documents-io")]
+    pub fn with_request_compression(self, compression: Compression) -> Client {
+        Client { compression: Some(compression), ..self }
+    }
+
+    /// Get a handle on the indexes whose uid starts with `{prefix}_`, to isolate the indexes of a tenant for example.
+    ///
+    /// The prefix must only contain alphanumeric characters, hyphens (`-`) and underscores (`_`), otherwise [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) is returned.
+    /// The names of the indexes in the namespace can't contain underscores, so that the namespace `tenant` never includes
+    /// the indexes of the namespace `tenant_42`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let tenant = client.namespace("tenant_42").unwrap();
+    ///
+    /// assert_eq!(tenant.uid("movies").unwrap(), "tenant_42_movies");
+    /// let movies = tenant.index("movies").unwrap();
+    /// ```
+    pub fn namespace(&self, prefix: impl Into<String>) -> Result<IndexNamespace<'_>, Error> {
+        let prefix = prefix.into();
+        let valid = !prefix.is_empty()
+            && prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(Error::InvalidRequest);
+        }
+        Ok(IndexNamespace { client: self, prefix })
+    }
+
+    pub(crate) fn check_writable(&self, attempted: &'static str) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::ReadOnlyClient { attempted });
+        }
+        Ok(())
+    }
+
+    /// Drop the cached responses related to an index.
+    /// Has no effect if the cache is not [enabled](#method.with_cache).
+    pub fn invalidate_cache(&self, index_uid: impl AsRef<str>) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(&self.host, index_uid.as_ref());
         }
     }
 
@@ -34,7 +212,7 @@ impl Client {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// // create the client
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -44,27 +222,61 @@ impl Client {
     /// # });
     /// ```
     pub async fn list_all_indexes(&self) -> Result<Vec<Index>, Error> {
-        let json_indexes = request::<(), Vec<JsonIndex>>(
-            &format!("{}/indexes", self.host),
-            &self.api_key,
-            Method::Get,
-            200,
-        ).await?;
+        let raw_indexes = self.get_raw_indexes().await?;
 
         let mut indexes = Vec::new();
-        for json_index in json_indexes {
+        for raw_index in raw_indexes {
+            let json_index: JsonIndex = serde_json::from_value(Value::Object(raw_index)).map_err(Error::ParseError)?;
             indexes.push(json_index.into_index(self))
         }
 
         Ok(indexes)
     }
 
+    /// List all the indexes as raw JSON objects, containing every field returned by the server.
+    ///
+    /// This is useful to access index metadata that this version of the SDK doesn't know about.
+    /// See also [list_all_indexes](#method.list_all_indexes).
+    pub async fn get_raw_indexes(&self) -> Result<Vec<Map<String, Value>>, Error> {
+        request::<(), Vec<Map<String, Value>>>(
+            &format!("{}/indexes", self.host),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get an index as a raw JSON object, containing every field returned by the server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// # client.create_index("movies_raw", None).await;
+    ///
+    /// let movies = client.get_raw_index("movies_raw").await.unwrap();
+    /// assert_eq!(movies["uid"], "movies_raw");
+    /// # client.delete_index("movies_raw").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_raw_index(&self, uid: impl AsRef<str>) -> Result<Map<String, Value>, Error> {
+        cached_request::<Map<String, Value>>(
+            &self.cache,
+            &format!("{}/indexes/{}", self.host, uid.as_ref()),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
     /// Get an [index](../indexes/struct.Index.html).
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     ///
     /// # futures::executor::block_on(async move {
     /// // create the client
@@ -76,11 +288,11 @@ impl Client {
     /// # });
     /// ```
     pub async fn get_index(&self, uid: impl AsRef<str>) -> Result<Index, Error> {
-        Ok(request::<(), JsonIndex>(
+        Ok(cached_request::<JsonIndex>(
+            &self.cache,
             &format!("{}/indexes/{}", self.host, uid.as_ref()),
             &self.api_key,
-            Method::Get,
-            200,
+            self.max_response_bytes,
         ).await?
         .into_index(self))
     }
@@ -90,26 +302,38 @@ impl Client {
         Index {
             uid: Rc::new(uid.into()),
             host: Rc::clone(&self.host),
-            api_key: Rc::clone(&self.api_key)
+            api_key: Rc::clone(&self.api_key),
+            cache: self.cache.clone(),
+            waits: Rc::clone(&self.waits),
+            read_only: self.read_only,
+            max_response_bytes: self.max_response_bytes,
+            check_primary_key: self.check_primary_key,
+            check_vector_dimensions: self.check_vector_dimensions,
+            compression: self.compression,
         }
     }
 
+    /// Create a [batch of queries](../search/struct.MultiSearchQuery.html), possibly on different indexes, sent in a single request.
+    pub fn multi_search<'b>(&self) -> MultiSearchQuery<'_, 'b> {
+        MultiSearchQuery::new(self)
+    }
+
     /// Create an [index](../indexes/struct.Index.html).
     /// The second parameter will be used as the primary key of the new index. If it is not specified, MeiliSearch will **try** to infer the primary key.
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// // create the client
     /// let client = Client::new("http://localhost:7700", "masterKey");
     ///
-    /// # if let Ok(mut movies) = client.get_index("movies").await {
+    /// # if let Ok(mut movies) = client.get_index("movies_create_index").await {
     /// #   movies.delete().await.unwrap();
     /// # }
     /// // create a new index called movies and access it
-    /// let movies = client.create_index("movies", None).await;
+    /// let movies = client.create_index("movies_create_index", None).await;
     /// # });
     /// ```
     pub async fn create_index(
@@ -117,6 +341,8 @@ impl Client {
         uid: impl AsRef<str>,
         primary_key: Option<&str>,
     ) -> Result<Index, Error> {
+        self.check_writable("create_index")?;
+        self.invalidate_cache(uid.as_ref());
         Ok(request::<Value, JsonIndex>(
             &format!("{}/indexes", self.host),
             &self.api_key,
@@ -125,6 +351,7 @@ impl Client {
                 "primaryKey": primary_key,
             })),
             201,
+            self.max_response_bytes,
         ).await?
         .into_index(self))
     }
@@ -147,11 +374,14 @@ impl Client {
     /// Delete an index from its UID.
     /// To delete an index from the [index object](../indexes/struct.Index.html), use [the delete method](../indexes/struct.Index.html#method.delete).
     pub async fn delete_index(&self, uid: impl AsRef<str>) -> Result<(), Error> {
+        self.check_writable("delete_index")?;
+        self.invalidate_cache(uid.as_ref());
         Ok(request::<(), ()>(
             &format!("{}/indexes/{}", self.host, uid.as_ref()),
             &self.api_key,
             Method::Delete,
             204,
+            self.max_response_bytes,
         ).await?)
     }
 
@@ -174,7 +404,7 @@ impl Client {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -182,11 +412,11 @@ impl Client {
     /// # });
     /// ```
     pub async fn get_stats(&self) -> Result<ClientStats, Error> {
-        request::<serde_json::Value, ClientStats>(
+        cached_request::<ClientStats>(
+            &self.cache,
             &format!("{}/stats", self.host),
             &self.api_key,
-            Method::Get,
-            200,
+            self.max_response_bytes,
         ).await
     }
 
@@ -195,7 +425,7 @@ impl Client {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, errors::{Error, ErrorCode}};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -208,12 +438,16 @@ impl Client {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         )
         .await
     }
 
     /// Get health of MeiliSearch server, return true or false.
     ///
+    /// Returns `false` if the server is reachable but rejects the API key (MeiliSearch Cloud requires a valid key even on this route).
+    /// Use [health_status](#method.health_status) to tell the difference.
+    ///
     /// # Example
     ///
     /// ```
@@ -233,12 +467,85 @@ impl Client {
         }
     }
 
+    /// Get the health of the MeiliSearch server, distinguishing a server that is down from a server rejecting the API key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let status = client.health_status().await;
+    /// assert_eq!(status, HealthStatus::Available);
+    /// # });
+    /// ```
+    pub async fn health_status(&self) -> HealthStatus {
+        match self.health().await {
+            Ok(health) if health.status == "available" => HealthStatus::Available,
+            Ok(health) => HealthStatus::Unavailable(health.status),
+            Err(Error::MeiliSearchError { error_type: ErrorType::Authentication, .. }) => HealthStatus::Unauthorized,
+            Err(Error::MeiliSearchError { message, .. }) => HealthStatus::Unavailable(message),
+            Err(_) => HealthStatus::Unreachable,
+        }
+    }
+
+    /// Send a request to any route of the MeiliSearch server, for the routes this SDK doesn't cover yet.
+    ///
+    /// The request is sent like the other requests of the client: the `path` is appended to the host, the API key is sent, and the errors of MeiliSearch are parsed into [Error::MeiliSearchError].
+    /// The `query` is serialized to query parameters. It must be a map, such as a struct or a [HashMap], or `()` for no parameters.
+    /// Writes fail with [Error::ReadOnlyClient] on a [read-only client](#method.with_read_only). Requests other than `GET` are considered writes,
+    /// except for the searches (`indexes/{uid}/search`, `indexes/{uid}/facet-search`, `indexes/{uid}/similar` and `multi-search`) and `indexes/{uid}/documents/fetch`.
+    /// A write drops the [cached](#method.with_cache) responses of its index, or all of them if it isn't sent to a route of an index.
+    ///
+    /// Only the signature of this method is stable: the routes, their parameters and their responses are those of the server, and may change with its version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// # use serde_json::Value;
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let health: Value = client.raw_request("/health", Method::Get::<()>, (), 200).await.unwrap();
+    /// assert_eq!(health["status"], "available");
+    /// # });
+    /// ```
+    pub async fn raw_request<Q: Serialize, B: Serialize + std::fmt::Debug, O: 'static + DeserializeOwned>(
+        &self,
+        path: &str,
+        method: Method<B>,
+        query: Q,
+        expected_status: u16,
+    ) -> Result<O, Error> {
+        let path = path.trim_start_matches('/');
+        let route = path.split('?').next().unwrap_or_default();
+        let writing = !is_read_request(&method, route);
+        if writing {
+            self.check_writable("raw_request")?;
+        }
+        let parameters = query_parameters(&query)?;
+        let parameters: Vec<(&str, &str)> = parameters.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        let url = add_query_parameters(&format!("{}/{}", self.host, path), &parameters);
+
+        let result = request::<B, O>(&url, &self.api_key, method, expected_status, self.max_response_bytes).await;
+        // The request may have changed an index whose responses are cached
+        if let (true, Some(cache)) = (writing, &self.cache) {
+            match route.strip_prefix("indexes/").and_then(|route| route.split('/').next()) {
+                Some(uid) if !uid.is_empty() => cache.invalidate(&self.host, uid),
+                _ => cache.clear(),
+            }
+        }
+        result
+    }
+
     /// Get the private and public key.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, errors::Error};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -251,6 +558,7 @@ impl Client {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         ).await
     }
 
@@ -259,7 +567,7 @@ impl Client {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, errors::Error};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -272,8 +580,261 @@ impl Client {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         ).await
     }
+
+    /// Get version of the MeiliSearch server, or `None` if the API key is not allowed to read it.
+    ///
+    /// Useful to enable features depending on the version of the server without failing when the key lacks the `version` action.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// if let Some(version) = client.try_get_version().await.unwrap() {
+    ///     println!("Running MeiliSearch {}", version.pkg_version);
+    /// }
+    /// # });
+    /// ```
+    pub async fn try_get_version(&self) -> Result<Option<Version>, Error> {
+        match self.get_version().await {
+            Ok(version) => Ok(Some(version)),
+            Err(Error::MeiliSearchError { error_type: ErrorType::Authentication, .. }) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Check which [capabilities](enum.Capability.html) of the SDK are supported by the server.
+    ///
+    /// The version of the server decides whether a capability exists.
+    /// Capabilities that are still experimental in this version are then checked against the enabled experimental features.
+    /// If the API key can't read the experimental features, they are reported as [experimental](struct.CompatibilityReport.html#structfield.experimental).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, client::Capability};
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let report = client.check_compatibility(&[Capability::FacetSearch, Capability::MultiSearch]).await.unwrap();
+    /// println!("{}", serde_json::to_string(&report).unwrap());
+    /// # });
+    /// ```
+    pub async fn check_compatibility(&self, required: &[Capability]) -> Result<CompatibilityReport, Error> {
+        let version = self.get_version().await?;
+        let parsed = parse_version(&version.pkg_version)
+            .ok_or_else(|| Error::UnknownServerVersion { version: version.pkg_version.clone() })?;
+
+        let mut report = CompatibilityReport {
+            version: version.pkg_version,
+            available: Vec::new(),
+            missing: Vec::new(),
+            experimental: Vec::new(),
+        };
+        let mut features: Option<Map<String, Value>> = None;
+        for &capability in required {
+            if parsed < capability.since() {
+                report.missing.push(capability);
+                continue;
+            }
+            let flag = match capability.experimental_feature(parsed) {
+                Some(flag) => flag,
+                None => {
+                    report.available.push(capability);
+                    continue;
+                }
+            };
+            if features.is_none() {
+                features = Some(self.get_experimental_features().await?);
+            }
+            match features.as_ref().and_then(|features| features.get(flag)) {
+                Some(Value::Bool(true)) => report.available.push(capability),
+                _ => report.experimental.push(capability),
+            }
+        }
+        Ok(report)
+    }
+
+    /// The experimental features of the server, or an empty map if the API key is not allowed to read them.
+    async fn get_experimental_features(&self) -> Result<Map<String, Value>, Error> {
+        match request::<(), Map<String, Value>>(
+            &format!("{}/experimental-features", self.host),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await {
+            Ok(features) => Ok(features),
+            Err(Error::MeiliSearchError { error_type: ErrorType::Authentication, .. }) => Ok(Map::new()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// The major and minor numbers of a version like `1.7.0` or `1.7.0-rc.1`.
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut numbers = version.trim_start_matches('v').split(['.', '-']);
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// A feature of the SDK that is not supported by every version of MeiliSearch.
+/// See [Client::check_compatibility](struct.Client.html#method.check_compatibility).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Capability {
+    /// Searching several indexes in one request, with [multi_search](struct.Client.html#method.multi_search). Since 1.1.
+    MultiSearch,
+    /// Deleting the documents matching a filter, with [delete_documents_with](../indexes/struct.Index.html#method.delete_documents_with). Since 1.2.
+    DeleteDocumentsByFilter,
+    /// Searching the values of a facet. Since 1.3.
+    FacetSearch,
+    /// Searching with vectors and embedders. Since 1.3, behind the `vectorStore` experimental feature until 1.13.
+    VectorSearch,
+    /// The [separator tokens](../settings/struct.Settings.html#structfield.separator_tokens) setting. Since 1.4.
+    SeparatorTokensSetting,
+    /// Merging the results of a multi search. Since 1.10.
+    Federation,
+    /// Editing documents with a function. Since 1.10, behind the `editDocumentsByFunction` experimental feature.
+    EditDocumentsByFunction,
+    /// The prefix search setting. Since 1.12.
+    PrefixSearchSetting,
+}
+
+impl Capability {
+    /// All the capabilities, to check the full compatibility of a server.
+    pub const ALL: [Capability; 8] = [
+        Capability::MultiSearch,
+        Capability::DeleteDocumentsByFilter,
+        Capability::FacetSearch,
+        Capability::VectorSearch,
+        Capability::SeparatorTokensSetting,
+        Capability::Federation,
+        Capability::EditDocumentsByFunction,
+        Capability::PrefixSearchSetting,
+    ];
+
+    /// The first version (major, minor) supporting the capability.
+    pub(crate) fn since(self) -> (u32, u32) {
+        match self {
+            Capability::MultiSearch => (1, 1),
+            Capability::DeleteDocumentsByFilter => (1, 2),
+            Capability::FacetSearch => (1, 3),
+            Capability::VectorSearch => (1, 3),
+            Capability::SeparatorTokensSetting => (1, 4),
+            Capability::Federation => (1, 10),
+            Capability::EditDocumentsByFunction => (1, 10),
+            Capability::PrefixSearchSetting => (1, 12),
+        }
+    }
+
+    /// The experimental feature that must be enabled to use the capability with this version, if any.
+    fn experimental_feature(self, version: (u32, u32)) -> Option<&'static str> {
+        match self {
+            Capability::VectorSearch if version < (1, 13) => Some("vectorStore"),
+            Capability::EditDocumentsByFunction => Some("editDocumentsByFunction"),
+            _ => None,
+        }
+    }
+}
+
+/// The result of [Client::check_compatibility](struct.Client.html#method.check_compatibility).
+/// It can be serialized to be logged.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    /// The version of the server
+    pub version: String,
+    /// The capabilities that can be used
+    pub available: Vec<Capability>,
+    /// The capabilities that don't exist in this version
+    pub missing: Vec<Capability>,
+    /// The capabilities that exist in this version, but behind an experimental feature that is not enabled
+    pub experimental: Vec<Capability>,
+}
+
+impl CompatibilityReport {
+    /// Whether all the checked capabilities can be used.
+    pub fn is_compatible(&self) -> bool {
+        self.missing.is_empty() && self.experimental.is_empty()
+    }
+
+    /// Whether a capability was checked and can be used.
+    pub fn is_available(&self, capability: Capability) -> bool {
+        self.available.contains(&capability)
+    }
+}
+
+/// The indexes of a [Client](struct.Client.html) whose uid starts with a given prefix.
+/// Obtained with [Client::namespace](struct.Client.html#method.namespace).
+#[derive(Debug, Clone)]
+pub struct IndexNamespace<'a> {
+    client: &'a Client,
+    prefix: String,
+}
+
+impl<'a> IndexNamespace<'a> {
+    /// The prefix of the namespace (without the trailing `_`).
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The uid of an index in this namespace: `{prefix}_{name}`.
+    ///
+    /// The name must only contain alphanumeric characters and hyphens (`-`), otherwise [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) is returned.
+    pub fn uid(&self, name: impl AsRef<str>) -> Result<String, Error> {
+        let name = name.as_ref();
+        if !is_namespaced_name(name) {
+            return Err(Error::InvalidRequest);
+        }
+        Ok(format!("{}_{}", self.prefix, name))
+    }
+
+    /// Get an [Index](../indexes/struct.Index.html) of this namespace, without checking that it exists.
+    /// See [Client::assume_index](struct.Client.html#method.assume_index) and [uid](#method.uid).
+    pub fn index(&self, name: impl AsRef<str>) -> Result<Index, Error> {
+        Ok(self.client.assume_index(self.uid(name)?))
+    }
+
+    fn contains(&self, uid: &str) -> bool {
+        uid.strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_prefix('_'))
+            .map(is_namespaced_name)
+            .unwrap_or(false)
+    }
+
+    /// List the indexes of this namespace.
+    /// All the indexes are fetched and filtered by the SDK.
+    pub async fn list_indexes(&self) -> Result<Vec<Index>, Error> {
+        let mut indexes = self.client.list_all_indexes().await?;
+        indexes.retain(|index| self.contains(&index.uid));
+        Ok(indexes)
+    }
+
+    /// Delete all the indexes of this namespace, and return their uids.
+    ///
+    /// Fails without listing nor deleting anything if the client is [read-only](struct.Client.html#method.with_read_only).
+    pub async fn delete_all(&self) -> Result<Vec<String>, Error> {
+        self.client.check_writable("delete_all")?;
+
+        let mut deleted = Vec::new();
+        for index in self.list_indexes().await? {
+            self.client.delete_index(index.uid.as_str()).await?;
+            deleted.push(index.uid.to_string());
+        }
+        Ok(deleted)
+    }
+}
+
+/// Whether `name` can be the name of an index in a namespace: the underscores are reserved to the prefixes.
+fn is_namespaced_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
 }
 
 #[derive(Deserialize)]
@@ -289,7 +850,7 @@ pub struct ClientStats {
 /// Example:
 ///
 /// ```
-/// # use meilisearch_sdk::{client::*, indexes::*, errors::Error};
+/// # use meilisearch_sdk::client::*;
 /// Health {
 ///    status: "available".to_string(),
 /// };
@@ -299,6 +860,19 @@ pub struct Health {
     pub status: String,
 }
 
+/// The state of a MeiliSearch server, as returned by [health_status](struct.Client.html#method.health_status).
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthStatus {
+    /// The server is up and accepts the API key.
+    Available,
+    /// The server is reachable but rejects the API key.
+    Unauthorized,
+    /// The server is reachable but not available. Contains the status or the error message returned by the server.
+    Unavailable(String),
+    /// The server can't be reached.
+    Unreachable,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Keys {
@@ -311,14 +885,14 @@ pub struct Keys {
 /// Example:
 ///
 /// ```
-/// # use meilisearch_sdk::{client::*, indexes::*, errors::Error};
+/// # use meilisearch_sdk::client::*;
 /// Version {
 ///    commit_sha: "b46889b5f0f2f8b91438a08a358ba8f05fc09fc1".to_string(),
 ///    commit_date: "2019-11-15T09:51:54.278247+00:00".to_string(),
 ///    pkg_version: "0.1.1".to_string(),
 /// };
 /// ```
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Version {
     pub commit_sha: String,
@@ -328,9 +902,415 @@ pub struct Version {
 
 #[cfg(test)]
 mod tests {
-    use crate::{client::*};
+    use crate::{client::*, request::tests::mock_server, tests::{unique_uid, TestIndex}};
     use futures_await_test::async_test;
 
+    const CLOUD_UNAUTHORIZED: &str = r#"{"message":"The Authorization header is missing. It must use the bearer authorization method.","code":"missing_authorization_header","type":"auth","link":"https://docs.meilisearch.com/errors#missing_authorization_header"}"#;
+    const CLOUD_FORBIDDEN: &str = r#"{"message":"The provided API key is invalid.","code":"invalid_api_key","type":"auth","link":"https://docs.meilisearch.com/errors#invalid_api_key"}"#;
+
+    #[async_test]
+    async fn test_cloud_health_unauthorized() {
+        let (host, requests) = mock_server(vec![(401, CLOUD_UNAUTHORIZED), (200, r#"{"status":"available"}"#)]);
+        let client = Client::new(host, "cloudKey");
+
+        assert_eq!(client.health_status().await, HealthStatus::Unauthorized);
+        assert!(requests.recv().unwrap().contains("authorization: Bearer cloudKey"));
+        assert_eq!(client.health_status().await, HealthStatus::Available);
+    }
+
+    #[async_test]
+    async fn test_health_unreachable() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = Client::new(format!("http://127.0.0.1:{}", port), "masterKey");
+        assert_eq!(client.health_status().await, HealthStatus::Unreachable);
+    }
+
+    #[async_test]
+    async fn test_cloud_version_forbidden() {
+        let (host, _requests) = mock_server(vec![(403, CLOUD_FORBIDDEN), (403, CLOUD_FORBIDDEN)]);
+        let client = Client::new(host, "searchOnlyKey");
+
+        assert!(client.try_get_version().await.unwrap().is_none());
+        let error = client.get_version().await.unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MeiliSearchError { error_code: ErrorCode::InvalidApiKey, error_type: ErrorType::Authentication, .. }
+        ));
+        assert!(error.to_string().contains("`version` action"));
+    }
+
+    #[async_test]
+    async fn test_cloud_search_forbidden() {
+        let (host, _requests) = mock_server(vec![(403, CLOUD_FORBIDDEN)]);
+        let client = Client::new(host, "documentsOnlyKey");
+        let index = client.assume_index("movies");
+
+        let error = index.search().with_query("space").execute::<serde_json::Value>().await.unwrap_err();
+        assert!(error.to_string().contains("`search` action"));
+    }
+
+    #[async_test]
+    async fn test_raw_request() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Query {
+            limit: usize,
+            fields: Vec<&'static str>,
+            before_date: Option<&'static str>,
+        }
+
+        const INDEX_NOT_FOUND: &str = r#"{"message":"Index unknown not found","code":"index_not_found","type":"invalid_request","link":""}"#;
+        let (host, requests) = mock_server(vec![(200, r#"{"results":[]}"#), (202, r#"{"updateId":1}"#), (404, INDEX_NOT_FOUND)]);
+        let client = Client::new(host, "masterKey");
+
+        let query = Query { limit: 2, fields: vec!["uid", "status"], before_date: None };
+        let tasks: serde_json::Value = client.raw_request("/tasks", Method::Get::<()>, query, 200).await.unwrap();
+        assert_eq!(tasks, serde_json::json!({"results": []}));
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /tasks?fields=uid%2Cstatus&limit=2 "));
+        assert!(request.contains("authorization: Bearer masterKey"));
+
+        let update: serde_json::Value =
+            client.raw_request("indexes/movies/compact", Method::Post(serde_json::json!({})), (), 202).await.unwrap();
+        assert_eq!(update["updateId"], 1);
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/compact "));
+
+        let error = client.raw_request::<_, (), serde_json::Value>("/indexes/unknown", Method::Delete, (), 204).await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { error_code: ErrorCode::IndexNotFound, .. }));
+        assert!(requests.recv().unwrap().starts_with("DELETE /indexes/unknown "));
+
+        // Nothing is sent for an invalid query or a write on a read-only client
+        assert!(matches!(client.raw_request::<_, (), serde_json::Value>("/tasks", Method::Get, [1, 2], 200).await, Err(Error::InvalidRequest)));
+        let client = client.with_read_only(true);
+        let error = client.raw_request::<_, (), serde_json::Value>("/indexes/movies", Method::Delete, (), 204).await.unwrap_err();
+        assert!(matches!(error, Error::ReadOnlyClient { attempted: "raw_request" }));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_raw_request_search_on_read_only_client() {
+        let (host, requests) = mock_server(vec![(200, r#"{"hits":[],"query":"wonder"}"#), (200, r#"{"results":[]}"#)]);
+        let client = Client::new(host, "masterKey").with_read_only(true);
+
+        let results: serde_json::Value =
+            client.raw_request("/indexes/movies/search", Method::Post(serde_json::json!({"q": "wonder"})), (), 200).await.unwrap();
+        assert_eq!(results["query"], "wonder");
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/search "));
+
+        let results: serde_json::Value =
+            client.raw_request("/multi-search", Method::Post(serde_json::json!({"queries": []})), (), 200).await.unwrap();
+        assert_eq!(results["results"], serde_json::json!([]));
+        assert!(requests.recv().unwrap().starts_with("POST /multi-search "));
+    }
+
+    #[async_test]
+    async fn test_raw_request_invalidates_cache() {
+        let (host, requests) = mock_server(vec![(200, r#"["the"]"#), (202, r#"{"updateId":1}"#), (200, r#"["of"]"#)]);
+        let client = Client::new(host, "masterKey").with_cache(std::time::Duration::from_secs(60), 16);
+        let index = client.assume_index("movies");
+
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings/stop-words "));
+
+        // The query string is not part of the uid of the index
+        let _: serde_json::Value =
+            client.raw_request("/indexes/movies/documents?primaryKey=id", Method::Post(serde_json::json!([])), (), 202).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/documents?primaryKey=id "));
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings/stop-words "));
+    }
+
+    #[async_test]
+    async fn test_raw_request_cache_outside_indexes() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"["the"]"#),
+            (200, r#"{"hits":[]}"#),
+            (202, r#"{"taskUid":1}"#),
+            (200, r#"["of"]"#),
+        ]);
+        let client = Client::new(host, "masterKey").with_cache(std::time::Duration::from_secs(60), 16);
+        let index = client.assume_index("movies");
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+
+        // A search doesn't drop the cached responses
+        let _: serde_json::Value =
+            client.raw_request("/indexes/movies/search", Method::Post(serde_json::json!({})), (), 200).await.unwrap();
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+
+        // A write to another route may change any index
+        let _: serde_json::Value =
+            client.raw_request("/swap-indexes", Method::Post(serde_json::json!([])), (), 202).await.unwrap();
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+        assert_eq!(requests.try_iter().count(), 4);
+    }
+
+    #[async_test]
+    async fn test_raw_request_health() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let raw: serde_json::Value = client.raw_request("/health", Method::Get::<()>, (), 200).await.unwrap();
+        let typed = client.health().await.unwrap();
+        assert_eq!(raw["status"], typed.status);
+    }
+
+    const VERSION_1_7: &str = r#"{"commitSha":"a1b2c3d","commitDate":"2024-03-11T14:09:40Z","pkgVersion":"1.7.0"}"#;
+
+    #[async_test]
+    async fn test_check_compatibility() {
+        let (host, requests) = mock_server(vec![
+            (200, VERSION_1_7),
+            (200, r#"{"vectorStore":false,"metrics":false,"logsRoute":false}"#),
+        ]);
+        let client = Client::new(host, "masterKey");
+
+        let report = client.check_compatibility(&Capability::ALL).await.unwrap();
+        assert!(requests.recv().unwrap().contains("GET /version"));
+        assert!(requests.recv().unwrap().contains("GET /experimental-features"));
+        assert_eq!(report.version, "1.7.0");
+        assert_eq!(report.available, [Capability::MultiSearch, Capability::DeleteDocumentsByFilter, Capability::FacetSearch, Capability::SeparatorTokensSetting]);
+        assert_eq!(report.missing, [Capability::Federation, Capability::EditDocumentsByFunction, Capability::PrefixSearchSetting]);
+        assert_eq!(report.experimental, [Capability::VectorSearch]);
+        assert!(!report.is_compatible());
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "version": "1.7.0",
+                "available": ["multiSearch", "deleteDocumentsByFilter", "facetSearch", "separatorTokensSetting"],
+                "missing": ["federation", "editDocumentsByFunction", "prefixSearchSetting"],
+                "experimental": ["vectorSearch"],
+            })
+        );
+    }
+
+    #[async_test]
+    async fn test_check_compatibility_experimental_enabled() {
+        let (host, _requests) = mock_server(vec![(200, VERSION_1_7), (200, r#"{"vectorStore":true}"#)]);
+        let client = Client::new(host, "masterKey");
+
+        let report = client.check_compatibility(&[Capability::VectorSearch, Capability::FacetSearch]).await.unwrap();
+        assert_eq!(report.available, [Capability::VectorSearch, Capability::FacetSearch]);
+        assert!(report.is_compatible());
+    }
+
+    #[async_test]
+    async fn test_check_compatibility_without_probe() {
+        // The experimental features are not requested when no capability needs them
+        let (host, _requests) = mock_server(vec![(200, VERSION_1_7)]);
+        let client = Client::new(host, "masterKey");
+
+        let report = client.check_compatibility(&[Capability::MultiSearch, Capability::Federation]).await.unwrap();
+        assert!(report.is_available(Capability::MultiSearch));
+        assert_eq!(report.missing, [Capability::Federation]);
+
+        let (host, _requests) = mock_server(vec![(200, r#"{"commitSha":"","commitDate":"","pkgVersion":"nightly"}"#)]);
+        let error = Client::new(host, "masterKey").check_compatibility(&Capability::ALL).await.unwrap_err();
+        assert!(matches!(error, Error::UnknownServerVersion { version } if version == "nightly"));
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.7.0"), Some((1, 7)));
+        assert_eq!(parse_version("1.12.3-rc.1"), Some((1, 12)));
+        assert_eq!(parse_version("v1.6"), Some((1, 6)));
+        assert_eq!(parse_version("nightly"), None);
+    }
+
+    #[async_test]
+    async fn test_max_response_bytes() {
+        const DOCUMENTS: &str = r#"[{"id":1},{"id":2},{"id":3},{"id":4},{"id":5},{"id":6},{"id":7},{"id":8}]"#;
+        let (host, _requests) = mock_server(vec![(200, DOCUMENTS), (200, DOCUMENTS)]);
+
+        // The limit is inherited by the indexes
+        let index = Client::new(host.as_str(), "masterKey").with_max_response_bytes(32).assume_index("movies");
+        let error = index.get_documents::<Movie>(None, None, None).await.unwrap_err();
+        assert!(matches!(error, Error::ResponseTooLarge { limit: 32, .. }));
+        assert_eq!(error.code(), "response_too_large");
+
+        let index = Client::new(host.as_str(), "masterKey").assume_index("movies");
+        assert_eq!(index.get_documents::<Movie>(None, None, None).await.unwrap().len(), 8);
+    }
+
+    #[async_test]
+    async fn test_api_key_override() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"{"hits":[],"processingTimeMs":0,"query":""}"#),
+            (200, r#"{"hits":[],"processingTimeMs":0,"query":""}"#),
+            (200, "[]"),
+            (200, "[]"),
+        ]);
+        let client = Client::new(host, "searchKey").with_cache(std::time::Duration::from_secs(60), 16);
+        let index = client.assume_index("movies");
+
+        index.search().with_api_key_override("adminKey").execute::<serde_json::Value>().await.unwrap();
+        assert!(requests.recv().unwrap().contains("authorization: Bearer adminKey"));
+        index.search().execute::<serde_json::Value>().await.unwrap();
+        assert!(requests.recv().unwrap().contains("authorization: Bearer searchKey"));
+
+        index.with_key("adminKey").get_documents::<Movie>(None, None, None).await.unwrap();
+        assert!(requests.recv().unwrap().contains("authorization: Bearer adminKey"));
+        index.get_documents::<Movie>(None, None, None).await.unwrap();
+        assert!(requests.recv().unwrap().contains("authorization: Bearer searchKey"));
+    }
+
+    #[async_test]
+    async fn test_with_key_shares_cache_invalidation() {
+        let (host, requests) = mock_server(vec![(200, r#"["the"]"#), (200, r#"["a"]"#), (202, r#"{"updateId":1}"#), (200, r#"["of"]"#)]);
+        let client = Client::new(host, "searchKey").with_cache(std::time::Duration::from_secs(60), 16);
+        let index = client.assume_index("movies");
+        let admin = index.with_key("adminKey");
+
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+        assert!(requests.recv().unwrap().contains("authorization: Bearer searchKey"));
+        // The response cached for the key of the client is not served to the other key
+        assert_eq!(admin.get_stop_words().await.unwrap(), vec!["a"]);
+        assert!(requests.recv().unwrap().contains("authorization: Bearer adminKey"));
+
+        // A write with the other key drops the cached responses of the client
+        admin.set_stop_words(["of"]).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/settings/stop-words "));
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+        assert!(requests.recv().unwrap().contains("authorization: Bearer searchKey"));
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct Movie {
+        id: usize,
+    }
+
+    impl crate::document::Document for Movie {
+        type UIDType = usize;
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    fn blocked<T>(result: Result<T, Error>) -> Option<&'static str> {
+        match result {
+            Err(Error::ReadOnlyClient { attempted }) => Some(attempted),
+            _ => None,
+        }
+    }
+
+    #[async_test]
+    async fn test_read_only_blocked_methods() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = Client::new(format!("http://127.0.0.1:{}", port), "masterKey").with_read_only(true);
+        let index = client.assume_index("movies");
+
+        assert_eq!(blocked(client.create_index("movies", None).await), Some("create_index"));
+        assert_eq!(blocked(client.get_or_create("movies").await), Some("create_index"));
+        assert_eq!(blocked(client.delete_index("movies").await), Some("delete_index"));
+        assert_eq!(blocked(client.delete_index_if_exists("movies").await), Some("delete_index"));
+        assert_eq!(blocked(index.update("id").await), Some("update"));
+        assert_eq!(blocked(index.set_primary_key("id").await), Some("set_primary_key"));
+        assert_eq!(blocked(client.assume_index("movies").delete().await), Some("delete"));
+        assert_eq!(blocked(index.add_documents::<Movie>(&[], None).await), Some("add_documents"));
+        assert_eq!(blocked(index.add_or_update::<Movie>(&[], None::<&str>).await), Some("add_or_update"));
+        assert_eq!(blocked(index.delete_document(1).await), Some("delete_document"));
+        assert_eq!(blocked(index.delete_documents(&[1, 2]).await), Some("delete_documents"));
+        assert_eq!(blocked(index.delete_all_documents_and_wait(None, None).await), Some("delete_all_documents"));
+        assert_eq!(blocked(index.set_settings(&crate::settings::Settings::new()).await), Some("set_settings"));
+        assert_eq!(blocked(index.set_stop_words(["the"]).await), Some("set_stop_words"));
+        assert_eq!(blocked(index.reset_settings().await), Some("reset_settings"));
+        assert_eq!(blocked(index.reset_displayed_attributes().await), Some("reset_displayed_attributes"));
+    }
+
+    #[async_test]
+    async fn test_read_only_allowed_methods() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = Client::new(format!("http://127.0.0.1:{}", port), "masterKey").with_read_only(true);
+        let index = client.assume_index("movies");
+
+        // The requests are sent, and fail because nothing is listening
+        assert!(matches!(client.get_index("movies").await, Err(Error::UnreachableServer)));
+        assert!(matches!(client.list_all_indexes().await, Err(Error::UnreachableServer)));
+        assert!(matches!(client.get_stats().await, Err(Error::UnreachableServer)));
+        assert!(matches!(client.get_version().await, Err(Error::UnreachableServer)));
+        assert!(matches!(index.search().with_query("space").execute::<serde_json::Value>().await, Err(Error::UnreachableServer)));
+        assert!(matches!(client.multi_search().with_search_query(index.search().build()).execute::<serde_json::Value>().await, Err(Error::UnreachableServer)));
+        assert!(matches!(index.get_documents::<Movie>(None, None, None).await, Err(Error::UnreachableServer)));
+        assert!(matches!(index.get_settings().await, Err(Error::UnreachableServer)));
+        assert!(matches!(index.get_stats().await, Err(Error::UnreachableServer)));
+    }
+
+    #[test]
+    fn test_namespace_prefix() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        assert!(matches!(client.namespace(""), Err(Error::InvalidRequest)));
+        assert!(matches!(client.namespace("tenant 42"), Err(Error::InvalidRequest)));
+        assert!(matches!(client.namespace("tenant/42"), Err(Error::InvalidRequest)));
+
+        let tenant = client.namespace("tenant_42").unwrap();
+        assert_eq!(tenant.uid("movies").unwrap(), "tenant_42_movies");
+        assert_eq!(tenant.index("movies").unwrap().uid.as_str(), "tenant_42_movies");
+        assert!(tenant.contains("tenant_42_movies"));
+        assert!(tenant.contains("tenant_42_tv-shows"));
+        assert!(!tenant.contains("tenant_420_movies"));
+        assert!(!tenant.contains("tenant_42"));
+        assert!(!tenant.contains("tenant_42_"));
+
+        // The names can't contain the separator
+        assert!(matches!(tenant.uid("tv_shows"), Err(Error::InvalidRequest)));
+        assert!(matches!(tenant.index(""), Err(Error::InvalidRequest)));
+
+        // A prefix of another prefix doesn't see the indexes of the other namespace
+        let parent = client.namespace("tenant").unwrap();
+        assert!(parent.contains("tenant_movies"));
+        assert!(!parent.contains("tenant_42_movies"));
+        assert!(!tenant.contains("tenant_movies"));
+    }
+
+    #[async_test]
+    async fn test_namespace_delete_all_read_only() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = Client::new(format!("http://127.0.0.1:{}", port), "masterKey").with_read_only(true);
+        let tenant = client.namespace("tenant_42").unwrap();
+
+        assert_eq!(blocked(tenant.delete_all().await), Some("delete_all"));
+    }
+
+    #[async_test]
+    async fn test_namespace_isolation() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        // The first namespace is a prefix of the second
+        let prefix = unique_uid("test_namespace");
+        let first = client.namespace(prefix.as_str()).unwrap();
+        let second = client.namespace(format!("{}_1", prefix)).unwrap();
+        let _first_movies = TestIndex::with_uid(&client, first.uid("movies").unwrap()).await;
+        let _first_books = TestIndex::with_uid(&client, first.uid("books").unwrap()).await;
+        let _second_movies = TestIndex::with_uid(&client, second.uid("movies").unwrap()).await;
+
+        let expected = vec![first.uid("books").unwrap(), first.uid("movies").unwrap()];
+        let mut uids: Vec<String> = first.list_indexes().await.unwrap().iter().map(|index| index.uid.to_string()).collect();
+        uids.sort();
+        assert_eq!(uids, expected);
+
+        let mut deleted = first.delete_all().await.unwrap();
+        deleted.sort();
+        assert_eq!(deleted, expected);
+        assert!(first.list_indexes().await.unwrap().is_empty());
+        assert_eq!(second.list_indexes().await.unwrap().len(), 1);
+    }
+
+    #[async_test]
+    async fn test_get_raw_index() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_get_raw_index").await;
+        index.set_primary_key("id").await.unwrap();
+
+        let raw_index = client.get_raw_index(index.uid.as_str()).await.unwrap();
+        for field in ["uid", "createdAt", "updatedAt", "primaryKey"].iter() {
+            assert!(raw_index.contains_key(*field), "missing {}", field);
+        }
+        assert_eq!(raw_index["primaryKey"], "id");
+
+        let raw_indexes = client.get_raw_indexes().await.unwrap();
+        assert!(raw_indexes.iter().any(|raw_index| raw_index["uid"] == index.uid.as_str()));
+        // Other tests may create or delete indexes meanwhile, so the lists can't be compared
+        assert!(client.list_all_indexes().await.unwrap().iter().any(|listed| listed.uid == index.uid));
+    }
+
     #[async_test]
     async fn test_get_keys() {
         let client = Client::new("http://localhost:7700", "masterKey");
@@ -340,8 +1320,8 @@ mod tests {
     #[async_test]
     async fn test_delete_if_exits() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index_name = "movies_delete_if_exists";
-        client.create_index(index_name, None).await.unwrap();
+        let test_index = TestIndex::new(&client, "test_delete_if_exists").await;
+        let index_name = test_index.uid.as_str();
         let mut index = client.get_index(index_name).await;
         assert!(index.is_ok());
         let deleted = client.delete_index_if_exists(index_name).await.unwrap();
@@ -350,6 +1330,24 @@ mod tests {
         assert!(index.is_err());
     }
 
+    #[async_test]
+    async fn test_cache_invalidated_on_write() {
+        let client = Client::new("http://localhost:7700", "masterKey")
+            .with_cache(std::time::Duration::from_secs(60), 16);
+        let index = TestIndex::new(&client, "test_cache_invalidated_on_write").await;
+
+        index.set_stop_words(["the"]).await.unwrap()
+            .wait_for_pending_update(None, None).await.unwrap().unwrap();
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+
+        // Served from the cache
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+
+        index.set_stop_words(["of"]).await.unwrap()
+            .wait_for_pending_update(None, None).await.unwrap().unwrap();
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+    }
+
     #[async_test]
     async fn test_delete_if_exits_none() {
         let client = Client::new("http://localhost:7700", "masterKey");
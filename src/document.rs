@@ -1,10 +1,12 @@
-use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Display;
+use crate::errors::Error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::{BTreeMap, HashMap}, fmt::Display};
 
 /// Documents are not a predefined structure.
 /// You can use your structs as documents by implementing that trait.
 ///
-/// **WARNING**! The get_uid() method **MUST** only return an object that displays himself only using alphanumeric characters, '/' and '-'.
+/// **WARNING**! The get_uid() method **MUST** only return an object that displays himself only using alphanumeric characters, '-' and '_'.
 /// Otherwise, the MeiliSearch server will reject your documents.
 ///
 /// *To be able to use derive with serde, put this line on your Cargo.toml: `serde = {version="1.0", features=["derive"]}`.*
@@ -36,7 +38,183 @@ pub trait Document: DeserializeOwned + std::fmt::Debug + Serialize {
 
     /// The method returning the primary key of the Document.
     ///
-    /// **WARNING**! This method **MUST** only return an object that displays himself only using alphanumeric characters, '/' and '-'.
-    /// Otherwise, the MeiliSearch server will reject your document.
+    /// **WARNING**! This method **MUST** only return an object that displays himself only using alphanumeric characters, '-' and '_'.
+    /// Otherwise, the MeiliSearch server will reject your document, and the methods taking a document id return
+    /// [Error::InvalidDocumentId](../errors/enum.Error.html#variant.InvalidDocumentId).
     fn get_uid(&self) -> &Self::UIDType;
+
+    /// The name of the field holding the primary key, sent by [add_documents_with_uid](../indexes/struct.Index.html#method.add_documents_with_uid).
+    ///
+    /// Without it, MeiliSearch infers the primary key of an index from its first documents.
+    const PRIMARY_KEY: Option<&'static str> = None;
+}
+
+/// The vectors of a document, by embedder name, to send in its `_vectors` field.
+///
+/// The vectors are needed for the embedders with a `userProvided` [source](../settings/enum.Embedder.html#variant.UserProvided),
+/// and can replace the ones generated by the other embedders.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::document::{Document, Vectors};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Movie {
+///     id: usize,
+///     title: String,
+///     #[serde(rename = "_vectors")]
+///     vectors: Vectors,
+/// }
+///
+/// let movie = Movie {
+///     id: 1,
+///     title: String::from("Interstellar"),
+///     vectors: Vectors::new().with("default", vec![0.1, 0.2, 0.3]),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&movie).unwrap(),
+///     r#"{"id":1,"title":"Interstellar","_vectors":{"default":[0.1,0.2,0.3]}}"#
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Vectors(BTreeMap<String, EmbedderVectors>);
+
+/// The vectors of a document for one embedder, in one of the forms accepted by MeiliSearch.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum EmbedderVectors {
+    /// A single vector: `[0.1, 0.2]`
+    Vector(Vec<f32>),
+    /// Several vectors: `[[0.1, 0.2], [0.3, 0.4]]`
+    Vectors(Vec<Vec<f32>>),
+    /// Vectors along with whether MeiliSearch must generate them again when the document changes:
+    /// `{"embeddings": [[0.1, 0.2]], "regenerate": false}`
+    Embeddings {
+        /// The vectors
+        embeddings: Vec<Vec<f32>>,
+        /// Whether the vectors are generated again by the embedder when the document changes
+        regenerate: bool,
+    },
+}
+
+impl EmbedderVectors {
+    fn as_slices(&self) -> Vec<&[f32]> {
+        match self {
+            EmbedderVectors::Vector(vector) => vec![vector.as_slice()],
+            EmbedderVectors::Vectors(vectors) | EmbedderVectors::Embeddings { embeddings: vectors, .. } => {
+                vectors.iter().map(Vec::as_slice).collect()
+            }
+        }
+    }
+}
+
+#[allow(missing_docs)]
+impl Vectors {
+    /// Create vectors for no embedder
+    pub fn new() -> Vectors {
+        Vectors::default()
+    }
+    pub fn with(self, embedder: impl Into<String>, vector: Vec<f32>) -> Vectors {
+        self.with_vectors_of(embedder, EmbedderVectors::Vector(vector))
+    }
+    pub fn with_many(self, embedder: impl Into<String>, vectors: Vec<Vec<f32>>) -> Vectors {
+        self.with_vectors_of(embedder, EmbedderVectors::Vectors(vectors))
+    }
+    pub fn with_embeddings(self, embedder: impl Into<String>, embeddings: Vec<Vec<f32>>, regenerate: bool) -> Vectors {
+        self.with_vectors_of(embedder, EmbedderVectors::Embeddings { embeddings, regenerate })
+    }
+    pub fn with_vectors_of(mut self, embedder: impl Into<String>, vectors: EmbedderVectors) -> Vectors {
+        self.0.insert(embedder.into(), vectors);
+        self
+    }
+
+    /// The vectors of an embedder, if any
+    pub fn get(&self, embedder: &str) -> Option<&EmbedderVectors> {
+        self.0.get(embedder)
+    }
+
+    /// Returns `true` if there is no vector for any embedder
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Check that all the vectors of an embedder have the same number of dimensions in a batch of documents.
+///
+/// The vectors are read from the serialized `_vectors` field of the documents, whether it comes from [Vectors] or not.
+/// Entries that are not vectors, such as `null`, are left for MeiliSearch to validate.
+pub(crate) fn check_vector_dimensions<T: Serialize>(documents: &[T]) -> Result<(), Error> {
+    let mut dimensions: HashMap<String, usize> = HashMap::new();
+    for document in documents {
+        let vectors = match serde_json::to_value(document).map_err(Error::ParseError)? {
+            Value::Object(mut document) => match document.remove("_vectors") {
+                Some(vectors) => vectors,
+                None => continue,
+            },
+            _ => continue,
+        };
+        let vectors = match vectors {
+            Value::Object(vectors) => vectors,
+            _ => continue,
+        };
+        for (embedder, vectors) in vectors {
+            let vectors: EmbedderVectors = match serde_json::from_value(vectors) {
+                Ok(vectors) => vectors,
+                Err(_) => continue,
+            };
+            for vector in vectors.as_slices() {
+                let expected = *dimensions.entry(embedder.clone()).or_insert(vector.len());
+                if vector.len() != expected {
+                    return Err(Error::VectorDimensionMismatch { embedder: embedder.clone(), expected, found: vector.len() });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_vectors_wire_forms() {
+        let vectors = Vectors::new()
+            .with("default", vec![0.5, 1.0])
+            .with_many("images", vec![vec![0.25, 0.5], vec![1.0, 2.0]])
+            .with_embeddings("manual", vec![vec![3.0, 4.0]], false);
+        let json = json!({
+            "default": [0.5, 1.0],
+            "images": [[0.25, 0.5], [1.0, 2.0]],
+            "manual": {"embeddings": [[3.0, 4.0]], "regenerate": false}
+        });
+        assert_eq!(serde_json::to_value(&vectors).unwrap(), json);
+        assert_eq!(serde_json::from_value::<Vectors>(json).unwrap(), vectors);
+        assert_eq!(serde_json::to_string(&Vectors::new().with("default", vec![0.1, 0.2])).unwrap(), r#"{"default":[0.1,0.2]}"#);
+        assert_eq!(vectors.get("manual"), Some(&EmbedderVectors::Embeddings { embeddings: vec![vec![3.0, 4.0]], regenerate: false }));
+        assert!(Vectors::new().is_empty());
+    }
+
+    #[test]
+    fn test_check_vector_dimensions() {
+        let documents = [
+            json!({"id": 1, "_vectors": {"default": [0.5, 1.0], "images": [[1.0, 2.0, 3.0]]}}),
+            json!({"id": 2, "_vectors": {"default": {"embeddings": [[0.5, 1.5]], "regenerate": false}}}),
+            json!({"id": 3, "_vectors": {"images": null, "default": [1.0, 2.0]}}),
+            json!({"id": 4}),
+        ];
+        check_vector_dimensions(&documents).unwrap();
+
+        let documents = [
+            json!({"id": 1, "_vectors": {"default": [0.5, 1.0]}}),
+            json!({"id": 2, "_vectors": {"default": [[0.5, 1.0], [0.5, 1.0, 1.5]]}}),
+        ];
+        let error = check_vector_dimensions(&documents).unwrap_err();
+        assert!(matches!(error, Error::VectorDimensionMismatch { ref embedder, expected: 2, found: 3 } if embedder == "default"));
+        assert_eq!(error.to_string(), "The vectors of the embedder `default` have 2 dimensions, but one of them has 3.");
+    }
 }
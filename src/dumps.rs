@@ -91,6 +91,7 @@ impl Client {
             &self.api_key,
             Method::Post(()),
             202,
+            self.max_response_bytes,
         )
         .await
     }
@@ -118,6 +119,7 @@ impl Client {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         )
         .await
     }
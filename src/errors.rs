@@ -1,3 +1,5 @@
+use crate::client::{Capability, Compression};
+
 /// An enum representing the errors that can occur.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -21,8 +23,113 @@ pub enum Error {
     /// The MeiliSearch server returned invalid JSON for a request.
     ParseError(serde_json::Error),
     /// This MeiliSearch SDK generated an invalid request (which was not sent).
-    /// It probably comes from an invalid API key resulting in an invalid HTTP header.
+    /// It probably comes from an invalid API key resulting in an invalid HTTP header,
+    /// or from a search parameter that is out of its valid range.
     InvalidRequest,
+    /// MeiliSearch didn't process the update before the timeout.
+    Timeout,
+    /// A request took longer than its [timeout](../search/struct.Query.html#method.with_request_timeout).
+    RequestTimeout {
+        /// The url of the request
+        url: String,
+        /// The timeout of the request
+        timeout: std::time::Duration,
+    },
+    /// The response of the server was larger than the [limit of the client](../client/struct.Client.html#method.with_max_response_bytes).
+    /// It was dropped without being fully read.
+    ResponseTooLarge {
+        /// The maximum size of a response, in bytes
+        limit: usize,
+        /// The url of the request
+        url: String,
+        /// The size announced by the server, or the number of bytes read before giving up when the size wasn't announced
+        observed: usize,
+    },
+    /// Documents were sent with another primary key than the one of the index, with the [check](../client/struct.Client.html#method.with_primary_key_check) enabled.
+    /// Nothing was sent.
+    PrimaryKeyMismatch {
+        /// The primary key of the index
+        index_key: String,
+        /// The primary key passed with the documents
+        provided: String,
+    },
+    /// The document has no vector for the requested embedder, so [similar documents](../indexes/struct.Index.html#method.recommend_for) can't be searched.
+    MissingVector {
+        /// The id of the document
+        document_id: String,
+        /// The name of the embedder
+        embedder: String,
+    },
+    /// The vectors of an embedder don't all have the same number of dimensions in a batch of documents,
+    /// with the [check](../client/struct.Client.html#method.with_vector_dimension_check) enabled. Nothing was sent.
+    VectorDimensionMismatch {
+        /// The name of the embedder
+        embedder: String,
+        /// The number of dimensions of the first vector of the embedder
+        expected: usize,
+        /// The number of dimensions of the mismatching vector
+        found: usize,
+    },
+    /// A search parameter can't be sent with a [GET request](../search/enum.SearchMethod.html#variant.Get). Nothing was sent.
+    UnsupportedGetParameter {
+        /// The name of the parameter, as sent to MeiliSearch
+        parameter: String,
+    },
+    /// The status of an update couldn't be checked, even after [retrying](../progress/struct.WaitPolicy.html#structfield.max_poll_retries).
+    /// The update itself may still succeed.
+    UpdateStatusUnavailable {
+        /// The id of the update that was waited for
+        update_id: u64,
+        /// The number of failed attempts to get the status of the update
+        attempts: usize,
+        /// The error of the last attempt
+        last_error: Box<Error>,
+    },
+    /// The version of the server couldn't be parsed, so its [compatibility](../client/struct.Client.html#method.check_compatibility) can't be checked.
+    UnknownServerVersion {
+        /// The version returned by the server
+        version: String,
+    },
+    /// The key of a [stable pagination](../search/struct.Query.html#method.execute_stable_pages) is not filterable and sortable,
+    /// or is missing from a result. Nothing was searched in the first case.
+    InvalidPaginationKey {
+        /// The key the results are sorted by
+        key: String,
+    },
+    /// The server doesn't know the route of a method, because it is older than the version introducing the [capability](../client/enum.Capability.html).
+    UnsupportedCapability {
+        /// The capability the method needs
+        capability: Capability,
+    },
+    /// A write operation was attempted with a [read-only client](../client/struct.Client.html#method.with_read_only). Nothing was sent.
+    ReadOnlyClient {
+        /// The name of the method that was called
+        attempted: &'static str,
+    },
+    /// Some placeholders of a [search template](../search/struct.SearchTemplate.html) have no value. Nothing was rendered.
+    MissingTemplateParameters {
+        /// The names of the placeholders without a value, from `q`, then `filter`, then `sort` of the template
+        names: Vec<String>,
+    },
+    /// A document id is empty, longer than 511 bytes, or contains other characters than alphanumeric characters, hyphens (`-`) and underscores (`_`).
+    /// Nothing was sent.
+    InvalidDocumentId {
+        /// The id, as displayed
+        id: String,
+    },
+    /// The [compression](../client/struct.Client.html#method.with_request_compression) of the requests isn't supported by the http client,
+    /// which is the case of the WASM one. Nothing was sent.
+    UnsupportedCompression {
+        /// The compression of the client
+        compression: Compression,
+    },
+    /// The body of a request couldn't be [compressed](../client/struct.Client.html#method.with_request_compression). Nothing was sent.
+    CompressionFailed {
+        /// The compression of the client
+        compression: Compression,
+        /// The error of the encoder
+        error: std::io::Error,
+    },
 
     /// The http client encountered an error.
     #[cfg(not(target_arch = "wasm32"))]
@@ -89,6 +196,8 @@ pub enum ErrorCode {
     InternalError,
     /// The provided token is invalid.
     InvalidToken,
+    /// The provided API key is invalid.
+    InvalidApiKey,
     /// The MeiliSearch instance is under maintenance.
     Maintenance,
     /// The requested resources are protected with an API key, which was not
@@ -146,7 +255,7 @@ impl ErrorType {
         match input {
             "invalid_request_error" => Some(ErrorType::InvalidRequest),
             "internal_error" => Some(ErrorType::Internal),
-            "authentication_error" => Some(ErrorType::Authentication),
+            "authentication_error" | "auth" => Some(ErrorType::Authentication),
             _ => None,
         }
     }
@@ -172,6 +281,7 @@ impl ErrorCode {
             ErrorCode::DocumentNotFound => "document_not_found",
             ErrorCode::InternalError => "internal",
             ErrorCode::InvalidToken => "invalid_token",
+            ErrorCode::InvalidApiKey => "invalid_api_key",
             ErrorCode::Maintenance => "maintenance",
             ErrorCode::MissingAuthorizationHeader => "missing_authorization_header",
             ErrorCode::NotFound => "not_found",
@@ -204,6 +314,7 @@ impl ErrorCode {
             "document_not_found" => ErrorCode::DocumentNotFound,
             "internal" => ErrorCode::InternalError,
             "invalid_token" => ErrorCode::InvalidToken,
+            "invalid_api_key" => ErrorCode::InvalidApiKey,
             "maintenance" => ErrorCode::Maintenance,
             "missing_authorization_header" => ErrorCode::MissingAuthorizationHeader,
             "not_found" => ErrorCode::NotFound,
@@ -243,7 +354,54 @@ impl std::fmt::Display for Error {
                 error_link,
             ),
             Error::UnreachableServer => write!(fmt, "The MeiliSearch server can't be reached."),
-            Error::InvalidRequest => write!(fmt, "Unable to generate a valid HTTP request. It probably comes from an invalid API key."),
+            Error::InvalidRequest => write!(fmt, "Unable to generate a valid HTTP request. It probably comes from an invalid API key or an out of range search parameter."),
+            Error::ReadOnlyClient { attempted } => write!(fmt, "The client is read-only, `{}` was not sent.", attempted),
+            Error::Timeout => write!(fmt, "MeiliSearch didn't process the update before the timeout."),
+            Error::RequestTimeout { url, timeout } => write!(fmt, "The request to `{}` didn't complete within {:?}.", url, timeout),
+            Error::PrimaryKeyMismatch { index_key, provided } => write!(
+                fmt,
+                "The primary key of the index is `{}`, but the documents were sent with `{}`.",
+                index_key, provided
+            ),
+            Error::MissingVector { document_id, embedder } => write!(
+                fmt,
+                "The document `{}` has no vector for the embedder `{}`.",
+                document_id, embedder
+            ),
+            Error::VectorDimensionMismatch { embedder, expected, found } => write!(
+                fmt,
+                "The vectors of the embedder `{}` have {} dimensions, but one of them has {}.",
+                embedder, expected, found
+            ),
+            Error::UnknownServerVersion { version } => write!(fmt, "The version `{}` of the server can't be parsed.", version),
+            Error::UnsupportedGetParameter { parameter } => write!(fmt, "The search parameter `{}` can't be sent with a GET request.", parameter),
+            Error::UnsupportedCapability { capability } => {
+                let (major, minor) = capability.since();
+                write!(fmt, "The MeiliSearch server doesn't support {:?}: upgrade it to v{}.{} or newer.", capability, major, minor)
+            }
+            Error::InvalidPaginationKey { key } => write!(fmt, "The results can't be paginated by `{}`: it must be filterable, sortable and retrieved.", key),
+            Error::ResponseTooLarge { limit, url, observed } => write!(
+                fmt,
+                "The response to {} is larger than the limit of {} bytes ({} bytes received or announced).",
+                url, limit, observed
+            ),
+            Error::UpdateStatusUnavailable { update_id, attempts, last_error } => write!(
+                fmt,
+                "The status of the update {} couldn't be checked after {} attempts: {}",
+                update_id, attempts, last_error
+            ),
+            Error::MissingTemplateParameters { names } => write!(
+                fmt,
+                "The search template has no value for the parameters `{}`.",
+                names.join("`, `")
+            ),
+            Error::InvalidDocumentId { id } => write!(
+                fmt,
+                "The document id `{}` is invalid: it must be made of 1 to 511 alphanumeric characters, hyphens (-) and underscores (_).",
+                id
+            ),
+            Error::UnsupportedCompression { compression } => write!(fmt, "The http client can't compress the requests with {:?}, nothing was sent.", compression),
+            Error::CompressionFailed { compression, error } => write!(fmt, "The request couldn't be compressed with {:?}, nothing was sent: {}", compression, error),
             Error::ParseError(e) => write!(fmt, "Error parsing response JSON: {}", e),
             Error::HttpError(e) => write!(fmt, "HTTP request failed: {}", e),
         }
@@ -252,6 +410,66 @@ impl std::fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// A short machine-readable code for the error: the [MeiliSearch error code](enum.ErrorCode.html), or the name of the variant in snake case.
+    pub fn code(&self) -> &str {
+        match self {
+            Error::MeiliSearchError { error_code, .. } => error_code.as_str(),
+            Error::UnreachableServer => "unreachable_server",
+            Error::ParseError(_) => "parse_error",
+            Error::InvalidRequest => "invalid_request",
+            Error::Timeout => "timeout",
+            Error::RequestTimeout { .. } => "request_timeout",
+            Error::ResponseTooLarge { .. } => "response_too_large",
+            Error::MissingVector { .. } => "missing_vector",
+            Error::VectorDimensionMismatch { .. } => "vector_dimension_mismatch",
+            Error::UnknownServerVersion { .. } => "unknown_server_version",
+            Error::UnsupportedGetParameter { .. } => "unsupported_get_parameter",
+            Error::InvalidPaginationKey { .. } => "invalid_pagination_key",
+            Error::UnsupportedCapability { .. } => "unsupported_capability",
+            Error::PrimaryKeyMismatch { .. } => "primary_key_mismatch",
+            Error::UpdateStatusUnavailable { .. } => "update_status_unavailable",
+            Error::ReadOnlyClient { .. } => "read_only_client",
+            Error::MissingTemplateParameters { .. } => "missing_template_parameters",
+            Error::InvalidDocumentId { .. } => "invalid_document_id",
+            Error::UnsupportedCompression { .. } => "unsupported_compression",
+            Error::CompressionFailed { .. } => "compression_failed",
+            Error::HttpError(_) => "http_error",
+        }
+    }
+}
+
+/// Converts the error into a JavaScript `Error` whose `message` is the [Display](#impl-Display) of the error,
+/// with a `code` property (see [ErrorCode](enum.ErrorCode.html) for the errors returned by MeiliSearch)
+/// and, for errors returned by MeiliSearch, `type` and `link` properties.
+#[cfg(target_arch = "wasm32")]
+impl From<&Error> for js_sys::Error {
+    fn from(error: &Error) -> js_sys::Error {
+        use wasm_bindgen::JsValue;
+
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name("MeiliSearchError");
+        let mut properties = vec![("code", error.code().to_string())];
+        if let Error::MeiliSearchError { error_type, error_link, .. } = error {
+            properties.push(("type", error_type.as_str().to_string()));
+            properties.push(("link", error_link.clone()));
+        }
+        for (key, value) in properties {
+            // Setting a property on a fresh object can't fail
+            let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str(key), &JsValue::from_str(&value));
+        }
+        js_error
+    }
+}
+
+/// Allows using `?` on SDK results in functions exported with `#[wasm_bindgen]`. See [js_sys::Error](#impl-From%3C%26Error%3E).
+#[cfg(target_arch = "wasm32")]
+impl From<Error> for wasm_bindgen::JsValue {
+    fn from(error: Error) -> wasm_bindgen::JsValue {
+        js_sys::Error::from(&error).into()
+    }
+}
+
 impl From<&serde_json::Value> for Error {
     fn from(json: &serde_json::Value) -> Error {
 
@@ -261,14 +479,17 @@ impl From<&serde_json::Value> for Error {
             .map(|s| s.to_string())
             .unwrap_or_else(|| json.to_string());
 
+        // Newer versions of MeiliSearch (and MeiliSearch Cloud) use the `link`, `type` and `code` fields
         let error_link = json
             .get("errorLink")
+            .or_else(|| json.get("link"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string())
             .unwrap_or_else(String::new);
 
         let error_type = json
             .get("errorType")
+            .or_else(|| json.get("type"))
             .and_then(|v| v.as_str())
             .and_then(|s| ErrorType::parse(s))
             .unwrap_or(ErrorType::Internal);
@@ -278,6 +499,7 @@ impl From<&serde_json::Value> for Error {
 
         let error_code = json
             .get("errorCode")
+            .or_else(|| json.get("code"))
             .and_then(|v| v.as_str())
             .map(|s| ErrorCode::parse(s))
             .unwrap_or_else(|| {
@@ -303,3 +525,56 @@ impl From<isahc::Error> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code() {
+        let error = Error::from(&serde_json::json!({
+            "message": "Index `movies` not found.",
+            "code": "index_not_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#index_not_found"
+        }));
+        assert_eq!(error.code(), "index_not_found");
+        assert_eq!(Error::UnreachableServer.code(), "unreachable_server");
+        assert_eq!(Error::ReadOnlyClient { attempted: "delete" }.code(), "read_only_client");
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests {
+    use crate::client::Client;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn property(error: &js_sys::Error, key: &str) -> Option<String> {
+        js_sys::Reflect::get(error, &JsValue::from_str(key)).ok()?.as_string()
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_network_failure_as_js_error() {
+        // Nothing listens on the discard port
+        let client = Client::new("http://localhost:9", "masterKey");
+        let error = client.get_version().await.unwrap_err();
+        let js_error = js_sys::Error::from(&error);
+
+        assert_eq!(property(&js_error, "code").as_deref(), Some("unreachable_server"));
+        assert_eq!(String::from(js_error.message()), error.to_string());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_meilisearch_error_as_js_error() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let error = client.create_index("invalid uid!", None).await.unwrap_err();
+        let js_error = js_sys::Error::from(&error);
+
+        assert_eq!(property(&js_error, "code").as_deref(), Some("invalid_index_uid"));
+        assert_eq!(property(&js_error, "type").as_deref(), Some("invalid_request_error"));
+        assert!(property(&js_error, "link").is_some());
+    }
+}
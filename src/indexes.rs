@@ -1,15 +1,17 @@
 use crate::{
-    client::Client, document::*, errors::Error, errors::ErrorCode, progress::*, request::*, search::*, Rc,
+    cache::*, client::{Capability, Client, Version}, document::*, errors::Error, errors::ErrorCode, progress::*, request::*, search::*,
+    settings::Settings, Rc,
 };
+use futures::{stream, Stream, StreamExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::json;
-use std::{fmt::Display, collections::HashMap};
+use std::{fmt::Display, collections::{HashMap, HashSet}, time::Duration};
 
 #[derive(Deserialize, Debug)]
 #[allow(non_snake_case)]
 pub(crate) struct JsonIndex {
     uid: String,
-    primaryKey: Option<String>,
+    pub(crate) primaryKey: Option<String>,
     createdAt: String,
     updatedAt: String,
 }
@@ -19,17 +21,33 @@ impl JsonIndex {
         Index {
             uid: Rc::new(self.uid),
             host: Rc::clone(&client.host),
-            api_key: Rc::clone(&client.api_key)
+            api_key: Rc::clone(&client.api_key),
+            cache: client.cache.clone(),
+            waits: Rc::clone(&client.waits),
+            read_only: client.read_only,
+            max_response_bytes: client.max_response_bytes,
+            check_primary_key: client.check_primary_key,
+            check_vector_dimensions: client.check_vector_dimensions,
+            compression: client.compression,
         }
     }
 }
 
+/// A document fetched with its vectors.
+#[derive(Deserialize)]
+struct WithVectors<T> {
+    #[serde(flatten)]
+    document: T,
+    #[serde(rename = "_vectors")]
+    vectors: Option<HashMap<String, serde_json::Value>>,
+}
+
 /// An index containing [Documents](../document/trait.Document.html).
 ///
 /// # Example
 ///
 /// ```
-/// # use meilisearch_sdk::{client::*, indexes::*};
+/// # use meilisearch_sdk::prelude::*;
 /// # futures::executor::block_on(async move {
 /// let client = Client::new("http://localhost:7700", "masterKey");
 ///
@@ -44,18 +62,91 @@ pub struct Index {
     pub(crate) uid: Rc<String>,
     pub(crate) host: Rc<String>,
     pub(crate) api_key: Rc<String>,
+    pub(crate) cache: Option<Rc<ResponseCache>>,
+    pub(crate) waits: Rc<PendingWaits>,
+    pub(crate) read_only: bool,
+    pub(crate) max_response_bytes: Option<usize>,
+    pub(crate) check_primary_key: bool,
+    pub(crate) check_vector_dimensions: bool,
+    pub(crate) compression: Option<Compression>,
 }
 
 impl Index {
+    /// Refuse to send a write if the client is [read-only](../client/struct.Client.html#method.with_read_only).
+    pub(crate) fn check_writable(&self, attempted: &'static str) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::ReadOnlyClient { attempted });
+        }
+        Ok(())
+    }
+
+    /// Refuse to send documents with another primary key than the one of the index,
+    /// if the [check](../client/struct.Client.html#method.with_primary_key_check) is enabled.
+    async fn check_primary_key(&self, provided: Option<&str>) -> Result<(), Error> {
+        let provided = match provided {
+            Some(provided) if self.check_primary_key => provided,
+            _ => return Ok(()),
+        };
+        let index = match cached_request::<JsonIndex>(
+            &self.cache,
+            &format!("{}/indexes/{}", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await {
+            Ok(index) => index,
+            // The index will be created with the provided primary key
+            Err(Error::MeiliSearchError { error_code: ErrorCode::IndexNotFound, .. }) => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        match index.primaryKey {
+            Some(index_key) if index_key != provided => Err(Error::PrimaryKeyMismatch { index_key, provided: provided.to_string() }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Drop the cached responses related to this index, if the cache of the client is enabled.
+    pub(crate) fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(&self.host, &self.uid);
+        }
+    }
+
+    /// Get a copy of this index sending its requests with another API key.
+    ///
+    /// This is useful when a few requests need a more privileged key than the one of the client, to read the settings for example.
+    /// The copy shares the [cache](../client/struct.Client.html#method.with_cache) of the client, so that its writes invalidate it,
+    /// but the responses are cached by key: responses obtained with one key are never served to the other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::client::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "searchKey");
+    /// let movies = client.assume_index("movies");
+    ///
+    /// let settings = movies.with_key("masterKey").get_settings().await;
+    /// # });
+    /// ```
+    pub fn with_key(&self, api_key: impl Into<String>) -> Index {
+        Index {
+            api_key: Rc::new(api_key.into()),
+            ..self.clone()
+        }
+    }
+
     /// Set the primary key of the index.
     ///
     /// If you prefer, you can use the method [set_primary_key](#method.set_primary_key), which is an alias.
     pub async fn update(&self, primary_key: impl AsRef<str>) -> Result<(), Error> {
+        self.check_writable("update")?;
+        self.invalidate_cache();
         request::<serde_json::Value, JsonIndex>(
             &format!("{}/indexes/{}", self.host, self.uid),
             &self.api_key,
             Method::Put(json!({ "primaryKey": primary_key.as_ref() })),
             200,
+            self.max_response_bytes,
         ).await?;
         Ok(())
     }
@@ -65,22 +156,25 @@ impl Index {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// # client.create_index("movies", None).await;
+    /// # client.create_index("movies_delete", None).await;
     ///
-    /// // get the index named "movies" and delete it
-    /// let movies = client.get_index("movies").await.unwrap();
+    /// // get the index named "movies_delete" and delete it
+    /// let movies = client.get_index("movies_delete").await.unwrap();
     /// movies.delete().await.unwrap();
     /// # });
     /// ```
     pub async fn delete(self) -> Result<(), Error> {
+        self.check_writable("delete")?;
+        self.invalidate_cache();
         Ok(request::<(), ()>(
             &format!("{}/indexes/{}", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             204,
+            self.max_response_bytes,
         ).await?)
     }
 
@@ -89,16 +183,16 @@ impl Index {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// client.create_index("movies", None).await;
+    /// client.create_index("movies_delete_if_exists", None).await;
     ///
-    /// // get the index named "movies" and delete it
-    /// let movies = client.assume_index("movies");
+    /// // get the index named "movies_delete_if_exists" and delete it
+    /// let movies = client.assume_index("movies_delete_if_exists");
     /// let mut deleted = movies.delete_if_exists().await.unwrap();
     /// assert_eq!(deleted, true);
-    /// let index = client.get_index("movies").await;
+    /// let index = client.get_index("movies_delete_if_exists").await;
     /// assert!(index.is_err());
     ///
     /// // get an index that doesn't exist and try to delete it
@@ -127,7 +221,7 @@ impl Index {
     ///
     /// ```
     /// use serde::{Serialize, Deserialize};
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, search::*};
+    /// # use meilisearch_sdk::prelude::*;
     ///
     /// #[derive(Serialize, Deserialize, Debug)]
     /// struct Movie {
@@ -159,16 +253,132 @@ impl Index {
         &self,
         query: &Query<'_>,
     ) -> Result<SearchResults<T>, Error> {
-        Ok(request::<&Query, SearchResults<T>>(
+        self.send_query::<SearchResults<T>>(query).await
+    }
+
+    /// Search for documents matching a query, and get the results as raw JSON.
+    ///
+    /// Unlike [execute_query](#method.execute_query), nothing is lost: the hits keep all their fields, including `_formatted` and `_rankingScore`.
+    /// This is useful for schemaless documents. See also [Query::execute_raw](../search/struct.Query.html#method.execute_raw).
+    pub async fn search_raw(&self, query: &Query<'_>) -> Result<RawSearchResults, Error> {
+        self.send_query::<RawSearchResults>(query).await
+    }
+
+    /// Search for documents matching an [OwnedQuery](../search/struct.OwnedQuery.html).
+    ///
+    /// Unlike a [Query](../search/struct.Query.html), the same owned query can be executed on several indexes.
+    pub async fn execute_owned_query<T: 'static + DeserializeOwned>(&self, query: &OwnedQuery) -> Result<SearchResults<T>, Error> {
+        request_with_timeout::<&serde_json::Map<String, serde_json::Value>, SearchResults<T>>(
+            &format!("{}/indexes/{}/search", self.host, self.uid),
+            query.api_key_override.as_deref().unwrap_or(&self.api_key),
+            Method::Post(&query.body),
+            200,
+            self.max_response_bytes,
+            query.request_timeout,
+        ).await
+    }
+
+    /// Search for the documents most similar to a document, according to the vectors of an embedder.
+    ///
+    /// The vector of the document is fetched, then used for a vector search excluding the document itself.
+    /// The exclusion is a filter on the primary key, combined with the filter of the `options`: the primary key must be [filterable](../settings/struct.Settings.html#structfield.filterable_attributes).
+    ///
+    /// Returns [Error::MissingVector](../errors/enum.Error.html#variant.MissingVector) if the document has no vector for the embedder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, indexes::RecommendOptions};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Serialize, Deserialize, Debug)]
+    /// struct Movie {
+    ///     id: usize,
+    ///     title: String,
+    /// }
+    /// # impl meilisearch_sdk::document::Document for Movie {
+    /// #    type UIDType = usize;
+    /// #    fn get_uid(&self) -> &Self::UIDType { &self.id }
+    /// # }
+    ///
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.assume_index("movies");
+    ///
+    /// let options = RecommendOptions { embedder: "default", limit: Some(5), filter: Some("year > 2000") };
+    /// let similar = movies.recommend_for::<Movie>(42, options).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn recommend_for<T: 'static + Document>(&self, document_id: T::UIDType, options: RecommendOptions<'_>) -> Result<SearchResults<T>, Error> {
+        let document_id = document_id.to_string();
+        let document = request::<(), WithVectors<serde_json::Value>>(
+            &format!("{}?retrieveVectors=true", self.document_path(&document_id)?),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        let vector = document
+            .vectors
+            .as_ref()
+            .and_then(|vectors| vectors.get(options.embedder))
+            .and_then(first_vector)
+            .ok_or_else(|| Error::MissingVector { document_id: document_id.clone(), embedder: options.embedder.to_string() })?;
+        let primary_key = cached_request::<JsonIndex>(
+            &self.cache,
+            &format!("{}/indexes/{}", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await?
+        .primaryKey
+        .ok_or(Error::InvalidRequest)?;
+
+        let mut query = self.search();
+        query
+            .with_vector(vector)
+            .with_hybrid(options.embedder, 1.0)
+            .with_filter(exclusion_filter(options.filter, &primary_key, &document_id));
+        if let Some(limit) = options.limit {
+            query.with_limit(limit);
+        }
+        self.execute_query::<T>(&query).await
+    }
+
+    /// Send the query with a GET request, as with [Query::execute_with_method](../search/struct.Query.html#method.execute_with_method).
+    pub(crate) async fn execute_query_get<T: 'static + DeserializeOwned>(&self, query: &Query<'_>) -> Result<SearchResults<T>, Error> {
+        query.validate()?;
+        let parameters = query.to_get_parameters()?;
+        let parameters: Vec<(&str, &str)> = parameters.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        request_with_timeout::<(), SearchResults<T>>(
+            &add_query_parameters(&format!("{}/indexes/{}/search", self.host, self.uid), &parameters),
+            query.api_key_override.unwrap_or(&self.api_key),
+            Method::Get,
+            200,
+            self.max_response_bytes,
+            query.request_timeout,
+        ).await
+    }
+
+    async fn send_query<Output: 'static + DeserializeOwned>(&self, query: &Query<'_>) -> Result<Output, Error> {
+        Ok(self.send_query_with_metrics::<Output>(query).await?.0)
+    }
+
+    pub(crate) async fn send_query_with_metrics<Output: 'static + DeserializeOwned>(
+        &self,
+        query: &Query<'_>,
+    ) -> Result<(Output, RequestMetrics), Error> {
+        query.validate()?;
+        request_with_metrics::<&Query, Output>(
             &format!(
                 "{}/indexes/{}/search",
                 self.host,
                 self.uid
             ),
-            &self.api_key,
+            query.api_key_override.unwrap_or(&self.api_key),
             Method::Post(query),
             200,
-        ).await?)
+            self.max_response_bytes,
+            query.request_timeout,
+        ).await
     }
 
     /// Search for documents matching a specific query in the index.\
@@ -178,7 +388,7 @@ impl Index {
     ///
     /// ```
     /// use serde::{Serialize, Deserialize};
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, search::*};
+    /// # use meilisearch_sdk::prelude::*;
     ///
     /// #[derive(Serialize, Deserialize, Debug)]
     /// struct Movie {
@@ -223,7 +433,7 @@ impl Index {
     /// ```
     /// use serde::{Serialize, Deserialize};
     ///
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     ///
     /// #[derive(Serialize, Deserialize, Debug)]
     /// # #[derive(PartialEq)]
@@ -259,16 +469,111 @@ impl Index {
     /// ```
     pub async fn get_document<T: 'static + Document>(&self, uid: T::UIDType) -> Result<T, Error> {
         Ok(request::<(), T>(
-            &format!(
-                "{}/indexes/{}/documents/{}",
-                self.host, self.uid, uid
-            ),
+            &self.document_path(uid)?,
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         ).await?)
     }
 
+    /// Get one [document](../document/trait.Document.html) using its unique id, along with its vectors.
+    ///
+    /// The vectors are indexed by embedder name, see [SearchResult::vectors](../search/struct.SearchResult.html#structfield.vectors) for their format.
+    /// They are `None` if the server didn't return any.
+    pub async fn get_document_with_vectors<T: 'static + Document>(
+        &self,
+        uid: T::UIDType,
+    ) -> Result<(T, Option<HashMap<String, serde_json::Value>>), Error> {
+        let document = request::<(), WithVectors<T>>(
+            &format!("{}?retrieveVectors=true", self.document_path(uid)?),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        Ok((document.document, document.vectors))
+    }
+
+    /// Get one document using its unique id, with [parameters](struct.DocumentQueryParams.html).
+    ///
+    /// The document can be read into any deserializable type, including a `serde_json::Value` to access its raw fields.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use meilisearch_sdk::{client::*, document::Vectors, indexes::*};
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize, Debug)]
+    /// struct Movie {
+    ///     id: String,
+    ///     #[serde(rename = "_vectors")]
+    ///     vectors: Vectors,
+    /// }
+    ///
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_index("movies").await.unwrap();
+    /// let params = DocumentQueryParams::default().with_fields(["id"]).with_retrieve_vectors(true);
+    /// let movie: Movie = movies.get_document_with_params("interstellar", &params).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_document_with_params<T: 'static + DeserializeOwned>(
+        &self,
+        uid: impl Display,
+        params: &DocumentQueryParams,
+    ) -> Result<T, Error> {
+        request::<(), T>(
+            &self.document_url(uid, params)?,
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Check whether the index has a document with this unique id.
+    ///
+    /// None of the fields of the document are retrieved. The `document_not_found` error of MeiliSearch is turned into `Ok(false)`,
+    /// the other errors are returned as they are.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_index("movies").await.unwrap();
+    /// if !movies.document_exists("interstellar").await.unwrap() {
+    ///     println!("Interstellar is missing");
+    /// }
+    /// # });
+    /// ```
+    pub async fn document_exists(&self, uid: impl Display) -> Result<bool, Error> {
+        let params = DocumentQueryParams { fields: Some(Vec::new()), ..DocumentQueryParams::default() };
+        match self.get_document_with_params::<serde::de::IgnoredAny>(uid, &params).await {
+            Ok(_) => Ok(true),
+            Err(Error::MeiliSearchError { error_code: ErrorCode::DocumentNotFound, .. }) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn document_url(&self, uid: impl Display, params: &DocumentQueryParams) -> Result<String, Error> {
+        let parameters = query_parameters(params)?;
+        let parameters: Vec<(&str, &str)> = parameters.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        Ok(add_query_parameters(&self.document_path(uid)?, &parameters))
+    }
+
+    /// The url of a document, refusing the ids that MeiliSearch doesn't accept rather than sending a malformed path.
+    fn document_path(&self, uid: impl Display) -> Result<String, Error> {
+        let uid = uid.to_string();
+        let valid = (1..=511).contains(&uid.len()) && uid.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+        if !valid {
+            return Err(Error::InvalidDocumentId { id: uid });
+        }
+        Ok(format!("{}/indexes/{}/documents/{}", self.host, self.uid, encode_query_component(&uid)))
+    }
+
     /// Get [documents](../document/trait.Document.html) by batch.
     ///
     /// Using the optional parameters offset and limit, you can browse through all your documents.
@@ -281,7 +586,7 @@ impl Index {
     /// ```
     /// use serde::{Serialize, Deserialize};
     ///
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     ///
     /// #[derive(Serialize, Deserialize, Debug)]
     /// # #[derive(PartialEq)]
@@ -319,27 +624,213 @@ impl Index {
         limit: Option<usize>,
         attributes_to_retrieve: Option<&str>,
     ) -> Result<Vec<T>, Error> {
-        let mut url = format!("{}/indexes/{}/documents?", self.host, self.uid);
-        if let Some(offset) = offset {
-            url.push_str("offset=");
-            url.push_str(offset.to_string().as_str());
-            url.push('&');
-        }
-        if let Some(limit) = limit {
-            url.push_str("limit=");
-            url.push_str(limit.to_string().as_str());
-            url.push('&');
-        }
-        if let Some(attributes_to_retrieve) = attributes_to_retrieve {
-            url.push_str("attributesToRetrieve=");
-            url.push_str(attributes_to_retrieve);
+        self.get_documents_with_params(&DocumentsQueryParams {
+            offset,
+            limit,
+            fields: attributes_to_retrieve.map(|fields| fields.split(',').map(String::from).collect()),
+            ..DocumentsQueryParams::default()
+        })
+        .await
+    }
+
+    /// Get [documents](../document/trait.Document.html) by batch, as described by [params](struct.DocumentsQueryParams.html).
+    ///
+    /// Without a [filter](struct.DocumentsQueryParams.html#structfield.filter), the parameters are sent in the query string of a GET request.
+    /// With one, they are sent in the body of a POST request to the `documents/fetch` route, which requires MeiliSearch v1.2 or newer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, indexes::DocumentsQueryParams};
+    /// # use serde_json::Value;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_get_documents_with_params").await.unwrap();
+    ///
+    /// let params = DocumentsQueryParams { limit: Some(5), fields: Some(vec!["title".to_string()]), ..DocumentsQueryParams::default() };
+    /// let documents: Vec<Value> = movies.get_documents_with_params(&params).await.unwrap();
+    /// assert!(documents.len() <= 5);
+    /// # client.delete_index("movies_get_documents_with_params").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_documents_with_params<T: 'static + DeserializeOwned>(&self, params: &DocumentsQueryParams) -> Result<Vec<T>, Error> {
+        Ok(self.get_documents_with_metadata(params).await?.0)
+    }
+
+    /// Same as [get_documents_with_params](#method.get_documents_with_params), also returning the [measures](../client/struct.RequestMetrics.html) of the request.
+    pub async fn get_documents_with_metadata<T: 'static + DeserializeOwned>(
+        &self,
+        params: &DocumentsQueryParams,
+    ) -> Result<(Vec<T>, RequestMetrics), Error> {
+        if params.filter.is_some() {
+            let (documents, metrics) = request_with_metrics::<&DocumentsQueryParams, FetchedDocuments<T>>(
+                &format!("{}/indexes/{}/documents/fetch", self.host, self.uid),
+                &self.api_key,
+                Method::Post(params),
+                200,
+                self.max_response_bytes,
+                None,
+            ).await?;
+            return Ok((documents.results, metrics));
         }
-        Ok(request::<(), Vec<T>>(
-            &url,
+        let fields = params.fields.as_ref().map(|fields| fields.join(","));
+        request_with_metrics::<(), Vec<T>>(
+            &self.documents_url(params.offset, params.limit, fields.as_deref(), None, params.retrieve_vectors),
             &self.api_key,
             Method::Get,
             200,
-        ).await?)
+            self.max_response_bytes,
+            None,
+        ).await
+    }
+
+    /// Stream all the documents of the index, fetching them `page_size` at a time.
+    ///
+    /// The pages are requested one after the other, when the previous one has been consumed.
+    /// The stream ends after the first error, or after a page shorter than `page_size`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use meilisearch_sdk::prelude::*;
+    /// # use futures::StreamExt;
+    /// # use serde_json::Value;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_index("movies").await.unwrap();
+    ///
+    /// let mut documents = Box::pin(movies.all_documents::<Value>(100));
+    /// while let Some(document) = documents.next().await {
+    ///     println!("{}", document.unwrap());
+    /// }
+    /// # });
+    /// ```
+    pub fn all_documents<T: 'static + DeserializeOwned>(&self, page_size: usize) -> impl Stream<Item = Result<T, Error>> + '_ {
+        self.all_documents_with_params(page_size, DocumentsQueryParams::default())
+    }
+
+    /// Same as [all_documents](#method.all_documents), with the [fields, filter and vectors](struct.DocumentsQueryParams.html) of `params`.
+    ///
+    /// The `offset` of `params` is where the stream starts, and its `limit` is ignored.
+    pub fn all_documents_with_params<T: 'static + DeserializeOwned>(
+        &self,
+        page_size: usize,
+        params: DocumentsQueryParams,
+    ) -> impl Stream<Item = Result<T, Error>> + '_ {
+        let pages = stream::unfold(Some(params.offset.unwrap_or(0)), move |offset| {
+            let params = DocumentsQueryParams { offset, limit: Some(page_size), ..params.clone() };
+            async move {
+                let offset = offset?;
+                if page_size == 0 {
+                    return Some((Err(Error::InvalidRequest), None));
+                }
+                match self.get_documents_with_params::<T>(&params).await {
+                    Ok(page) => {
+                        let next = if page.len() < page_size { None } else { Some(offset + page.len()) };
+                        Some((Ok(page), next))
+                    }
+                    Err(error) => Some((Err(error), None)),
+                }
+            }
+        });
+        pages.flat_map(|page| {
+            stream::iter(match page {
+                Ok(documents) => documents.into_iter().map(Ok).collect(),
+                Err(error) => vec![Err(error)],
+            })
+        })
+    }
+
+    /// Same as [all_documents_with_params](#method.all_documents_with_params), starting where a previous stream stopped.
+    ///
+    /// Each document comes with the [ResumeToken] to continue after it, possibly from another process since the token can be serialized.
+    /// After an error, the stream can be continued with the token of the last document received.
+    /// The documents are read by offset, so documents added or deleted in the meantime may shift the documents left to read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use futures::StreamExt;
+    /// # use serde_json::Value;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_index("movies").await.unwrap();
+    ///
+    /// let mut token = ResumeToken::new(100, DocumentsQueryParams::default());
+    /// let mut documents = Box::pin(movies.documents_stream_from::<Value>(token.clone()).take(150));
+    /// while let Some(document) = documents.next().await {
+    ///     let (document, next) = document.unwrap();
+    ///     println!("{}", document);
+    ///     token = next;
+    /// }
+    ///
+    /// // Store the token, and continue after the 150th document later
+    /// let token = serde_json::to_string(&token).unwrap();
+    /// let token: ResumeToken = serde_json::from_str(&token).unwrap();
+    /// let rest = movies.documents_stream_from::<Value>(token);
+    /// # });
+    /// ```
+    pub fn documents_stream_from<T: 'static + DeserializeOwned>(
+        &self,
+        token: ResumeToken,
+    ) -> impl Stream<Item = Result<(T, ResumeToken), Error>> + '_ {
+        let ResumeToken { offset, page_size, params } = token;
+        let next = ResumeToken { offset, page_size, params: DocumentsQueryParams { offset: None, limit: None, ..params.clone() } };
+        self.all_documents_with_params(page_size, DocumentsQueryParams { offset: Some(offset), ..params })
+            .enumerate()
+            .map(move |(position, document)| {
+                document.map(|document| (document, ResumeToken { offset: offset + position + 1, ..next.clone() }))
+            })
+    }
+
+    /// Get [documents](../document/trait.Document.html) by batch, along with their vectors.
+    ///
+    /// Same as [get_documents](#method.get_documents), but each document comes with its vectors, indexed by embedder name.
+    /// See [SearchResult::vectors](../search/struct.SearchResult.html#structfield.vectors) for the format of the vectors.
+    pub async fn get_documents_with_vectors<T: 'static + Document>(
+        &self,
+        offset: Option<usize>,
+        limit: Option<usize>,
+        attributes_to_retrieve: Option<&str>,
+    ) -> Result<Vec<(T, Option<HashMap<String, serde_json::Value>>)>, Error> {
+        let documents = request::<(), Vec<WithVectors<T>>>(
+            &self.documents_url(offset, limit, attributes_to_retrieve, None, true),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        Ok(documents.into_iter().map(|document| (document.document, document.vectors)).collect())
+    }
+
+    fn documents_url(
+        &self,
+        offset: Option<usize>,
+        limit: Option<usize>,
+        attributes_to_retrieve: Option<&str>,
+        filter: Option<&str>,
+        retrieve_vectors: bool,
+    ) -> String {
+        let offset = offset.map(|offset| offset.to_string());
+        let limit = limit.map(|limit| limit.to_string());
+        let mut parameters = Vec::new();
+        if let Some(offset) = &offset {
+            parameters.push(("offset", offset.as_str()));
+        }
+        if let Some(limit) = &limit {
+            parameters.push(("limit", limit.as_str()));
+        }
+        if retrieve_vectors {
+            parameters.push(("retrieveVectors", "true"));
+        }
+        if let Some(attributes_to_retrieve) = attributes_to_retrieve {
+            parameters.push(("attributesToRetrieve", attributes_to_retrieve));
+        }
+        if let Some(filter) = filter {
+            parameters.push(("filter", filter));
+        }
+        add_query_parameters(&format!("{}/indexes/{}/documents", self.host, self.uid), &parameters)
     }
 
     /// Add a list of [documents](../document/trait.Document.html) or replace them if they already exist.
@@ -356,7 +847,7 @@ impl Index {
     /// ```
     /// use serde::{Serialize, Deserialize};
     ///
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # use std::thread::sleep;
     /// # use std::time::Duration;
     /// #[derive(Serialize, Deserialize, Debug)]
@@ -404,23 +895,26 @@ impl Index {
         documents: &[T],
         primary_key: Option<&str>,
     ) -> Result<Progress, Error> {
-        let url = if let Some(primary_key) = primary_key {
-            format!(
-                "{}/indexes/{}/documents?primaryKey={}",
-                self.host, self.uid, primary_key
-            )
-        } else {
-            format!("{}/indexes/{}/documents", self.host, self.uid)
-        };
-        Ok(
-            request::<&[T], ProgressJson>(
-                &url,
-                &self.api_key,
-                Method::Post(documents),
-                202,
-            ).await?
-            .into_progress(self),
-        )
+        self.check_writable("add_or_replace")?;
+        if self.check_vector_dimensions {
+            check_vector_dimensions(documents)?;
+        }
+        let payload = serde_json::to_vec(documents).map_err(Error::ParseError)?;
+        self.send_payload(Method::Post(payload), "application/json", primary_key, None).await
+    }
+
+    /// Same as [add_or_replace](#method.add_or_replace), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn add_or_replace_and_wait<T: Document>(
+        &self,
+        documents: &[T],
+        primary_key: Option<&str>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.add_or_replace(documents, primary_key).await?.wait_until_processed(interval, timeout).await
     }
 
     /// Alias for [add_or_replace](#method.add_or_replace).
@@ -429,9 +923,62 @@ impl Index {
         documents: &[T],
         primary_key: Option<&str>,
     ) -> Result<Progress, Error> {
+        self.check_writable("add_documents")?;
         self.add_or_replace(documents, primary_key).await
     }
 
+    /// Same as [add_documents](#method.add_documents), with the [primary key declared by the documents](../document/trait.Document.html#associatedconstant.PRIMARY_KEY).
+    ///
+    /// If the index already has another primary key, the update fails and the error of MeiliSearch is returned when waiting for it,
+    /// unless the [check](../client/struct.Client.html#method.with_primary_key_check) of the client is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, Debug)]
+    /// struct Product {
+    ///     sku: String,
+    ///     name: String,
+    /// }
+    ///
+    /// impl Document for Product {
+    ///     type UIDType = String;
+    ///     const PRIMARY_KEY: Option<&'static str> = Some("sku");
+    ///
+    ///     fn get_uid(&self) -> &Self::UIDType {
+    ///         &self.sku
+    ///     }
+    /// }
+    ///
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let products = client.get_or_create("products_add_documents_with_uid").await.unwrap();
+    /// let progress = products.add_documents_with_uid(&[Product { sku: "a-1".to_string(), name: "Lamp".to_string() }]).await.unwrap();
+    /// # progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// # client.delete_index("products_add_documents_with_uid").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn add_documents_with_uid<T: Document>(&self, documents: &[T]) -> Result<Progress, Error> {
+        self.check_writable("add_documents_with_uid")?;
+        self.add_or_replace(documents, T::PRIMARY_KEY).await
+    }
+
+    /// Same as [add_documents](#method.add_documents), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn add_documents_and_wait<T: Document>(
+        &self,
+        documents: &[T],
+        primary_key: Option<&str>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.add_documents(documents, primary_key).await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Add a list of documents and update them if they already.
     ///
     /// If you send an already existing document (same id) the old document will be only partially updated according to the fields of the new document.
@@ -444,7 +991,7 @@ impl Index {
     /// ```
     /// use serde::{Serialize, Deserialize};
     ///
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # use std::thread::sleep;
     /// # use std::time::Duration;
     /// #[derive(Serialize, Deserialize, Debug)]
@@ -492,18 +1039,166 @@ impl Index {
         documents: &[T],
         primary_key: Option<impl AsRef<str>>,
     ) -> Result<Progress, Error> {
-        let url = if let Some(primary_key) = primary_key {
-            format!(
-                "{}/indexes/{}/documents?primaryKey={}",
-                self.host, self.uid, primary_key.as_ref()
-            )
-        } else {
-            format!("{}/indexes/{}/documents", self.host, self.uid)
-        };
-        Ok(
-            request::<&[T], ProgressJson>(&url, &self.api_key, Method::Put(documents), 202).await?
-                .into_progress(self),
-        )
+        self.check_writable("add_or_update")?;
+        let primary_key = primary_key.as_ref().map(|primary_key| primary_key.as_ref());
+        if self.check_vector_dimensions {
+            check_vector_dimensions(documents)?;
+        }
+        let payload = serde_json::to_vec(documents).map_err(Error::ParseError)?;
+        self.send_payload(Method::Put(payload), "application/json", primary_key, None).await
+    }
+
+    /// Same as [add_or_update](#method.add_or_update), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn add_or_update_and_wait<T: Document>(
+        &self,
+        documents: &[T],
+        primary_key: Option<impl AsRef<str>>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.add_or_update(documents, primary_key).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Add or replace documents by batches of `batch_size` (default: 1000), each batch being sent with [add_or_replace](#method.add_or_replace).
+    ///
+    /// Returns the progress of each batch. Fails before sending anything if `batch_size` is 0.
+    /// If a batch can't be sent, the error is returned and the previous batches are still processed.
+    pub async fn add_documents_in_batches<T: Document>(
+        &self,
+        documents: &[T],
+        batch_size: Option<usize>,
+        primary_key: Option<&str>,
+    ) -> Result<Vec<Progress>, Error> {
+        self.check_writable("add_documents_in_batches")?;
+        self.send_documents_in_batches(documents, batch_size, primary_key, false).await
+    }
+
+    /// Add or update documents by batches of `batch_size` (default: 1000), each batch being sent with [add_or_update](#method.add_or_update),
+    /// so that partial documents are merged into the existing ones.
+    ///
+    /// Returns the progress of each batch. Fails before sending anything if `batch_size` is 0.
+    /// If a batch can't be sent, the error is returned and the previous batches are still processed.
+    pub async fn update_documents_in_batches<T: Document>(
+        &self,
+        documents: &[T],
+        batch_size: Option<usize>,
+        primary_key: Option<&str>,
+    ) -> Result<Vec<Progress>, Error> {
+        self.check_writable("update_documents_in_batches")?;
+        self.send_documents_in_batches(documents, batch_size, primary_key, true).await
+    }
+
+    async fn send_documents_in_batches<T: Document>(
+        &self,
+        documents: &[T],
+        batch_size: Option<usize>,
+        primary_key: Option<&str>,
+        partial: bool,
+    ) -> Result<Vec<Progress>, Error> {
+        let batch_size = batch_size.unwrap_or(1000);
+        if batch_size == 0 {
+            return Err(Error::InvalidRequest);
+        }
+
+        let mut progresses = Vec::new();
+        for batch in documents.chunks(batch_size) {
+            progresses.push(if partial {
+                self.add_or_update(batch, primary_key).await?
+            } else {
+                self.add_or_replace(batch, primary_key).await?
+            });
+        }
+        Ok(progresses)
+    }
+
+    /// Add documents from a payload of [newline-delimited JSON](http://ndjson.org/), sent as is.
+    ///
+    /// Each line of the payload is a document. The documents with the same primary key as documents already in the index replace them,
+    /// as with [add_or_replace](#method.add_or_replace). MeiliSearch parses the payload itself, so a malformed line makes the update fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_add_documents_ndjson").await.unwrap();
+    ///
+    /// let payload = b"{\"id\":1,\"title\":\"Dune\"}\n{\"id\":2,\"title\":\"Carrie\"}\n";
+    /// let progress = movies.add_documents_ndjson(payload, Some("id")).await.unwrap();
+    /// # progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// # client.delete_index("movies_add_documents_ndjson").await.unwrap();
+    /// # });
+    /// ```
+    #[cfg(feature = "documents-io")]
+    pub async fn add_documents_ndjson(&self, payload: &[u8], primary_key: Option<&str>) -> Result<Progress, Error> {
+        self.check_writable("add_documents_ndjson")?;
+        self.send_payload(Method::Post(payload.to_vec()), "application/x-ndjson", primary_key, None).await
+    }
+
+    /// Same as [add_documents_ndjson](#method.add_documents_ndjson), but the documents already in the index are only partially updated,
+    /// as with [add_or_update](#method.add_or_update).
+    #[cfg(feature = "documents-io")]
+    pub async fn update_documents_ndjson(&self, payload: &[u8], primary_key: Option<&str>) -> Result<Progress, Error> {
+        self.check_writable("update_documents_ndjson")?;
+        self.send_payload(Method::Put(payload.to_vec()), "application/x-ndjson", primary_key, None).await
+    }
+
+    /// Add documents from a CSV payload, sent as is.
+    ///
+    /// The first line of the payload names the fields, optionally typed with `:string`, `:number` or `:boolean` (like `count:number`);
+    /// the untyped fields are strings. The fields are separated by commas, or by `delimiter` if provided,
+    /// which must be a single ASCII character or [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) is returned.
+    /// The documents with the same primary key as documents already in the index replace them, as with [add_or_replace](#method.add_or_replace).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_add_documents_csv").await.unwrap();
+    ///
+    /// let payload = b"id;title;year:number\n1;Dune;1965\n2;Carrie;1974\n";
+    /// let progress = movies.add_documents_csv(payload, Some("id"), Some(';')).await.unwrap();
+    /// # progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// # client.delete_index("movies_add_documents_csv").await.unwrap();
+    /// # });
+    /// ```
+    #[cfg(feature = "documents-io")]
+    pub async fn add_documents_csv(&self, payload: &[u8], primary_key: Option<&str>, delimiter: Option<char>) -> Result<Progress, Error> {
+        self.check_writable("add_documents_csv")?;
+        if matches!(delimiter, Some(delimiter) if !delimiter.is_ascii()) {
+            return Err(Error::InvalidRequest);
+        }
+        self.send_payload(Method::Post(payload.to_vec()), "text/csv", primary_key, delimiter).await
+    }
+
+    /// Send documents already encoded in `content_type`, compressed if the client [compresses](../client/struct.Client.html#method.with_request_compression) the requests.
+    async fn send_payload(
+        &self,
+        method: Method<Vec<u8>>,
+        content_type: &str,
+        primary_key: Option<&str>,
+        csv_delimiter: Option<char>,
+    ) -> Result<Progress, Error> {
+        self.check_primary_key(primary_key).await?;
+        let csv_delimiter = csv_delimiter.map(String::from);
+        let mut parameters = Vec::new();
+        if let Some(primary_key) = primary_key {
+            parameters.push(("primaryKey", primary_key));
+        }
+        if let Some(csv_delimiter) = &csv_delimiter {
+            parameters.push(("csvDelimiter", csv_delimiter.as_str()));
+        }
+        let url = add_query_parameters(&format!("{}/indexes/{}/documents", self.host, self.uid), &parameters);
+        Ok(
+            request_bytes::<ProgressJson>(&url, &self.api_key, method, content_type, self.compression, 202, self.max_response_bytes).await?
+                .into_progress(self),
+        )
     }
 
     /// Delete all documents in the index.
@@ -512,7 +1207,7 @@ impl Index {
     ///
     /// ```
     /// # use serde::{Serialize, Deserialize};
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # #[derive(Serialize, Deserialize, Debug)]
     /// # struct Movie {
@@ -531,7 +1226,7 @@ impl Index {
     /// # futures::executor::block_on(async move {
     /// #
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    /// let mut movie_index = client.get_or_create("movies_delete_all_documents").await.unwrap();
     ///
     /// // add some documents
     ///
@@ -540,25 +1235,40 @@ impl Index {
     /// # progress.get_status().await.unwrap();
     /// # let movies = movie_index.get_documents::<Movie>(None, None, None).await.unwrap();
     /// # assert_eq!(movies.len(), 0);
+    /// # client.delete_index("movies_delete_all_documents").await.unwrap();
     /// # });
     /// ```
     pub async fn delete_all_documents(&self) -> Result<Progress, Error> {
+        self.check_writable("delete_all_documents")?;
         Ok(request::<(), ProgressJson>(
             &format!("{}/indexes/{}/documents", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [delete_all_documents](#method.delete_all_documents), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn delete_all_documents_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.delete_all_documents().await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Delete one document based on its unique id.
     ///
     /// # Example
     ///
     /// ```
     /// # use serde::{Serialize, Deserialize};
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # #[derive(Serialize, Deserialize, Debug)]
     /// # struct Movie {
@@ -577,7 +1287,7 @@ impl Index {
     /// # futures::executor::block_on(async move {
     /// #
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// let mut movies = client.get_or_create("movies").await.unwrap();
+    /// let mut movies = client.get_or_create("movies_delete_document").await.unwrap();
     ///
     /// # movies.add_or_replace(&[Movie{name:String::from("Interstellar"), description:String::from("Interstellar chronicles the adventures of a group of explorers who make use of a newly discovered wormhole to surpass the limitations on human space travel and conquer the vast distances involved in an interstellar voyage.")}], Some("name")).await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(1));
@@ -585,28 +1295,41 @@ impl Index {
     ///
     /// let progress = movies.delete_document("Interstellar").await.unwrap();
     /// # progress.get_status().await.unwrap();
+    /// # client.delete_index("movies_delete_document").await.unwrap();
     /// # });
     /// ```
     pub async fn delete_document<T: Display>(&self, uid: T) -> Result<Progress, Error> {
+        self.check_writable("delete_document")?;
         Ok(request::<(), ProgressJson>(
-            &format!(
-                "{}/indexes/{}/documents/{}",
-                self.host, self.uid, uid
-            ),
+            &self.document_path(uid)?,
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [delete_document](#method.delete_document), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn delete_document_and_wait<T: Display>(
+        &self,
+        uid: T,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.delete_document(uid).await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Delete a selection of documents based on array of document id's.
     ///
     /// # Example
     ///
     /// ```
     /// # use serde::{Serialize, Deserialize};
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # #[derive(Serialize, Deserialize, Debug)]
     /// # struct Movie {
@@ -625,7 +1348,7 @@ impl Index {
     /// # futures::executor::block_on(async move {
     /// #
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// let mut movies = client.get_or_create("movies").await.unwrap();
+    /// let mut movies = client.get_or_create("movies_delete_documents").await.unwrap();
     ///
     /// // add some documents
     /// # movies.add_or_replace(&[Movie{name:String::from("Interstellar"), description:String::from("Interstellar chronicles the adventures of a group of explorers who make use of a newly discovered wormhole to surpass the limitations on human space travel and conquer the vast distances involved in an interstellar voyage.")},Movie{name:String::from("Unknown"), description:String::from("Unknown")}], Some("name")).await.unwrap();
@@ -634,12 +1357,14 @@ impl Index {
     /// // delete some documents
     /// let progress = movies.delete_documents(&["Interstellar", "Unknown"]).await.unwrap();
     /// # progress.get_status().await.unwrap();
+    /// # client.delete_index("movies_delete_documents").await.unwrap();
     /// # });
     /// ```
     pub async fn delete_documents<T: Display + Serialize + std::fmt::Debug>(
         &self,
         uids: &[T],
     ) -> Result<Progress, Error> {
+        self.check_writable("delete_documents")?;
         Ok(request::<&[T], ProgressJson>(
             &format!(
                 "{}/indexes/{}/documents/delete-batch",
@@ -648,12 +1373,112 @@ impl Index {
             &self.api_key,
             Method::Post(uids),
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [delete_documents](#method.delete_documents), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn delete_documents_and_wait<T: Display + Serialize + std::fmt::Debug>(
+        &self,
+        uids: &[T],
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.delete_documents(uids).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Delete documents by batches of `batch_size` uids (default: 1000), each batch being sent with [delete_documents](#method.delete_documents),
+    /// so that long lists of uids don't make oversized requests.
+    ///
+    /// Returns the progress of each batch. Fails before sending anything if `batch_size` is 0.
+    /// If a batch can't be sent, the error is returned and the previous batches are still processed.
+    pub async fn delete_documents_in_batches<T: Display + Serialize + std::fmt::Debug>(
+        &self,
+        uids: &[T],
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Progress>, Error> {
+        self.check_writable("delete_documents_in_batches")?;
+        let batch_size = batch_size.unwrap_or(1000);
+        if batch_size == 0 {
+            return Err(Error::InvalidRequest);
+        }
+
+        let mut progresses = Vec::new();
+        for batch in uids.chunks(batch_size) {
+            progresses.push(self.delete_documents(batch).await?);
+        }
+        Ok(progresses)
+    }
+
+    /// Delete all the documents matching `filter`.
+    /// The attributes used in the filter must be [filterable](../settings/struct.Settings.html#structfield.filterable_attributes).
+    ///
+    /// Returns [Error::UnsupportedCapability](../errors/enum.Error.html#variant.UnsupportedCapability) if the server is too old to delete documents by filter.
+    /// Deleting documents by uid with [delete_documents](#method.delete_documents) works with all versions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_delete_documents_with").await.unwrap();
+    /// # movies.set_filterable_attributes_and_wait(["year"], None, None).await.unwrap();
+    ///
+    /// let progress = movies.delete_documents_with("year < 1950").await.unwrap();
+    /// # progress.get_status().await.unwrap();
+    /// # client.delete_index("movies_delete_documents_with").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn delete_documents_with(&self, filter: &str) -> Result<Progress, Error> {
+        self.check_writable("delete_documents_with")?;
+        self.delete_documents_with_params(&DocumentDeletionParams { filter: filter.to_string() }).await
+    }
+
+    /// Delete the documents described by [params](struct.DocumentDeletionParams.html).
+    ///
+    /// Same as [delete_documents_with](#method.delete_documents_with), failing with [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) without sending anything if the filter is empty,
+    /// since that would not select any document.
+    pub async fn delete_documents_with_params(&self, params: &DocumentDeletionParams) -> Result<Progress, Error> {
+        self.check_writable("delete_documents_with_params")?;
+        if params.filter.trim().is_empty() {
+            return Err(Error::InvalidRequest);
+        }
+        match request::<&DocumentDeletionParams, ProgressJson>(
+            &format!("{}/indexes/{}/documents/delete", self.host, self.uid),
+            &self.api_key,
+            Method::Post(params),
+            202,
+            self.max_response_bytes,
+        ).await {
+            Ok(progress) => Ok(progress.into_progress(self)),
+            Err(Error::MeiliSearchError { error_code: ErrorCode::NotFound, .. }) => {
+                Err(Error::UnsupportedCapability { capability: Capability::DeleteDocumentsByFilter })
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Same as [delete_documents_with](#method.delete_documents_with), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn delete_documents_with_and_wait(
+        &self,
+        filter: &str,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.delete_documents_with(filter).await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Alias for the [update method](#method.update).
     pub async fn set_primary_key(&self, primary_key: impl AsRef<str>) -> Result<(), Error> {
+        self.check_writable("set_primary_key")?;
         self.update(primary_key).await
     }
 
@@ -710,6 +1535,7 @@ impl Index {
     ///    UpdateStatus::Processing{content} => content.update_id,
     ///    UpdateStatus::Failed{content} => content.update_id,
     ///    UpdateStatus::Processed{content} => content.update_id,
+    ///    status => panic!("unexpected status: {}", status.as_str()),
     /// };
     ///
     /// let update_id = progress.get_update_id();
@@ -721,6 +1547,7 @@ impl Index {
     ///    UpdateStatus::Processing{content} => content.update_id,
     ///    UpdateStatus::Failed{content} => content.update_id,
     ///    UpdateStatus::Processed{content} => content.update_id,
+    ///    status => panic!("unexpected status: {}", status.as_str()),
     /// };
     ///
     /// assert_eq!(from_progress, from_index);
@@ -729,7 +1556,7 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_update(&self, update_id: u64) -> Result<UpdateStatus, Error> {
-        request::<(), UpdateStatus>(
+        let status = request::<(), UpdateStatus>(
             &format!(
                 "{}/indexes/{}/updates/{}",
                 self.host, self.uid, update_id
@@ -737,11 +1564,19 @@ impl Index {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         )
-        .await
+        .await?;
+        if let Some(cache) = &self.cache {
+            if !is_pending(&status) {
+                cache.finish_update(&self.host, &self.uid, update_id);
+            }
+        }
+        Ok(status)
     }
 
     /// Get the status of all updates in a given index.
+    /// Updates that can't be parsed are returned as [Other](../progress/enum.UpdateStatus.html#variant.Other) instead of failing the whole list.
     ///
     /// # Example
     ///
@@ -788,7 +1623,7 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_all_updates(&self) -> Result<Vec<UpdateStatus>, Error> {
-        request::<(), Vec<UpdateStatus>>(
+        let updates = request::<(), Vec<serde_json::Value>>(
             &format!(
                 "{}/indexes/{}/updates",
                 self.host, self.uid
@@ -796,8 +1631,10 @@ impl Index {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         )
-        .await
+        .await?;
+        Ok(updates.into_iter().map(UpdateStatus::from_list_entry).collect())
     }
 
     /// Get stats of an index.
@@ -805,7 +1642,7 @@ impl Index {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// #
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
@@ -815,16 +1652,293 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_stats(&self) -> Result<IndexStats, Error> {
-        request::<serde_json::Value, IndexStats>(
+        cached_request::<IndexStats>(
+            &self.cache,
             &format!("{}/indexes/{}/stats", self.host, self.uid),
             &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Wait until the index stops indexing, without the [Progress](../progress/struct.Progress.html) of the updates.
+    ///
+    /// The stats of the index are polled every `interval` until they report that the index is not indexing for two consecutive polls.
+    /// The final stats are returned, or [Error::Timeout](../errors/enum.Error.html#variant.Timeout) after `timeout`.
+    /// Transient failures are retried like in [Progress::wait_with_policy](../progress/struct.Progress.html#method.wait_with_policy).
+    /// The defaults of `interval` and `timeout` are those of [WaitPolicy](../progress/struct.WaitPolicy.html).
+    ///
+    /// **Caveat**: this races with updates enqueued in the meantime.
+    /// An update enqueued right after the last poll is not waited for, and updates enqueued continuously can keep the index busy until the timeout.
+    /// Prefer waiting for the [Progress](../progress/struct.Progress.html) of the updates when you have it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_wait_until_idle").await.unwrap();
+    ///
+    /// let stats = movies.wait_until_idle(None, Some(Duration::from_secs(60))).await.unwrap();
+    /// assert!(!stats.is_indexing);
+    /// # client.delete_index("movies_wait_until_idle").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn wait_until_idle(&self, interval: Option<Duration>, timeout: Option<Duration>) -> Result<IndexStats, Error> {
+        let default = WaitPolicy::default();
+        let policy = WaitPolicy {
+            interval: interval.unwrap_or(default.interval),
+            timeout: timeout.unwrap_or(default.timeout),
+            ..default
+        };
+        let mut elapsed_time = Duration::new(0, 0);
+        let mut failed_attempts = 0;
+        let mut idle_polls = 0;
+
+        while policy.timeout > elapsed_time {
+            // The cache is bypassed since the stats change while indexing
+            let wait = match request::<(), IndexStats>(
+                &format!("{}/indexes/{}/stats", self.host, self.uid),
+                &self.api_key,
+                Method::Get,
+                200,
+                self.max_response_bytes,
+            ).await {
+                Ok(stats) if !stats.is_indexing => {
+                    failed_attempts = 0;
+                    idle_polls += 1;
+                    if idle_polls == 2 {
+                        if let Some(cache) = &self.cache {
+                            cache.finish_all_updates(&self.host, &self.uid);
+                        }
+                        return Ok(stats);
+                    }
+                    policy.interval
+                }
+                Ok(_) => {
+                    failed_attempts = 0;
+                    idle_polls = 0;
+                    policy.interval
+                }
+                Err(error) if is_transient(&error) && failed_attempts < policy.max_poll_retries => {
+                    failed_attempts += 1;
+                    policy.backoff(failed_attempts)
+                }
+                Err(error) => return Err(error),
+            };
+            elapsed_time = elapsed_time.saturating_add(wait);
+            async_sleep(wait).await;
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Gather the server version, the settings and stats of the index and the serialized query into a single [DebugBundle](struct.DebugBundle.html).
+    ///
+    /// Documents are not included unless `sample_documents` is set, in which case the first `sample_documents` documents of the index are included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let query = movies.search().with_query("space").build();
+    /// let bundle = movies.debug_bundle(&query, None).await.unwrap();
+    /// println!("{}", serde_json::to_string_pretty(&bundle).unwrap());
+    /// # });
+    /// ```
+    pub async fn debug_bundle(&self, query: &Query<'_>, sample_documents: Option<usize>) -> Result<DebugBundle, Error> {
+        let version = request::<(), Version>(
+            &format!("{}/version", self.host),
+            &self.api_key,
             Method::Get,
             200,
-        ).await
+            self.max_response_bytes,
+        ).await?;
+        let documents = match sample_documents {
+            Some(limit) => Some(request::<(), Vec<serde_json::Value>>(
+                &format!("{}/indexes/{}/documents?limit={}", self.host, self.uid, limit),
+                &self.api_key,
+                Method::Get,
+                200,
+                self.max_response_bytes,
+            ).await?),
+            None => None,
+        };
+
+        Ok(DebugBundle {
+            version,
+            index_uid: self.uid.to_string(),
+            settings: self.get_settings().await?,
+            stats: self.get_stats().await?,
+            query: serde_json::to_value(query).map_err(Error::ParseError)?,
+            curl: query.to_curl(),
+            documents,
+        })
+    }
+
+    /// Copy the settings and the documents of the index into a [LocalIndexSnapshot](struct.LocalIndexSnapshot.html).
+    ///
+    /// Documents are fetched by batches of 1000 and each batch is deserialized as soon as it is received.
+    /// The snapshot can be serialized to disk and reloaded later, to test ranking changes without a server for example.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, indexes::{LocalIndexSnapshot, SnapshotOptions}};
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Serialize, Deserialize, Debug)]
+    /// # struct Movie {
+    /// #    name: String,
+    /// # }
+    /// # impl Document for Movie {
+    /// #    type UIDType = String;
+    /// #    fn get_uid(&self) -> &Self::UIDType { &self.name }
+    /// # }
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let snapshot = movies.snapshot::<Movie>(SnapshotOptions::default()).await.unwrap();
+    /// let json = serde_json::to_string(&snapshot).unwrap();
+    /// let reloaded: LocalIndexSnapshot<Movie> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(reloaded.documents.len(), snapshot.documents.len());
+    /// # });
+    /// ```
+    pub async fn snapshot<T: 'static + Document>(&self, options: SnapshotOptions<'_>) -> Result<LocalIndexSnapshot<T>, Error> {
+        const BATCH_SIZE: usize = 1000;
+
+        let version = request::<(), Version>(
+            &format!("{}/version", self.host),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        // `SystemTime::now` panics on wasm32-unknown-unknown
+        #[cfg(not(target_arch = "wasm32"))]
+        let taken_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        #[cfg(target_arch = "wasm32")]
+        let taken_at = (js_sys::Date::now() / 1000.0) as u64;
+        let settings = request::<(), Settings>(
+            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+
+        let fields = options.fields.map(|fields| fields.join(","));
+        let mut documents = Vec::new();
+        loop {
+            let batch = request::<(), Vec<T>>(
+                &self.documents_url(Some(documents.len()), Some(BATCH_SIZE), fields.as_deref(), options.filter, false),
+                &self.api_key,
+                Method::Get,
+                200,
+                self.max_response_bytes,
+            ).await?;
+            let batch_len = batch.len();
+            documents.extend(batch);
+            if batch_len < BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(LocalIndexSnapshot {
+            index_uid: self.uid.to_string(),
+            version,
+            taken_at,
+            settings,
+            documents,
+        })
+    }
+
+    /// Collect the uids of all the documents of the index into a [UidSet](struct.UidSet.html).
+    ///
+    /// Only `field` (the primary key) is retrieved, `page_size` documents at a time, so the memory use grows with the number of documents but not with their size.
+    /// See [for_each_uid](#method.for_each_uid) to process the uids without storing them.
+    ///
+    /// A typical use is to delete the documents that were not reimported:
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # use serde::{Serialize, Deserialize};
+    /// #
+    /// # #[derive(Serialize, Deserialize, Debug)]
+    /// # struct Movie {
+    /// #    id: usize,
+    /// # }
+    /// # impl Document for Movie {
+    /// #    type UIDType = usize;
+    /// #    fn get_uid(&self) -> &Self::UIDType { &self.id }
+    /// # }
+    /// #
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let mut orphans = movies.collect_uids("id", 1000).await.unwrap();
+    /// let dataset = vec![Movie { id: 1 }, Movie { id: 2 }];
+    /// movies.add_or_replace(&dataset, Some("id")).await.unwrap();
+    /// orphans.remove_documents(&dataset);
+    /// orphans.difference_delete(&movies, 1000).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn collect_uids(&self, field: &str, page_size: usize) -> Result<UidSet, Error> {
+        let mut uids = HashSet::new();
+        self.for_each_uid(field, page_size, |uid| {
+            uids.insert(uid);
+        }).await?;
+        Ok(UidSet { uids })
+    }
+
+    /// Call `callback` with the uid of each document of the index, and return the number of documents visited.
+    ///
+    /// Same as [collect_uids](#method.collect_uids), without storing the uids.
+    /// String and number uids are passed as strings, and documents without `field` are skipped.
+    pub async fn for_each_uid(&self, field: &str, page_size: usize, mut callback: impl FnMut(String)) -> Result<usize, Error> {
+        if page_size == 0 {
+            return Err(Error::InvalidRequest);
+        }
+
+        let mut offset = 0;
+        loop {
+            let page = request::<(), Vec<serde_json::Map<String, serde_json::Value>>>(
+                &self.documents_url(Some(offset), Some(page_size), Some(field), None, false),
+                &self.api_key,
+                Method::Get,
+                200,
+                self.max_response_bytes,
+            ).await?;
+            let page_len = page.len();
+            for mut document in page {
+                match document.remove(field) {
+                    Some(serde_json::Value::String(uid)) => callback(uid),
+                    Some(serde_json::Value::Number(uid)) => callback(uid.to_string()),
+                    _ => (),
+                }
+            }
+            offset += page_len;
+            if page_len < page_size {
+                return Ok(offset);
+            }
+        }
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IndexStats {
     pub number_of_documents: usize,
@@ -832,41 +1946,1185 @@ pub struct IndexStats {
     pub field_distribution: HashMap<String, usize>,
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{client::*, progress::UpdateStatus};
-    use futures_await_test::async_test;
+/// Everything needed to reproduce a search issue, gathered by [Index::debug_bundle](struct.Index.html#method.debug_bundle).
+///
+/// It can be serialized (to JSON for example) and attached to a bug report.
+/// It never contains the API key.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugBundle {
+    /// Version of the MeiliSearch server
+    pub version: Version,
+    /// Uid of the index
+    pub index_uid: String,
+    /// Settings of the index
+    pub settings: Settings,
+    /// Stats of the index
+    pub stats: IndexStats,
+    /// The query, as sent to the server
+    pub query: serde_json::Value,
+    /// A `curl` command reproducing the query (see [Query::to_curl](../search/struct.Query.html#method.to_curl))
+    pub curl: String,
+    /// A sample of the documents of the index, only included when explicitly requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<serde_json::Value>>,
+}
 
-    #[async_test]
-    async fn test_get_all_updates_no_docs() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let uid = "test_get_all_updates_no_docs";
+/// Options of [Index::snapshot](struct.Index.html#method.snapshot).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnapshotOptions<'a> {
+    /// The fields to keep in each document. Default: all fields.
+    pub fields: Option<&'a [&'a str]>,
+    /// Only keep the documents matching this [filter](https://docs.meilisearch.com/reference/features/filtering.html). Default: all documents.
+    pub filter: Option<&'a str>,
+}
 
-        let index = client.get_or_create(uid).await.unwrap();
-        let status = index.get_all_updates().await.unwrap();
-        client.delete_index(uid).await.unwrap();
+/// Options of [Index::recommend_for](struct.Index.html#method.recommend_for).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecommendOptions<'a> {
+    /// The embedder whose vectors are compared
+    pub embedder: &'a str,
+    /// The maximum number of documents to return. Default: the limit of MeiliSearch (20)
+    pub limit: Option<usize>,
+    /// Only recommend the documents matching this [filter](https://docs.meilisearch.com/reference/features/filtering.html). Default: all documents.
+    pub filter: Option<&'a str>,
+}
 
-        assert_eq!(status.len(), 0);
+/// The description of a [get_documents_with_params](struct.Index.html#method.get_documents_with_params) query, independent of any index.
+///
+/// All the fields are optional when deserialized, so that queries can be read from a configuration file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DocumentsQueryParams {
+    /// The number of documents to skip. Default: 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// The maximum number of documents to return. Default: 20
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// The fields to retrieve. Default: all fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Only return the documents matching this [filter](https://docs.meilisearch.com/reference/features/filtering.html). Default: all documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Whether the documents include their vectors, in their `_vectors` field. Default: `false`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub retrieve_vectors: bool,
+}
+
+impl DocumentsQueryParams {
+    /// Only return the documents matching `filter`.
+    ///
+    /// The attributes used in the filter must be [filterable](../settings/struct.Settings.html#structfield.filterable_attributes).
+    pub fn with_filter(self, filter: impl Into<String>) -> DocumentsQueryParams {
+        DocumentsQueryParams { filter: Some(filter.into()), ..self }
     }
 
-    #[async_test]
-    async fn test_get_one_update() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let uid = "test_get_one_update";
+    /// Whether the documents include their vectors, in their `_vectors` field.
+    ///
+    /// The field can be read into a [Vectors](../document/struct.Vectors.html) field of the document type.
+    pub fn with_retrieve_vectors(self, retrieve_vectors: bool) -> DocumentsQueryParams {
+        DocumentsQueryParams { retrieve_vectors, ..self }
+    }
+}
+
+/// Where a [documents stream](struct.Index.html#method.documents_stream_from) stopped.
+///
+/// The token only holds plain data, so that it can be stored with serde and used by another process.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeToken {
+    /// The number of documents read before this token
+    pub offset: usize,
+    /// The number of documents fetched per request
+    pub page_size: usize,
+    /// The fields, filter and vectors of the documents. Their `offset` and `limit` are ignored.
+    #[serde(default)]
+    pub params: DocumentsQueryParams,
+}
+
+impl ResumeToken {
+    /// A token to read the documents matching `params` from the start, or from their `offset`, `page_size` at a time.
+    pub fn new(page_size: usize, params: DocumentsQueryParams) -> ResumeToken {
+        ResumeToken {
+            offset: params.offset.unwrap_or(0),
+            page_size,
+            params: DocumentsQueryParams { offset: None, limit: None, ..params },
+        }
+    }
+}
+
+/// The description of a [get_document_with_params](struct.Index.html#method.get_document_with_params) query, independent of any index.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DocumentQueryParams {
+    /// The fields to retrieve. Default: all fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Whether the document includes its vectors, in its `_vectors` field. Default: `false`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub retrieve_vectors: bool,
+}
 
-        let index = client.get_or_create(uid).await.unwrap();
+impl DocumentQueryParams {
+    /// Only retrieve these fields of the document.
+    pub fn with_fields<I: IntoIterator<Item = S>, S: Into<String>>(self, fields: I) -> DocumentQueryParams {
+        DocumentQueryParams { fields: Some(fields.into_iter().map(Into::into).collect()), ..self }
+    }
+
+    /// Whether the document includes its vectors, in its `_vectors` field.
+    ///
+    /// The field can be read into a [Vectors](../document/struct.Vectors.html) field of the document type.
+    pub fn with_retrieve_vectors(self, retrieve_vectors: bool) -> DocumentQueryParams {
+        DocumentQueryParams { retrieve_vectors, ..self }
+    }
+}
+
+/// The response of `POST /indexes/{uid}/documents/fetch`.
+#[derive(Deserialize)]
+struct FetchedDocuments<T> {
+    results: Vec<T>,
+}
+
+/// The description of a [delete_documents_with_params](struct.Index.html#method.delete_documents_with_params) deletion, independent of any index.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DocumentDeletionParams {
+    /// The [filter](https://docs.meilisearch.com/reference/features/filtering.html) selecting the documents to delete. It must not be empty.
+    pub filter: String,
+}
+
+/// The first vector in the `_vectors` entry of an embedder.
+///
+/// Depending on the embedder, the entry is a vector, an array of vectors, or an object like `{"embeddings": [...], "regenerate": false}`.
+fn first_vector(entry: &serde_json::Value) -> Option<Vec<f32>> {
+    match entry {
+        serde_json::Value::Object(entry) => first_vector(entry.get("embeddings")?),
+        serde_json::Value::Array(values) => match values.first()? {
+            first @ serde_json::Value::Array(_) => first_vector(first),
+            _ => values.iter().map(|value| value.as_f64().map(|value| value as f32)).collect(),
+        },
+        _ => None,
+    }
+}
+
+/// Exclude the document whose primary key is `id` from the documents selected by `filter`.
+fn exclusion_filter(filter: Option<&str>, primary_key: &str, id: &str) -> String {
+    let exclusion = format!("NOT {} = {}", primary_key, escape_value(id));
+    match filter {
+        Some(filter) => format!("({}) AND {}", filter, exclusion),
+        None => exclusion,
+    }
+}
+
+/// A set of document uids, collected by [Index::collect_uids](struct.Index.html#method.collect_uids).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UidSet {
+    uids: HashSet<String>,
+}
+
+impl UidSet {
+    /// The number of uids in the set.
+    pub fn len(&self) -> usize {
+        self.uids.len()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.uids.is_empty()
+    }
+
+    /// Whether the set contains `uid`.
+    pub fn contains(&self, uid: impl Display) -> bool {
+        self.uids.contains(&uid.to_string())
+    }
+
+    /// Iterate over the uids, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.uids.iter().map(String::as_str)
+    }
+
+    /// Remove `uid` from the set. Returns whether it was in the set.
+    pub fn remove(&mut self, uid: impl Display) -> bool {
+        self.uids.remove(&uid.to_string())
+    }
+
+    /// Remove the uids of `documents` from the set, after they were reimported for example.
+    pub fn remove_documents<T: Document>(&mut self, documents: &[T]) {
+        for document in documents {
+            self.uids.remove(&document.get_uid().to_string());
+        }
+    }
+
+    /// Delete the documents whose uid is still in the set, by batches of `batch_size`.
+    ///
+    /// Returns the progress of each batch. Fails before sending anything if `batch_size` is 0.
+    pub async fn difference_delete(&self, index: &Index, batch_size: usize) -> Result<Vec<Progress>, Error> {
+        let uids: Vec<&str> = self.iter().collect();
+        index.delete_documents_in_batches(&uids, Some(batch_size)).await
+    }
+}
+
+/// A local copy of an index, made by [Index::snapshot](struct.Index.html#method.snapshot).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+pub struct LocalIndexSnapshot<T> {
+    /// UID of the index
+    pub index_uid: String,
+    /// Version of the MeiliSearch server the snapshot was taken from
+    pub version: Version,
+    /// When the snapshot was taken, in seconds since the Unix epoch
+    pub taken_at: u64,
+    /// Settings of the index
+    pub settings: Settings,
+    /// Documents of the index
+    pub documents: Vec<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        exclusion_filter, first_vector, DocumentDeletionParams, DocumentQueryParams, DocumentsQueryParams, LocalIndexSnapshot, RecommendOptions, ResumeToken, SnapshotOptions,
+        UidSet,
+    };
+    use crate::{client::*, document::Vectors, errors::{Error, ErrorCode, ErrorType}, progress::UpdateStatus, request::tests::mock_server, tests::TestIndex};
+    use futures::StreamExt;
+    use futures_await_test::async_test;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Movie {
+        id: usize,
+        title: String,
+    }
+
+    #[test]
+    fn test_documents_url() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+        assert_eq!(
+            index.documents_url(Some(2), None, Some("title"), None, true),
+            "http://localhost:7700/indexes/movies/documents?offset=2&retrieveVectors=true&attributesToRetrieve=title"
+        );
+        assert_eq!(index.documents_url(None, Some(5), None, None, false), "http://localhost:7700/indexes/movies/documents?limit=5");
+        assert_eq!(index.documents_url(None, None, None, None, false), "http://localhost:7700/indexes/movies/documents");
+        assert_eq!(
+            index.documents_url(None, None, Some("title,release date"), None, false),
+            "http://localhost:7700/indexes/movies/documents?attributesToRetrieve=title%2Crelease%20date"
+        );
+        assert_eq!(
+            index.documents_url(None, Some(10), None, Some("year > 2000"), false),
+            "http://localhost:7700/indexes/movies/documents?limit=10&filter=year%20%3E%202000"
+        );
+    }
+
+    #[test]
+    fn test_documents_params_serialization() {
+        let params = DocumentsQueryParams {
+            limit: Some(5),
+            fields: Some(vec!["id".to_string(), "title".to_string()]),
+            filter: Some("year > 2000".to_string()),
+            ..DocumentsQueryParams::default()
+        };
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json, serde_json::json!({"limit": 5, "fields": ["id", "title"], "filter": "year > 2000"}));
+        assert_eq!(serde_json::from_value::<DocumentsQueryParams>(json).unwrap(), params);
+
+        // Every field can be left out
+        let params: DocumentsQueryParams = serde_json::from_str(r#"{"retrieveVectors":true}"#).unwrap();
+        assert_eq!(params, DocumentsQueryParams { retrieve_vectors: true, ..DocumentsQueryParams::default() });
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"retrieveVectors":true}"#);
+        assert_eq!(serde_json::from_str::<DocumentsQueryParams>("{}").unwrap(), DocumentsQueryParams::default());
+
+        let params = DocumentDeletionParams { filter: "year < 1950".to_string() };
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"filter":"year < 1950"}"#);
+        assert_eq!(serde_json::from_str::<DocumentDeletionParams>(r#"{"filter":"year < 1950"}"#).unwrap(), params);
+    }
+
+    #[async_test]
+    async fn test_documents_params_requests() {
+        let (host, requests) = mock_server(vec![(200, r#"[{"id":1,"title":"Dune","year":1965}]"#), (200, "[]"), (202, ENQUEUED)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let params = DocumentsQueryParams { offset: Some(10), fields: Some(vec!["id".to_string(), "title".to_string()]), ..DocumentsQueryParams::default() };
+        let books: Vec<Book> = index.get_documents_with_params(&params).await.unwrap();
+        assert_eq!(books, [Book { id: 1, title: "Dune".to_string(), year: 1965 }]);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=10&attributesToRetrieve=id%2Ctitle "));
+
+        // The borrowing methods send the same request
+        let _: Vec<Book> = index.get_documents(Some(10), None, Some("id,title")).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=10&attributesToRetrieve=id%2Ctitle "));
+
+        index.delete_documents_with_params(&DocumentDeletionParams { filter: "year < 1950".to_string() }).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents/delete "));
+        assert!(request.ends_with(r#"{"filter":"year < 1950"}"#), "{}", request);
+
+        // An empty filter is never sent
+        let error = index.delete_documents_with_params(&DocumentDeletionParams::default()).await.err().unwrap();
+        assert!(matches!(error, Error::InvalidRequest));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_documents_fetch_route() {
+        const FETCHED: &str = r#"{"results":[{"id":1,"title":"Dune","year":1965}],"offset":0,"limit":20,"total":1}"#;
+        let (host, requests) = mock_server(vec![(200, FETCHED), (200, "[]")]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        // With a filter, the parameters are posted to the fetch route
+        let params = DocumentsQueryParams { limit: Some(20), fields: Some(vec!["id".to_string()]), ..DocumentsQueryParams::default() }.with_filter("year < 1970");
+        let books: Vec<Book> = index.get_documents_with_params(&params).await.unwrap();
+        assert_eq!(books, [Book { id: 1, title: "Dune".to_string(), year: 1965 }]);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents/fetch "), "{}", request);
+        assert!(request.ends_with(r#"{"limit":20,"fields":["id"],"filter":"year < 1970"}"#), "{}", request);
+
+        // Without a filter, GET is still used
+        let params = DocumentsQueryParams { limit: Some(20), ..DocumentsQueryParams::default() };
+        let books: Vec<Book> = index.get_documents_with_params(&params).await.unwrap();
+        assert!(books.is_empty());
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?limit=20 "));
+    }
+
+    #[async_test]
+    async fn test_get_documents_with_filter() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Block {
+            id: usize,
+            kind: String,
+        }
+
+        impl crate::document::Document for Block {
+            type UIDType = usize;
+
+            fn get_uid(&self) -> &Self::UIDType {
+                &self.id
+            }
+        }
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_get_documents_with_filter").await;
+        let blocks: Vec<Block> = (0..6).map(|id| Block { id, kind: if id % 2 == 0 { "title" } else { "text" }.to_string() }).collect();
+        index.add_documents_and_wait(&blocks, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["kind"], None, None).await.unwrap();
+
+        let params = DocumentsQueryParams::default().with_filter("kind = title");
+        let titles: Vec<Block> = index.get_documents_with_params(&params).await.unwrap();
+        assert_eq!(titles.len(), 3);
+        assert!(titles.iter().all(|block| block.kind == "title"));
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Product {
+        sku: String,
+        product_id: usize,
+    }
+
+    impl crate::document::Document for Product {
+        type UIDType = String;
+        const PRIMARY_KEY: Option<&'static str> = Some("sku");
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.sku
+        }
+    }
+
+    #[async_test]
+    async fn test_add_documents_with_uid_requests() {
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":1}"#), (202, r#"{"updateId":2}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("products");
+        index.add_documents_with_uid(&[Product { sku: "a-1".to_string(), product_id: 1 }]).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/products/documents?primaryKey=sku "));
+
+        // Without a declared primary key, MeiliSearch infers it
+        index.add_documents_with_uid(&[Item { id: 1, kind: "a".to_string(), value: None }]).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/products/documents "));
+    }
+
+    #[async_test]
+    async fn test_add_documents_with_uid() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_add_documents_with_uid").await;
+        let products: Vec<Product> = (0..3).map(|id| Product { sku: format!("sku-{}", id), product_id: id }).collect();
+        index.add_documents_with_uid(&products).await.unwrap().wait_until_processed(None, None).await.unwrap();
+
+        // MeiliSearch would have inferred `product_id`
+        let info: serde_json::Value = client.raw_request(&format!("indexes/{}", index.uid), Method::<()>::Get, (), 200).await.unwrap();
+        assert_eq!(info["primaryKey"], "sku", "{}", info);
+        assert_eq!(index.get_document::<Product>("sku-2".to_string()).await.unwrap(), products[2]);
+
+        // The index has another primary key: the update fails with the error of MeiliSearch
+        let other = TestIndex::new(&client, "test_add_documents_with_uid_mismatch").await;
+        other.set_primary_key("product_id").await.unwrap();
+        let error = other.add_documents_with_uid(&products).await.unwrap().wait_until_processed(None, None).await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { .. }), "{:?}", error);
+    }
+
+    #[async_test]
+    async fn test_document_ids() {
+        let index = Client::new("http://localhost:7700", "masterKey").assume_index("movies");
+        assert_eq!(index.document_path(42u64).unwrap(), "http://localhost:7700/indexes/movies/documents/42");
+        assert_eq!(index.document_path("harry_potter-1").unwrap(), "http://localhost:7700/indexes/movies/documents/harry_potter-1");
+        assert!(index.document_path("a".repeat(511)).is_ok());
+
+        // The invalid ids are refused before sending anything
+        for id in ["a/b", "a b", "", "é", "../settings"].iter().map(|id| id.to_string()).chain(std::iter::once("a".repeat(512))) {
+            assert!(matches!(index.document_path(&id), Err(Error::InvalidDocumentId { id: ref rendered }) if *rendered == id), "{:?}", id);
+        }
+        assert!(matches!(index.delete_document("a/b").await, Err(Error::InvalidDocumentId { .. })));
+        assert!(matches!(index.document_exists("a b").await, Err(Error::InvalidDocumentId { .. })));
+
+        let (host, requests) = mock_server(vec![(200, r#"{"id": 7, "kind": "a"}"#), (200, "{}")]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        assert_eq!(index.get_document::<Item>(7).await.unwrap().id, 7);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents/7 "));
+        assert!(index.document_exists(7u64).await.unwrap());
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents/7?fields= "));
+    }
+
+    #[async_test]
+    async fn test_document_exists_requests() {
+        let (host, requests) = mock_server(vec![
+            (200, "{}"),
+            (404, r#"{"message": "Document `2` not found.", "code": "document_not_found", "type": "invalid_request", "link": "https://docs.meilisearch.com/errors#document_not_found"}"#),
+            (403, r#"{"message": "The provided API key is invalid.", "code": "invalid_api_key", "type": "auth", "link": "https://docs.meilisearch.com/errors#invalid_api_key"}"#),
+        ]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        assert!(index.document_exists(1).await.unwrap());
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents/1?fields= "));
+        assert!(!index.document_exists("2").await.unwrap());
+        let error = index.document_exists("3").await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { error_code: ErrorCode::InvalidApiKey, .. }), "{:?}", error);
+    }
+
+    #[async_test]
+    async fn test_document_exists() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_document_exists").await;
+        index.add_documents_and_wait(&[Item { id: 1, kind: "a".to_string(), value: None }], Some("id"), None, None).await.unwrap();
+
+        assert!(index.document_exists(1).await.unwrap());
+        assert!(!index.document_exists(2).await.unwrap());
+
+        let unauthorized = Client::new("http://localhost:7700", "wrongKey").assume_index(index.uid.as_str());
+        let error = unauthorized.document_exists(1).await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { error_type: ErrorType::Authentication, .. }), "{:?}", error);
+    }
+
+    #[async_test]
+    async fn test_all_documents_requests() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"[{"id": 1, "title": "Dune"}, {"id": 2, "title": "Carrie"}]"#),
+            (200, r#"[{"id": 3, "title": "Emma"}]"#),
+        ]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let movies: Vec<Result<Movie, Error>> = index.all_documents(2).collect().await;
+        let ids: Vec<usize> = movies.into_iter().map(|movie| movie.unwrap().id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=0&limit=2 "));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=2&limit=2 "));
+        assert!(requests.try_recv().is_err());
+
+        // The stream stops at the first error
+        let (host, requests) = mock_server(vec![(200, r#"[{"id": 1, "title": "Dune"}]"#), (500, r#"{"message": "oops"}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let movies: Vec<Result<Movie, Error>> = index.all_documents(1).collect().await;
+        assert_eq!(movies.len(), 2);
+        assert!(movies[0].is_ok() && movies[1].is_err());
+        assert_eq!(requests.try_iter().count(), 2);
+
+        let movies: Vec<Result<Movie, Error>> = index.all_documents(0).collect().await;
+        assert!(matches!(movies.as_slice(), [Err(Error::InvalidRequest)]));
+    }
+
+    #[async_test]
+    async fn test_documents_stream_resume() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"[{"id": 1, "title": "Dune"}, {"id": 2, "title": "Carrie"}]"#),
+            (200, r#"[{"id": 3, "title": "Emma"}, {"id": 4, "title": "Ulysses"}]"#),
+            (200, r#"[{"id": 4, "title": "Ulysses"}, {"id": 5, "title": "Walden"}]"#),
+            (200, r#"[]"#),
+        ]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        // The first process stops in the middle of the second page
+        let params = DocumentsQueryParams { fields: Some(vec!["id".to_string(), "title".to_string()]), ..DocumentsQueryParams::default() };
+        let token = ResumeToken::new(2, params);
+        let read: Vec<(Movie, ResumeToken)> = index.documents_stream_from(token).take(3).map(Result::unwrap).collect().await;
+        let mut ids: Vec<usize> = read.iter().map(|(movie, _)| movie.id).collect();
+        let token = serde_json::to_string(&read[2].1).unwrap();
+        assert_eq!(token, r#"{"offset":3,"pageSize":2,"params":{"fields":["id","title"]}}"#);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=0&limit=2&"));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=2&limit=2&"));
+
+        // Another one continues after the last document read
+        let token: ResumeToken = serde_json::from_str(&token).unwrap();
+        let rest: Vec<(Movie, ResumeToken)> = index.documents_stream_from(token).map(Result::unwrap).collect().await;
+        ids.extend(rest.iter().map(|(movie, _)| movie.id));
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(rest.last().unwrap().1.offset, 5);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=3&limit=2&"));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?offset=5&limit=2&"));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_all_documents() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_all_documents").await;
+        let items: Vec<Item> = (0..23).map(|id| Item { id, kind: if id % 3 == 0 { "a" } else { "b" }.to_string(), value: None }).collect();
+        index.add_documents_and_wait(&items, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["kind"], None, None).await.unwrap();
+
+        let params = DocumentsQueryParams { limit: Some(100), ..DocumentsQueryParams::default() };
+        let mut expected: Vec<usize> = index.get_documents_with_params::<Item>(&params).await.unwrap().into_iter().map(|item| item.id).collect();
+        expected.sort_unstable();
+        let streamed: Vec<Item> = index.all_documents(5).map(Result::unwrap).collect().await;
+        let mut ids: Vec<usize> = streamed.into_iter().map(|item| item.id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, expected);
+        assert_eq!(ids, (0..23).collect::<Vec<_>>());
+
+        let params = DocumentsQueryParams::default().with_filter("kind = a");
+        let streamed: Vec<Item> = index.all_documents_with_params(5, params).map(Result::unwrap).collect().await;
+        assert_eq!(streamed.len(), 8);
+        assert!(streamed.iter().all(|item| item.kind == "a"));
+    }
+
+    #[async_test]
+    async fn test_retrieve_vectors_requests() {
+        let index = Client::new("http://localhost:7700", "masterKey").assume_index("movies");
+        let params = DocumentQueryParams::default().with_fields(["id", "title"]).with_retrieve_vectors(true);
+        assert_eq!(
+            index.document_url(7, &params).unwrap(),
+            "http://localhost:7700/indexes/movies/documents/7?fields=id%2Ctitle&retrieveVectors=true"
+        );
+        let params = params.with_retrieve_vectors(false);
+        assert_eq!(index.document_url("a", &params).unwrap(), "http://localhost:7700/indexes/movies/documents/a?fields=id%2Ctitle");
+
+        let (host, requests) = mock_server(vec![(200, "[]"), (200, r#"{"results": []}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let params = DocumentsQueryParams::default().with_retrieve_vectors(true);
+        index.get_documents_with_params::<Movie>(&params).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/documents?retrieveVectors=true "));
+        index.get_documents_with_params::<Movie>(&params.with_filter("id > 1")).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents/fetch "), "{}", request);
+        assert!(request.ends_with(r#"{"filter":"id > 1","retrieveVectors":true}"#), "{}", request);
+    }
+
+    #[cfg(all(feature = "experimental-vector-store", feature = "documents-io"))]
+    #[async_test]
+    async fn test_get_documents_with_vectors() {
+        #[derive(Debug, Deserialize)]
+        struct Embedded {
+            id: usize,
+            #[serde(rename = "_vectors")]
+            vectors: Vectors,
+        }
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_get_documents_with_vectors").await;
+        let mut embedders = std::collections::HashMap::new();
+        embedders.insert("manual".to_string(), crate::settings::Embedder::UserProvided { dimensions: 3 });
+        index.set_embedders_and_wait(&embedders, None, None).await.unwrap();
+        let documents = b"{\"id\":1,\"title\":\"Interstellar\",\"_vectors\":{\"manual\":[0.1,0.2,0.3]}}\n\
+            {\"id\":2,\"title\":\"Apollo 13\",\"_vectors\":{\"manual\":[0.3,0.2,0.1]}}\n";
+        index.add_documents_ndjson(documents, Some("id")).await.unwrap().wait_until_processed(None, None).await.unwrap();
+
+        let params = DocumentQueryParams::default().with_retrieve_vectors(true);
+        let document: Embedded = index.get_document_with_params(1, &params).await.unwrap();
+        assert_eq!(document.id, 1);
+        assert!(document.vectors.get("manual").is_some(), "{:?}", document);
+
+        // Without the parameter, the raw document has no vectors
+        let document: serde_json::Value = index.get_document_with_params(2, &DocumentQueryParams::default()).await.unwrap();
+        assert!(document.get("_vectors").is_none(), "{}", document);
+
+        let params = DocumentsQueryParams::default().with_retrieve_vectors(true);
+        let documents: Vec<Embedded> = index.get_documents_with_params(&params).await.unwrap();
+        assert_eq!(documents.len(), 2);
+    }
+
+    #[cfg(feature = "documents-io")]
+    const NDJSON: &[u8] = b"{\"id\":1,\"title\":\"Dune\"}\n{\"id\":2,\"title\":\"Carrie\"}\n";
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_ndjson_requests() {
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":1}"#), (202, r#"{"updateId":2}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let progress = index.add_documents_ndjson(NDJSON, Some("id")).await.unwrap();
+        assert_eq!(progress.get_update_id(), 1);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents?primaryKey=id "), "{}", request);
+        assert!(request.to_lowercase().contains("content-type: application/x-ndjson\r\n"), "{}", request);
+        assert!(request.ends_with(std::str::from_utf8(NDJSON).unwrap()), "{}", request);
+
+        index.update_documents_ndjson(NDJSON, None).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("PUT /indexes/movies/documents "));
+
+        // Read-only clients refuse them like the other writes
+        let index = Client::new("http://localhost:7700", "masterKey").with_read_only(true).assume_index("movies");
+        assert!(matches!(index.add_documents_ndjson(NDJSON, None).await, Err(Error::ReadOnlyClient { .. })));
+    }
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_compressed_requests() {
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":1}"#), (202, r#"{"updateId":2}"#), (202, r#"{"updateId":3}"#)]);
+        let index = Client::new(host, "masterKey").with_request_compression(Compression::Gzip).assume_index("movies");
+
+        // Below the threshold, the documents are not compressed
+        index.add_documents_ndjson(NDJSON, Some("id")).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(!request.to_lowercase().contains("content-encoding"), "{}", request);
+        assert!(request.ends_with(std::str::from_utf8(NDJSON).unwrap()), "{}", request);
+
+        let items: Vec<Item> = (0..100).map(|id| Item { id, kind: format!("kind {}", id), value: None }).collect();
+        index.add_or_update(&items, Some("id")).await.unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("put /indexes/movies/documents?primarykey=id "), "{}", request);
+        assert!(request.contains("content-encoding: gzip\r\n"), "{}", request);
+        assert!(request.contains("content-type: application/json\r\n"), "{}", request);
+
+        let payload = b"id,title\n".iter().chain(&b"1,Dune\n".repeat(200)).copied().collect::<Vec<u8>>();
+        index.add_documents_csv(&payload, None, None).await.unwrap();
+        assert!(requests.recv().unwrap().to_lowercase().contains("content-encoding: gzip\r\n"));
+
+        // Other requests are never compressed
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":4}"#)]);
+        let index = Client::new(host, "masterKey").with_request_compression(Compression::Gzip).assume_index("movies");
+        let uids: Vec<usize> = (0..1000).collect();
+        index.delete_documents(&uids).await.unwrap();
+        assert!(!requests.recv().unwrap().to_lowercase().contains("content-encoding"));
+    }
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_add_compressed_documents() {
+        let client = Client::new("http://localhost:7700", "masterKey").with_request_compression(Compression::Gzip);
+        let index = TestIndex::new(&client, "test_add_compressed_documents").await;
+        let items: Vec<Item> = (0..500).map(|id| Item { id, kind: format!("kind {}", id), value: None }).collect();
+        index.add_or_replace_and_wait(&items, Some("id"), None, None).await.unwrap();
+        // A single document is sent uncompressed
+        index.add_or_update_and_wait(&[Item { id: 500, kind: "last".to_string(), value: None }], None::<&str>, None, None).await.unwrap();
+
+        assert_eq!(index.get_stats().await.unwrap().number_of_documents, 501);
+        assert_eq!(index.get_document::<Item>(499).await.unwrap(), items[499]);
+        assert_eq!(index.get_document::<Item>(500).await.unwrap().kind, "last");
+    }
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_add_documents_ndjson() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_add_documents_ndjson").await;
+        index.add_documents_ndjson(NDJSON, Some("id")).await.unwrap().wait_until_processed(None, None).await.unwrap();
+
+        let results = index.search().with_query("dune").execute::<Movie>().await.unwrap();
+        assert_eq!(results.hits[0].result, Movie { id: 1, title: "Dune".to_string() });
+
+        index.update_documents_ndjson(b"{\"id\":2,\"title\":\"Christine\"}\n", None).await.unwrap()
+            .wait_until_processed(None, None).await.unwrap();
+        let movies: Vec<Movie> = index.get_documents_with_params(&DocumentsQueryParams::default()).await.unwrap();
+        assert_eq!(movies, [Movie { id: 1, title: "Dune".to_string() }, Movie { id: 2, title: "Christine".to_string() }]);
+    }
+
+    #[cfg(feature = "documents-io")]
+    const CSV: &[u8] = b"id;title;count:number;available:boolean\n1;Dune;3;true\n2;Carrie, the novel;0;false\n";
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_csv_requests() {
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":1}"#), (202, r#"{"updateId":2}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        index.add_documents_csv(CSV, Some("id"), Some(';')).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents?primaryKey=id&csvDelimiter=%3B "), "{}", request);
+        assert!(request.to_lowercase().contains("content-type: text/csv\r\n"), "{}", request);
+        assert!(request.ends_with(std::str::from_utf8(CSV).unwrap()), "{}", request);
+
+        index.add_documents_csv(b"id,title\n1,Dune\n", None, None).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/documents "));
+
+        // Nothing is sent with an invalid delimiter
+        assert!(matches!(index.add_documents_csv(CSV, None, Some('§')).await, Err(Error::InvalidRequest)));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[cfg(feature = "documents-io")]
+    #[async_test]
+    async fn test_add_documents_csv() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_add_documents_csv").await;
+        index.add_documents_csv(CSV, Some("id"), Some(';')).await.unwrap().wait_until_processed(None, None).await.unwrap();
+
+        let documents: Vec<serde_json::Value> = index.get_documents_with_params(&DocumentsQueryParams::default()).await.unwrap();
+        assert_eq!(documents, [
+            serde_json::json!({"id": "1", "title": "Dune", "count": 3, "available": true}),
+            serde_json::json!({"id": "2", "title": "Carrie, the novel", "count": 0, "available": false}),
+        ]);
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Item {
+        id: usize,
+        kind: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    }
+
+    impl crate::document::Document for Item {
+        type UIDType = usize;
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    #[async_test]
+    async fn test_documents_in_batches_requests() {
+        let (host, requests) = mock_server(vec![(202, r#"{"updateId":1}"#), (202, r#"{"updateId":2}"#), (202, r#"{"updateId":3}"#), (202, r#"{"updateId":4}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let items: Vec<Item> = (0..5).map(|id| Item { id, kind: "text".to_string(), value: None }).collect();
+
+        let progresses = index.update_documents_in_batches(&items, Some(2), Some("id")).await.unwrap();
+        assert_eq!(progresses.iter().map(|progress| progress.get_update_id()).collect::<Vec<_>>(), [1, 2, 3]);
+        for ids in ["0,1", "2,3", "4"] {
+            let request = requests.recv().unwrap();
+            assert!(request.starts_with("PUT /indexes/movies/documents?primaryKey=id "), "{}", request);
+            assert!(request.ends_with(&format!("[{}]", ids.split(',').map(|id| format!(r#"{{"id":{},"kind":"text"}}"#, id)).collect::<Vec<_>>().join(","))), "{}", request);
+        }
+
+        // The documents fitting in a single batch are sent at once
+        let progresses = index.add_documents_in_batches(&items, None, None).await.unwrap();
+        assert_eq!(progresses.len(), 1);
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/documents "));
+
+        assert!(matches!(index.update_documents_in_batches(&items, Some(0), None).await, Err(Error::InvalidRequest)));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_update_documents_in_batches() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_update_documents_in_batches").await;
+        let items: Vec<Item> = (0..5).map(|id| Item { id, kind: "text".to_string(), value: Some(format!("value {}", id)) }).collect();
+        for progress in index.add_documents_in_batches(&items, Some(2), Some("id")).await.unwrap() {
+            progress.wait_until_processed(None, None).await.unwrap();
+        }
+
+        // Only the kind is sent, the values are kept
+        let kinds: Vec<Item> = (0..5).map(|id| Item { id, kind: "title".to_string(), value: None }).collect();
+        let progresses = index.update_documents_in_batches(&kinds, Some(2), Some("id")).await.unwrap();
+        assert_eq!(progresses.len(), 3);
+        for progress in progresses {
+            progress.wait_until_processed(None, None).await.unwrap();
+        }
+
+        let updated: Vec<Item> = index.get_documents_with_params(&DocumentsQueryParams::default()).await.unwrap();
+        let expected: Vec<Item> = (0..5).map(|id| Item { id, kind: "title".to_string(), value: Some(format!("value {}", id)) }).collect();
+        assert_eq!(updated, expected);
+    }
+
+    #[async_test]
+    async fn test_delete_documents_in_batches_requests() {
+        let responses = (1..=5).map(|id| (202, format!(r#"{{"updateId":{}}}"#, id))).collect();
+        let (host, requests) = mock_server(responses);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let uids: Vec<usize> = (0..30).collect();
+
+        let progresses = index.delete_documents_in_batches(&uids, Some(7)).await.unwrap();
+        assert_eq!(progresses.len(), 5);
+        for batch in uids.chunks(7) {
+            let request = requests.recv().unwrap();
+            assert!(request.starts_with("POST /indexes/movies/documents/delete-batch "), "{}", request);
+            assert!(request.ends_with(&serde_json::to_string(batch).unwrap()), "{}", request);
+        }
+
+        assert!(matches!(index.delete_documents_in_batches(&uids, Some(0)).await, Err(Error::InvalidRequest)));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_delete_documents_in_batches() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_delete_documents_in_batches").await;
+        let items: Vec<Item> = (0..30).map(|id| Item { id, kind: "text".to_string(), value: None }).collect();
+        index.add_documents_and_wait(&items, Some("id"), None, None).await.unwrap();
+
+        let uids: Vec<usize> = (0..30).collect();
+        for progress in index.delete_documents_in_batches(&uids, Some(7)).await.unwrap() {
+            progress.wait_until_processed(None, None).await.unwrap();
+        }
+        let remaining: Vec<Item> = index.get_documents_with_params(&DocumentsQueryParams::default()).await.unwrap();
+        assert!(remaining.is_empty(), "{:?}", remaining);
+    }
+
+    #[test]
+    fn test_document_with_vectors() {
+        let document: super::WithVectors<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Interstellar",
+            "_vectors": {"manual": {"embeddings": [[0.1, 0.2, 0.3]], "regenerate": false}}
+        })).unwrap();
+        assert_eq!(document.document, Movie { id: 1, title: "Interstellar".to_string() });
+        assert_eq!(document.vectors.unwrap()["manual"]["embeddings"], serde_json::json!([[0.1, 0.2, 0.3]]));
+
+        let document: super::WithVectors<Movie> = serde_json::from_value(serde_json::json!({"id": 2, "title": "Apollo 13"})).unwrap();
+        assert!(document.vectors.is_none());
+    }
+
+    #[async_test]
+    async fn test_get_all_updates_no_docs() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_get_all_updates_no_docs").await;
+        let status = index.get_all_updates().await.unwrap();
+
+        assert_eq!(status.len(), 0);
+    }
+
+    #[async_test]
+    async fn test_debug_bundle() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_debug_bundle").await;
+        let query = index.search().with_query("space").build();
+
+        let bundle = index.debug_bundle(&query, None).await.unwrap();
+        let json = serde_json::to_value(&bundle).unwrap();
+        for field in ["version", "indexUid", "settings", "stats", "query", "curl"] {
+            assert!(json.get(field).is_some(), "missing field {}", field);
+        }
+        assert!(json.get("documents").is_none());
+        assert_eq!(json["query"]["q"], "space");
+        assert!(!bundle.curl.contains("masterKey"));
+
+        let bundle = index.debug_bundle(&query, Some(10)).await.unwrap();
+        assert!(bundle.documents.is_some());
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Book {
+        id: usize,
+        title: String,
+        year: usize,
+    }
+
+    impl crate::document::Document for Book {
+        type UIDType = usize;
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    const INDEX_WITH_KEY: &str = r#"{"uid":"movies","name":"movies","primaryKey":"movie_id","createdAt":"2021-10-18T09:00:00Z","updatedAt":"2021-10-18T09:00:00Z"}"#;
+    const INDEX_WITHOUT_KEY: &str = r#"{"uid":"movies","name":"movies","primaryKey":null,"createdAt":"2021-10-18T09:00:00Z","updatedAt":"2021-10-18T09:00:00Z"}"#;
+    const INDEX_NOT_FOUND: &str = r#"{"message":"Index movies not found","errorCode":"index_not_found","errorType":"invalid_request_error","errorLink":""}"#;
+    const ENQUEUED: &str = r#"{"updateId":1}"#;
+
+    #[async_test]
+    async fn test_primary_key_check() {
+        let books = [Book { id: 1, title: String::from("Dune"), year: 1965 }];
+
+        // Mismatch: the documents are not sent
+        let (host, requests) = mock_server(vec![(200, INDEX_WITH_KEY), (200, INDEX_WITH_KEY)]);
+        let index = Client::new(host, "masterKey").with_primary_key_check(true).assume_index("movies");
+        let error = index.add_documents(&books, Some("id")).await.err().unwrap();
+        assert!(matches!(error, Error::PrimaryKeyMismatch { ref index_key, ref provided } if index_key == "movie_id" && provided == "id"));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies "));
+        let error = index.add_or_update(&books, Some("id")).await.err().unwrap();
+        assert!(matches!(error, Error::PrimaryKeyMismatch { .. }));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies "));
+        assert!(requests.try_recv().is_err());
+
+        // Match
+        let (host, requests) = mock_server(vec![(200, INDEX_WITH_KEY), (202, ENQUEUED)]);
+        let index = Client::new(host, "masterKey").with_primary_key_check(true).assume_index("movies");
+        index.add_or_update(&books, Some("movie_id")).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies "));
+        assert!(requests.recv().unwrap().starts_with("PUT /indexes/movies/documents?primaryKey=movie_id "));
+
+        // Unknown primary key, or unknown index
+        let (host, requests) = mock_server(vec![(200, INDEX_WITHOUT_KEY), (202, ENQUEUED), (404, INDEX_NOT_FOUND), (202, ENQUEUED)]);
+        let index = Client::new(host, "masterKey").with_primary_key_check(true).assume_index("movies");
+        index.add_documents(&books, Some("id")).await.unwrap();
+        index.add_documents(&books, Some("id")).await.unwrap();
+        let methods: Vec<String> = requests.iter().take(4).map(|request| request.split(' ').next().unwrap().to_string()).collect();
+        assert_eq!(methods, ["GET", "POST", "GET", "POST"]);
+
+        // No check without a primary key, or when the check is disabled
+        let (host, requests) = mock_server(vec![(202, ENQUEUED), (202, ENQUEUED)]);
+        let client = Client::new(host, "masterKey");
+        client.clone().with_primary_key_check(true).assume_index("movies").add_documents(&books, None).await.unwrap();
+        client.assume_index("movies").add_documents(&books, Some("id")).await.unwrap();
+        let methods: Vec<String> = requests.iter().take(2).map(|request| request.split(' ').next().unwrap().to_string()).collect();
+        assert_eq!(methods, ["POST", "POST"]);
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Picture {
+        id: usize,
+        #[serde(rename = "_vectors")]
+        vectors: Vectors,
+    }
+
+    impl crate::document::Document for Picture {
+        type UIDType = usize;
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    #[async_test]
+    async fn test_vector_dimension_check() {
+        let pictures = [
+            Picture { id: 1, vectors: Vectors::new().with("clip", vec![0.5, 1.0, 1.5]) },
+            Picture { id: 2, vectors: Vectors::new().with_embeddings("clip", vec![vec![0.5, 1.0]], false) },
+        ];
+
+        // Mismatch: the documents are not sent
+        let (host, requests) = mock_server(vec![(202, ENQUEUED)]);
+        let index = Client::new(host, "masterKey").with_vector_dimension_check(true).assume_index("pictures");
+        let error = index.add_documents(&pictures, Some("id")).await.err().unwrap();
+        assert!(matches!(error, Error::VectorDimensionMismatch { ref embedder, expected: 3, found: 2 } if embedder == "clip"));
+        let error = index.add_or_update(&pictures, Some("id")).await.err().unwrap();
+        assert!(matches!(error, Error::VectorDimensionMismatch { .. }));
+        assert!(requests.try_recv().is_err());
+
+        // Match
+        index.add_documents(&pictures[..1], Some("id")).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/pictures/documents?primaryKey=id "));
+        assert!(request.ends_with(r#"[{"id":1,"_vectors":{"clip":[0.5,1.0,1.5]}}]"#), "{}", request);
+
+        // No check when disabled
+        let (host, requests) = mock_server(vec![(202, ENQUEUED)]);
+        Client::new(host, "masterKey").assume_index("pictures").add_documents(&pictures, Some("id")).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("POST "));
+    }
+
+    #[async_test]
+    async fn test_snapshot() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_snapshot").await;
+        let books: Vec<Book> = (0..1500).map(|id| Book { id, title: format!("Book {}", id), year: 1900 + id % 100 }).collect();
+        index.add_documents_and_wait(&books, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["year"], None, None).await.unwrap();
+
+        let snapshot = index.snapshot::<Book>(SnapshotOptions::default()).await.unwrap();
+        assert_eq!(snapshot.index_uid, *index.uid);
+        assert_eq!(snapshot.documents.len(), 1500);
+        assert_eq!(snapshot.settings.filterable_attributes, Some(vec!["year".to_string()]));
+        assert!(snapshot.taken_at > 0);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let reloaded: LocalIndexSnapshot<Book> = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.documents, snapshot.documents);
+        assert_eq!(reloaded.version.pkg_version, snapshot.version.pkg_version);
+
+        let filtered = index.snapshot::<Book>(SnapshotOptions { fields: None, filter: Some("year = 1950") }).await.unwrap();
+        assert_eq!(filtered.documents.len(), 15);
+        assert!(filtered.documents.iter().all(|book| book.year == 1950));
+    }
+
+    #[async_test]
+    async fn test_for_each_uid_pages() {
+        let (host, requests) = mock_server(vec![(200, r#"[{"id":1},{"id":"a-2"}]"#), (200, r#"[{"id":3},{}]"#), (200, "[]")]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let mut uids = Vec::new();
+        let visited = index.for_each_uid("id", 2, |uid| uids.push(uid)).await.unwrap();
+        assert_eq!(visited, 4);
+        assert_eq!(uids, ["1", "a-2", "3"]);
+        let requests: Vec<String> = requests.iter().take(3).collect();
+        assert!(requests[0].starts_with("GET /indexes/movies/documents?offset=0&limit=2&attributesToRetrieve=id "));
+        assert!(requests[2].starts_with("GET /indexes/movies/documents?offset=4&limit=2&attributesToRetrieve=id "));
+
+        assert!(matches!(index.collect_uids("id", 0).await, Err(Error::InvalidRequest)));
+        assert!(matches!(UidSet::default().difference_delete(&index, 0).await, Err(Error::InvalidRequest)));
+    }
+
+    #[async_test]
+    async fn test_orphan_cleanup() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_orphan_cleanup").await;
+        let books: Vec<Book> = (0..10).map(|id| Book { id, title: format!("Book {}", id), year: 2000 }).collect();
+        index.add_documents_and_wait(&books, Some("id"), None, None).await.unwrap();
+
+        let mut orphans = index.collect_uids("id", 3).await.unwrap();
+        assert_eq!(orphans.len(), 10);
+        assert!(orphans.contains(9));
+
+        // The new dataset keeps the books 5 to 9, and adds the books 10 to 14
+        let reimported: Vec<Book> = (5..15).map(|id| Book { id, title: format!("Book {}", id), year: 2021 }).collect();
+        index.add_or_replace_and_wait(&reimported, None, None, None).await.unwrap();
+        orphans.remove_documents(&reimported);
+        assert_eq!(orphans.len(), 5);
+
+        for progress in orphans.difference_delete(&index, 2).await.unwrap() {
+            progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+        }
+        let remaining = index.collect_uids("id", 3).await.unwrap();
+        let mut remaining: Vec<&str> = remaining.iter().collect();
+        remaining.sort_by_key(|uid| uid.parse::<usize>().unwrap());
+        assert_eq!(remaining, ["5", "6", "7", "8", "9", "10", "11", "12", "13", "14"]);
+    }
+
+    #[test]
+    fn test_first_vector() {
+        assert_eq!(first_vector(&serde_json::json!([0.5, 1.0])), Some(vec![0.5, 1.0]));
+        assert_eq!(first_vector(&serde_json::json!([[0.5, 1.0], [2.0, 3.0]])), Some(vec![0.5, 1.0]));
+        assert_eq!(first_vector(&serde_json::json!({"embeddings": [[0.5, 1.0]], "regenerate": false})), Some(vec![0.5, 1.0]));
+        assert_eq!(first_vector(&serde_json::json!({"embeddings": [], "regenerate": true})), None);
+        assert_eq!(first_vector(&serde_json::json!(null)), None);
+        assert_eq!(first_vector(&serde_json::json!(["a"])), None);
+    }
+
+    #[test]
+    fn test_exclusion_filter() {
+        assert_eq!(exclusion_filter(None, "id", "42"), r#"NOT id = "42""#);
+        assert_eq!(exclusion_filter(Some("year > 2000 OR genre = drama"), "id", "42"), r#"(year > 2000 OR genre = drama) AND NOT id = "42""#);
+        assert_eq!(exclusion_filter(None, "slug", r#"a"b"#), r#"NOT slug = "a\"b""#);
+    }
+
+    #[async_test]
+    async fn test_recommend_for() {
+        const DOCUMENT: &str = r#"{"id":1,"title":"Interstellar","_vectors":{"manual":{"embeddings":[[0.1,0.2]],"regenerate":false}}}"#;
+        const RESULTS: &str = r#"{"hits":[{"id":2,"title":"Gravity","year":2013}],"offset":0,"limit":5,"nbHits":1,"exhaustiveNbHits":false,"processingTimeMs":0,"query":""}"#;
+        let (host, requests) = mock_server(vec![(200, DOCUMENT), (200, INDEX_WITH_KEY), (200, RESULTS), (200, DOCUMENT)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let options = RecommendOptions { embedder: "manual", limit: Some(5), filter: None };
+        let results = index.recommend_for::<Book>(1, options).await.unwrap();
+        assert_eq!(results.hits.len(), 1);
+        let requests: Vec<String> = requests.iter().take(3).collect();
+        assert!(requests[0].starts_with("GET /indexes/movies/documents/1?retrieveVectors=true "));
+        assert!(requests[1].starts_with("GET /indexes/movies "));
+        assert!(requests[2].starts_with("POST /indexes/movies/search "));
+
+        let options = RecommendOptions { embedder: "default", ..options };
+        let error = index.recommend_for::<Book>(1, options).await.unwrap_err();
+        assert!(matches!(error, Error::MissingVector { ref document_id, ref embedder } if document_id == "1" && embedder == "default"));
+    }
+
+    #[async_test]
+    async fn test_wait_until_idle_mock() {
+        const INDEXING: &str = r#"{"numberOfDocuments":10,"isIndexing":true,"fieldDistribution":{}}"#;
+        const IDLE: &str = r#"{"numberOfDocuments":20,"isIndexing":false,"fieldDistribution":{}}"#;
+
+        // A single idle poll between two indexing ones isn't enough
+        let (host, requests) = mock_server(vec![(200, INDEXING), (200, IDLE), (200, INDEXING), (200, IDLE), (200, IDLE)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let stats = index.wait_until_idle(Some(Duration::from_millis(1)), None).await.unwrap();
+        assert!(!stats.is_indexing);
+        assert_eq!(stats.number_of_documents, 20);
+        assert_eq!(requests.iter().take(5).filter(|request| request.starts_with("GET /indexes/movies/stats ")).count(), 5);
+
+        let (host, _requests) = mock_server(vec![(200, INDEXING); 100]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        let error = index.wait_until_idle(Some(Duration::from_millis(10)), Some(Duration::from_millis(50))).await.unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+    }
+
+    #[async_test]
+    async fn test_wait_until_idle() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_wait_until_idle").await;
+
+        let books: Vec<Book> = (0..5000).map(|id| Book { id, title: format!("Book {}", id), year: 1900 + id % 100 }).collect();
+        // The progress is dropped, as if another service had enqueued the import
+        index.add_documents(&books, Some("id")).await.unwrap();
+
+        let stats = index.wait_until_idle(None, Some(Duration::from_secs(60))).await.unwrap();
+        assert!(!stats.is_indexing);
+        assert_eq!(stats.number_of_documents, 5000);
+    }
+
+    #[async_test]
+    async fn test_delete_documents_routes() {
+        // Deleting by uid posts a bare array, on all versions
+        let (host, requests) = mock_server(vec![(202, ENQUEUED), (202, ENQUEUED), (404, ""), (405, "")]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+        index.delete_documents(&[1, 2]).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents/delete-batch "));
+        assert!(request.ends_with("[1,2]"), "{}", request);
+
+        // Deleting by filter posts the filter to another route
+        let progress = index.delete_documents_with("year < 1950").await.unwrap();
+        assert_eq!(progress.get_update_id(), 1);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/documents/delete "));
+        assert!(request.ends_with(r#"{"filter":"year < 1950"}"#), "{}", request);
+
+        // Older servers don't know the route
+        let error = index.delete_documents_with("year < 1950").await.err().unwrap();
+        assert!(matches!(error, Error::UnsupportedCapability { capability: Capability::DeleteDocumentsByFilter }));
+        assert!(error.to_string().contains("v1.2"));
+
+        // v0.22 takes `delete` for the uid of a document, which can't be posted
+        let error = index.delete_documents_with("year < 1950").await.err().unwrap();
+        assert!(matches!(error, Error::UnsupportedCapability { capability: Capability::DeleteDocumentsByFilter }));
+    }
+
+    #[async_test]
+    async fn test_documents_params() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_documents_params").await;
+        let books: Vec<Book> = (0..10).map(|id| Book { id, title: format!("Book {}", id), year: 1940 + id * 5 }).collect();
+        index.add_documents_and_wait(&books, Some("id"), None, None).await.unwrap();
+
+        let params = DocumentsQueryParams { offset: Some(2), limit: Some(3), ..DocumentsQueryParams::default() };
+        let with_params: Vec<Book> = index.get_documents_with_params(&params).await.unwrap();
+        let without_params: Vec<Book> = index.get_documents(Some(2), Some(3), None).await.unwrap();
+        assert_eq!(with_params.len(), 3);
+        assert_eq!(with_params, without_params);
+
+        index.set_filterable_attributes_and_wait(["year"], None, None).await.unwrap();
+        let params = DocumentDeletionParams { filter: "year < 1950".to_string() };
+        index.delete_documents_with_params(&params).await.unwrap().wait_until_processed(None, None).await.unwrap();
+        let remaining: Vec<Book> = index.get_documents(None, None, None).await.unwrap();
+        assert_eq!(remaining.len(), 8);
+    }
+
+    #[async_test]
+    async fn test_delete_documents_with() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_delete_documents_with").await;
+        let books: Vec<Book> = (0..10).map(|id| Book { id, title: format!("Book {}", id), year: 1940 + id * 5 }).collect();
+        index.add_documents_and_wait(&books, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["year"], None, None).await.unwrap();
+
+        index.delete_documents_with_and_wait("year < 1950", None, None).await.unwrap();
+        let remaining = index.get_documents::<Book>(None, None, None).await.unwrap();
+        assert_eq!(remaining.len(), 8);
+        assert!(remaining.iter().all(|book| book.year >= 1950));
+    }
+
+    #[async_test]
+    async fn test_get_one_update() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_get_one_update").await;
         let progress = index.delete_all_documents().await.unwrap();
 
         let update_id = progress.get_update_id();
         let status = index.get_update(update_id).await.unwrap();
 
-        client.delete_index(uid).await.unwrap();
 
         match status {
             UpdateStatus::Enqueued{content} => assert_eq!(content.update_id, update_id),
             UpdateStatus::Processing{content} => assert_eq!(content.update_id, update_id),
             UpdateStatus::Failed{content} => assert_eq!(content.update_id, update_id),
             UpdateStatus::Processed{content} => assert_eq!(content.update_id, update_id),
+            UpdateStatus::Unknown(status) => panic!("unexpected status: {}", status),
+            UpdateStatus::Other(raw) => panic!("unexpected update: {}", raw),
         }
     }
 }
@@ -16,6 +16,7 @@
 //!
 //! This crate is `async` but you can choose to use an async runtime like [tokio](https://crates.io/crates/tokio) or just [block on futures](https://docs.rs/futures/latest/futures/executor/fn.block_on.html).
 //! You can enable the `sync` feature to make most structs `Sync`. It may be a bit slower.
+//! The `documents-io` feature, enabled by default, provides the NDJSON and CSV imports and the compression of the requests.
 //!
 //! Using this crate is possible without [serde](https://crates.io/crates/serde), but a lot of features require serde.
 //!
@@ -36,7 +37,7 @@
 //! # 🚀 Getting Started
 //!
 //! ```
-//! use meilisearch_sdk::{document::*, client::*, search::*};
+//! use meilisearch_sdk::prelude::*;
 //! use serde::{Serialize, Deserialize};
 //! use futures::executor::block_on;
 //!
@@ -91,9 +92,11 @@
 //!
 //! However, making a program intended to run in a web browser requires a **very** different design than a CLI program. To see an example of a simple Rust web app using MeiliSearch, see the [our demo](./examples/web_app).
 //!
-//! WARNING: `meilisearch-sdk` will panic if no Window is available (ex: Web extension).
+//! In JavaScript contexts without a Window, like web extensions, the SDK falls back to the fetch function of web workers, and returns an error if there is none.
 
 #![warn(clippy::all)]
+// Malformed responses and transient failures must be returned as errors, never abort the caller
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used, clippy::panic))]
 #![allow(clippy::needless_doctest_main)]
 
 /// Module containing the Client struct.
@@ -105,15 +108,125 @@ pub mod dumps;
 pub mod errors;
 /// Module containing the Index struct.
 pub mod indexes;
+/// Module re-exporting the most commonly used items.
+pub mod prelude;
 /// Module containing objects useful for tracking the progress of async operations.
 pub mod progress;
+mod cache;
 mod request;
 /// Module related to search queries and results.
 pub mod search;
 /// Module containing settings
 pub mod settings;
+/// Module containing the checks of documents against the settings of an index, before sending them.
+pub mod validation;
 
 #[cfg(feature = "sync")]
 pub(crate) type Rc<T> = std::sync::Arc<T>;
 #[cfg(not(feature = "sync"))]
 pub(crate) type Rc<T> = std::rc::Rc<T>;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{client::Client, indexes::Index, search::{Filter, Query}, settings::Settings};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Environment variable keeping the indexes of failed tests, for inspection.
+    const KEEP_FAILED_INDEXES: &str = "MEILISEARCH_KEEP_FAILED_INDEXES";
+
+    /// A unique index uid starting with `name`, so that tests can run in parallel against the same server.
+    pub(crate) fn unique_uid(name: &str) -> String {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or(0);
+        format!("{}_{:x}_{:x}", name, std::process::id() ^ nanos, COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// An index created for a live test, with a [unique uid](unique_uid).
+    ///
+    /// The index is deleted when the guard is dropped, even if the test panics,
+    /// unless the test failed and `MEILISEARCH_KEEP_FAILED_INDEXES` is set.
+    pub(crate) struct TestIndex(Index);
+
+    impl TestIndex {
+        pub(crate) async fn new(client: &Client, name: &str) -> TestIndex {
+            TestIndex::with_uid(client, unique_uid(name)).await
+        }
+
+        /// Create the index with an exact uid, which should be built from a [unique uid](unique_uid).
+        pub(crate) async fn with_uid(client: &Client, uid: String) -> TestIndex {
+            TestIndex(client.create_index(uid, None).await.unwrap())
+        }
+    }
+
+    impl std::ops::Deref for TestIndex {
+        type Target = Index;
+
+        fn deref(&self) -> &Index {
+            &self.0
+        }
+    }
+
+    impl Drop for TestIndex {
+        fn drop(&mut self) {
+            let uid = self.0.uid.to_string();
+            if std::thread::panicking() && std::env::var_os(KEEP_FAILED_INDEXES).is_some() {
+                eprintln!("Keeping the index `{}` of the failed test", uid);
+                return;
+            }
+
+            // The test runs inside `block_on`, which can't be nested on the same thread
+            let host = self.0.host.to_string();
+            let api_key = self.0.api_key.to_string();
+            let _ = std::thread::spawn(move || {
+                futures::executor::block_on(Client::new(host, api_key).delete_index_if_exists(&uid))
+            })
+            .join();
+        }
+    }
+
+    #[test]
+    fn test_unique_uid() {
+        let first = unique_uid("test_movies");
+        let second = unique_uid("test_movies");
+        assert!(first.starts_with("test_movies_"));
+        assert_ne!(first, second);
+        assert!(first.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+    }
+
+    fn assert_clone<T: Clone>() {}
+    #[cfg(feature = "sync")]
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_builders_are_clone() {
+        assert_clone::<Client>();
+        assert_clone::<Index>();
+        assert_clone::<Query<'static>>();
+        assert_clone::<Settings>();
+    }
+
+    // Builders can only be shared with spawned tasks when the `sync` feature is enabled
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_builders_are_send_and_sync() {
+        assert_send_sync::<Client>();
+        assert_send_sync::<Index>();
+        assert_send_sync::<Query<'static>>();
+        assert_send_sync::<Settings>();
+    }
+
+    #[test]
+    fn test_query_accepts_owned_strings() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("does not matter");
+        let query = {
+            let text = String::from("space");
+            index.search().with_query(text).with_filter(format!("year > {}", 2000)).build()
+        };
+        assert_eq!(query.query.as_deref(), Some("space"));
+        assert_eq!(query.filter.as_ref().and_then(Filter::as_expression), Some("year > 2000"));
+    }
+}
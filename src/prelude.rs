@@ -0,0 +1,58 @@
+//! The most commonly used items of the SDK, to be imported at once:
+//!
+//! ```no_run
+//! use meilisearch_sdk::prelude::*;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! struct Movie {
+//!     id: usize,
+//!     title: String,
+//! }
+//!
+//! impl Document for Movie {
+//!     type UIDType = usize;
+//!
+//!     fn get_uid(&self) -> &Self::UIDType {
+//!         &self.id
+//!     }
+//! }
+//!
+//! async fn import(client: &Client, movies: &[Movie]) -> Result<SearchResults<Movie>, Error> {
+//!     let index: Index = client.get_or_create("movies").await?;
+//!     let settings = Settings::new().with_searchable_attributes(["title"]);
+//!     index.set_settings(&settings).await?;
+//!
+//!     let progress: Progress = index.add_documents(movies, Some("id")).await?;
+//!     if let Some(Ok(UpdateStatus::Failed { content })) = progress.wait_for_pending_update(None, None).await {
+//!         println!("Import failed: {:?}", content.error);
+//!     }
+//!
+//!     let query: Query = index.search().with_query("interstellar").build();
+//!     let results = index.execute_query::<Movie>(&query).await?;
+//!     for hit in &results.hits {
+//!         let hit: &SearchResult<Movie> = hit;
+//!         println!("{}", hit.result.title);
+//!     }
+//!     Ok(results)
+//! }
+//!
+//! async fn drop_movies(client: &Client) -> Result<(), Error> {
+//!     match client.delete_index("movies").await {
+//!         Err(Error::MeiliSearchError { error_code: ErrorCode::IndexNotFound, .. }) => Ok(()),
+//!         result => result,
+//!     }
+//! }
+//! ```
+//!
+//! Only stable items are re-exported here, so that moving an item between modules doesn't break the programs importing the prelude.
+
+pub use crate::{
+    client::Client,
+    document::Document,
+    errors::{Error, ErrorCode},
+    indexes::Index,
+    progress::{Progress, UpdateStatus},
+    search::{Query, SearchResult, SearchResults},
+    settings::Settings,
+};
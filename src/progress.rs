@@ -1,8 +1,10 @@
 #![allow(missing_docs)]
 
-use crate::{errors::Error, indexes::Index, request::*, Rc};
-use serde::Deserialize;
-use std::{collections::{BTreeMap, BTreeSet}, time::Duration};
+use crate::{cache::ResponseCache, errors::{Error, ErrorType}, indexes::Index, request::*, Rc};
+use log::warn;
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, sync::Mutex, time::Duration};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,11 +14,18 @@ pub(crate) struct ProgressJson {
 
 impl ProgressJson {
     pub(crate) fn into_progress(self, index: &Index) -> Progress {
+        // Every task enqueued on the index may change its settings or its stats, until it is processed
+        if let Some(cache) = &index.cache {
+            cache.start_update(&index.host, &index.uid, self.update_id as u64);
+        }
         Progress {
             id: self.update_id,
             index_uid: Rc::clone(&index.uid),
             host: Rc::clone(&index.host),
-            api_key: Rc::clone(&index.api_key)
+            api_key: Rc::clone(&index.api_key),
+            cache: index.cache.clone(),
+            waits: Rc::clone(&index.waits),
+            max_response_bytes: index.max_response_bytes,
         }
     }
 }
@@ -26,19 +35,22 @@ pub struct Progress {
     id: usize,
     index_uid: Rc<String>,
     host: Rc<String>,
-    api_key: Rc<String>
+    api_key: Rc<String>,
+    cache: Option<Rc<ResponseCache>>,
+    waits: Rc<PendingWaits>,
+    max_response_bytes: Option<usize>,
 }
 
 impl<'a> Progress {
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// let mut movies_index = client.get_or_create("movies").await.unwrap();
+    /// let mut movies_index = client.get_or_create("movies_get_update_id").await.unwrap();
     /// let progress = movies_index.delete_all_documents().await.unwrap();
     /// let update_id = progress.get_update_id();
-    /// # client.delete_index("movies").await.unwrap();
+    /// # client.delete_index("movies_get_update_id").await.unwrap();
     /// # });
     /// ```
     pub fn get_update_id(&self) -> u64 {
@@ -48,17 +60,17 @@ impl<'a> Progress {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
-    /// let mut movies_index = client.get_or_create("movies").await.unwrap();
+    /// let mut movies_index = client.get_or_create("movies_get_status").await.unwrap();
     /// let progress = movies_index.delete_all_documents().await.unwrap();
     /// let status = progress.get_status().await.unwrap();
-    /// # client.delete_index("movies").await.unwrap();
+    /// # client.delete_index("movies_get_status").await.unwrap();
     /// # });
     /// ```
     pub async fn get_status(&self) -> Result<UpdateStatus, Error> {
-        request::<(), UpdateStatus>(
+        let status = request::<(), UpdateStatus>(
             &format!(
                 "{}/indexes/{}/updates/{}",
                 self.host, self.index_uid, self.id
@@ -66,8 +78,29 @@ impl<'a> Progress {
             &self.api_key,
             Method::Get,
             200,
+            self.max_response_bytes,
         )
-        .await
+        .await?;
+        if let Some(cache) = &self.cache {
+            if !is_pending(&status) {
+                cache.finish_update(&self.host, &self.index_uid, self.id as u64);
+            }
+        }
+        Ok(status)
+    }
+
+    /// Get the status of the update, unless it was already polled by another waiter since the last `turn`.
+    /// Returns `None` when the caller should just sleep until its next turn.
+    async fn poll_shared(&self, turn: &mut WaitTurn<'_>) -> Option<Result<UpdateStatus, Error>> {
+        match turn.next() {
+            Turn::Done(status) => Some(Ok(*status)),
+            Turn::Wait => None,
+            Turn::Poll => {
+                let result = self.get_status().await;
+                turn.record(result.as_ref().ok());
+                Some(result)
+            }
+        }
     }
 
     /// Wait until MeiliSearch processes an update, and get its status.
@@ -77,6 +110,8 @@ impl<'a> Progress {
     ///
     /// If the waited time exceeds `timeout` then `None` will be returned.
     ///
+    /// Concurrent waits on the same update through clones of a [Client](../client/struct.Client.html) share a single polling loop.
+    ///
     /// # Example
     ///
     /// ```
@@ -122,29 +157,283 @@ impl<'a> Progress {
         let timeout = timeout.unwrap_or_else(|| Duration::from_millis(5000));
 
         let mut elapsed_time = Duration::new(0, 0);
-        let mut status_result: Result<UpdateStatus, Error>;
+        let mut turn = self.waits.join(&self.index_uid, self.id);
 
         while timeout > elapsed_time {
-            status_result = self.get_status().await;
-
-            match status_result {
-                Ok (status) => {
-                    match status {
-                        UpdateStatus::Failed { .. } | UpdateStatus::Processed { .. } => {
-                            return Some(self.get_status().await);
-                        },
-                        UpdateStatus::Enqueued { .. } | UpdateStatus::Processing { .. } => {
-                            elapsed_time += interval;
-                            async_sleep(interval).await;
-                        },
-                    }
+            match self.poll_shared(&mut turn).await {
+                Some(Ok(status)) if !is_pending(&status) => return Some(Ok(status)),
+                Some(Err(error)) => return Some(Err(error)),
+                Some(Ok(_)) | None => {
+                    elapsed_time = elapsed_time.saturating_add(interval);
+                    async_sleep(interval).await;
                 },
-                Err (error) => return Some(Err(error)),
             };
         }
 
         None
     }
+
+    /// Wait for the update with the default [WaitPolicy](struct.WaitPolicy.html), overriding its `interval` and `timeout` when provided.
+    pub(crate) async fn wait_until_processed(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        let default = WaitPolicy::default();
+        self.wait_with_policy(&WaitPolicy {
+            interval: interval.unwrap_or(default.interval),
+            timeout: timeout.unwrap_or(default.timeout),
+            ..default
+        }).await
+    }
+
+    /// Wait until MeiliSearch processes an update, tolerating transient failures while polling its status.
+    ///
+    /// Unlike [wait_for_pending_update](#method.wait_for_pending_update), a failure to get the status (during a server restart for example)
+    /// is retried according to the `policy` before being returned. The errors tell apart what happened:
+    /// - the update failed: [Error::MeiliSearchError](../errors/enum.Error.html#variant.MeiliSearchError), with the error of the update,
+    /// - the status couldn't be checked: [Error::UpdateStatusUnavailable](../errors/enum.Error.html#variant.UpdateStatusUnavailable),
+    /// - the update wasn't processed in time: [Error::Timeout](../errors/enum.Error.html#variant.Timeout).
+    ///
+    /// As with [wait_for_pending_update](#method.wait_for_pending_update), concurrent waits on the same update share their polls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, progress::*};
+    /// # use std::time::Duration;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_wait_with_policy").await.unwrap();
+    ///
+    /// let progress = movies.delete_all_documents().await.unwrap();
+    /// let policy = WaitPolicy { timeout: Duration::from_secs(60), max_poll_retries: 10, ..WaitPolicy::default() };
+    /// progress.wait_with_policy(&policy).await.unwrap();
+    /// # client.delete_index("movies_wait_with_policy").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn wait_with_policy(&self, policy: &WaitPolicy) -> Result<ProcessedUpdateResult, Error> {
+        let mut elapsed_time = Duration::new(0, 0);
+        let mut failed_attempts = 0;
+        let mut turn = self.waits.join(&self.index_uid, self.id);
+
+        while policy.timeout > elapsed_time {
+            let result = match self.poll_shared(&mut turn).await {
+                Some(result) => result,
+                None => {
+                    elapsed_time = elapsed_time.saturating_add(policy.interval);
+                    async_sleep(policy.interval).await;
+                    continue;
+                }
+            };
+            let wait = match result {
+                Ok(status) if is_pending(&status) => {
+                    failed_attempts = 0;
+                    policy.interval
+                }
+                Ok(status) => return final_status(status),
+                Err(error) if is_transient(&error) && failed_attempts < policy.max_poll_retries => {
+                    failed_attempts += 1;
+                    let backoff = policy.backoff(failed_attempts);
+                    warn!(
+                        "Failed to get the status of the update {} (attempt {}/{}), retrying in {:?}: {}",
+                        self.id, failed_attempts, policy.max_poll_retries + 1, backoff, error
+                    );
+                    backoff
+                }
+                Err(error) => {
+                    return Err(Error::UpdateStatusUnavailable {
+                        update_id: self.id as u64,
+                        attempts: failed_attempts + 1,
+                        last_error: Box::new(error),
+                    })
+                }
+            };
+            elapsed_time = elapsed_time.saturating_add(wait);
+            async_sleep(wait).await;
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+/// Turn the final status of an update into a result.
+fn final_status(status: UpdateStatus) -> Result<ProcessedUpdateResult, Error> {
+    match status {
+        UpdateStatus::Processed { content } => Ok(content),
+        UpdateStatus::Failed { content } => Err(Error::from(&json!({
+            "message": content.error.unwrap_or_else(|| String::from("The update failed")),
+            "errorType": content.error_type,
+            "errorCode": content.error_code,
+            "errorLink": content.error_link,
+        }))),
+        status => Err(Error::from(&json!({
+            "message": format!("The update ended with the status `{}`, unknown to this version of the SDK", status.as_str()),
+        }))),
+    }
+}
+
+/// Whether the update is still waiting to be processed.
+pub(crate) fn is_pending(status: &UpdateStatus) -> bool {
+    matches!(status, UpdateStatus::Enqueued { .. } | UpdateStatus::Processing { .. })
+}
+
+/// The updates being waited for through a [Client](../client/struct.Client.html) and its clones.
+///
+/// Waiters on the same update take turns: at most one of them polls the status at a time, the others skip their
+/// turn if the status was polled since they last checked, and all of them get the final status once it is known.
+/// An entry only lives as long as someone waits for its update.
+#[derive(Debug, Default)]
+pub(crate) struct PendingWaits {
+    waits: Mutex<HashMap<(String, usize), SharedWait>>,
+}
+
+#[derive(Debug, Default)]
+struct SharedWait {
+    waiters: usize,
+    polling: bool,
+    polls: u64,
+    final_status: Option<UpdateStatus>,
+}
+
+enum Turn {
+    Done(Box<UpdateStatus>),
+    Poll,
+    Wait,
+}
+
+impl PendingWaits {
+    fn waits(&self) -> std::sync::MutexGuard<'_, HashMap<(String, usize), SharedWait>> {
+        // A panic while holding the lock cannot leave the map in an invalid state
+        self.waits.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn join(&self, index_uid: &str, update_id: usize) -> WaitTurn<'_> {
+        let key = (index_uid.to_string(), update_id);
+        let mut waits = self.waits();
+        let wait = waits.entry(key.clone()).or_default();
+        wait.waiters += 1;
+        WaitTurn {
+            seen: wait.polls,
+            polling: false,
+            key,
+            pending: self,
+        }
+    }
+}
+
+/// The place of a waiter among the waiters of an update. Leaves the wait when dropped.
+struct WaitTurn<'a> {
+    pending: &'a PendingWaits,
+    key: (String, usize),
+    seen: u64,
+    polling: bool,
+}
+
+impl WaitTurn<'_> {
+    fn next(&mut self) -> Turn {
+        let mut waits = self.pending.waits();
+        let wait = waits.entry(self.key.clone()).or_default();
+        if let Some(status) = &wait.final_status {
+            Turn::Done(Box::new(status.clone()))
+        } else if wait.polling || wait.polls > self.seen {
+            self.seen = wait.polls;
+            Turn::Wait
+        } else {
+            wait.polling = true;
+            self.polling = true;
+            Turn::Poll
+        }
+    }
+
+    /// Share the status polled during this turn. Failed polls are not shared: each waiter handles its errors.
+    fn record(&mut self, status: Option<&UpdateStatus>) {
+        let mut waits = self.pending.waits();
+        let wait = waits.entry(self.key.clone()).or_default();
+        wait.polling = false;
+        self.polling = false;
+        match status {
+            Some(status) if !is_pending(status) => wait.final_status = Some(status.clone()),
+            Some(_) => wait.polls += 1,
+            None => (),
+        }
+        self.seen = wait.polls;
+    }
+}
+
+impl Drop for WaitTurn<'_> {
+    fn drop(&mut self) {
+        let mut waits = self.pending.waits();
+        if let Some(wait) = waits.get_mut(&self.key) {
+            // A waiter dropped while polling must let the others poll
+            if self.polling {
+                wait.polling = false;
+            }
+            wait.waiters = wait.waiters.saturating_sub(1);
+            if wait.waiters == 0 {
+                waits.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Whether polling again may succeed: the server is unreachable, the connection broke, or the server failed internally.
+pub(crate) fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::UnreachableServer | Error::HttpError(_) | Error::MeiliSearchError { error_type: ErrorType::Internal, .. }
+    )
+}
+
+/// How to wait for an update with [Progress::wait_with_policy](struct.Progress.html#method.wait_with_policy).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaitPolicy {
+    /// The frequency at which the server is polled. Default: 50ms
+    pub interval: Duration,
+    /// The maximum time to wait for the update, retries included. Default: 5000ms
+    pub timeout: Duration,
+    /// The number of consecutive failures to get the status that are retried before giving up. Default: 3
+    pub max_poll_retries: usize,
+    /// The delay before the first retry, doubled for each consecutive failure and randomized by ±25%. Default: 100ms
+    pub retry_backoff: Duration,
+}
+
+impl Default for WaitPolicy {
+    fn default() -> WaitPolicy {
+        WaitPolicy {
+            interval: Duration::from_millis(50),
+            timeout: Duration::from_millis(5000),
+            max_poll_retries: 3,
+            retry_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+impl WaitPolicy {
+    /// The delay before retrying after `failed_attempts` consecutive failures.
+    pub(crate) fn backoff(&self, failed_attempts: usize) -> Duration {
+        let exponent = failed_attempts.saturating_sub(1).min(16) as u32;
+        let base = self.retry_backoff.saturating_mul(2u32.pow(exponent));
+        // Spread the retries of concurrent clients so that they don't all hit the restarting server at once.
+        // `mul_f64` panics on overflow, so the jitter (±25%) is only applied to a quarter of the base.
+        let quarter = base / 4;
+        (base - quarter).saturating_add(quarter.mul_f64(2.0 * random_ratio()))
+    }
+}
+
+/// A number between 0 and 1, random enough for jitter.
+#[cfg(not(target_arch = "wasm32"))]
+fn random_ratio() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos() as f64 / 1_000_000_000.0)
+        .unwrap_or(0.5)
+}
+
+/// A number between 0 and 1, random enough for jitter.
+#[cfg(target_arch = "wasm32")]
+fn random_ratio() -> f64 {
+    js_sys::Math::random()
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -159,18 +448,31 @@ pub(crate) async fn async_sleep(interval: Duration) {
 
 #[cfg(target_arch = "wasm32")]
 pub(crate) async fn async_sleep(interval: Duration) {
-    use wasm_bindgen_futures::JsFuture;
+    let _ = wasm_bindgen_futures::JsFuture::from(timer_promise(interval)).await;
+}
+
+/// A promise resolved with `undefined` after `interval`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn timer_promise(interval: Duration) -> js_sys::Promise {
+    use wasm_bindgen::JsCast;
+    use web_sys::{Window, WorkerGlobalScope};
     use std::convert::TryInto;
 
-    JsFuture::from(js_sys::Promise::new(&mut |yes, _| {
-        web_sys::window()
-            .unwrap()
-            .set_timeout_with_callback_and_timeout_and_arguments_0(
-                &yes,
-                interval.as_millis().try_into().unwrap(),
-            )
-            .unwrap();
-    })).await.unwrap();
+    let timeout: i32 = interval.as_millis().try_into().unwrap_or(i32::MAX);
+    // If no timer can be set, the promise is resolved immediately instead of panicking
+    js_sys::Promise::new(&mut |yes, _| {
+        let global = js_sys::global();
+        let scheduled = if let Some(window) = global.dyn_ref::<Window>() {
+            window.set_timeout_with_callback_and_timeout_and_arguments_0(&yes, timeout).is_ok()
+        } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+            worker.set_timeout_with_callback_and_timeout_and_arguments_0(&yes, timeout).is_ok()
+        } else {
+            false
+        };
+        if !scheduled {
+            let _ = yes.call0(&wasm_bindgen::JsValue::NULL);
+        }
+    })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -194,9 +496,13 @@ pub struct SettingsUpdate {
     pub sortable_attributes: Option<Vec<String>>,
 }
 
+/// The type of an update.
+///
+/// Types added by newer versions of MeiliSearch are deserialized as [Unknown](#variant.Unknown) instead of failing.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(tag = "name")]
+#[serde(tag = "name", remote = "Self")]
+#[non_exhaustive]
 pub enum UpdateType {
     ClearAll,
     Customs,
@@ -215,6 +521,37 @@ pub enum UpdateType {
     Settings {
         settings: SettingsUpdate,
     },
+    /// A type unknown to this version of the SDK. Contains the raw name of the type.
+    #[serde(skip)]
+    Unknown(String),
+}
+
+impl UpdateType {
+    const KNOWN: [&'static str; 6] = ["ClearAll", "Customs", "DocumentsAddition", "DocumentsPartial", "DocumentsDeletion", "Settings"];
+
+    /// The name of the type, as returned by MeiliSearch.
+    pub fn as_str(&self) -> &str {
+        match self {
+            UpdateType::ClearAll => "ClearAll",
+            UpdateType::Customs => "Customs",
+            UpdateType::DocumentsAddition { .. } => "DocumentsAddition",
+            UpdateType::DocumentsPartial { .. } => "DocumentsPartial",
+            UpdateType::DocumentsDeletion { .. } => "DocumentsDeletion",
+            UpdateType::Settings { .. } => "Settings",
+            UpdateType::Unknown(name) => name,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UpdateType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<UpdateType, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value.get("name").and_then(|name| name.as_str()) {
+            Some(name) if !UpdateType::KNOWN.contains(&name) => Ok(UpdateType::Unknown(name.to_string())),
+            // Calls the implementation generated by serde for known types
+            _ => UpdateType::deserialize(value).map_err(D::Error::custom),
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -241,8 +578,12 @@ pub struct EnqueuedUpdateResult {
     pub enqueued_at: String, // TODO deserialize to datetime
 }
 
+/// The status of an update.
+///
+/// Statuses added by newer versions of MeiliSearch are deserialized as [Unknown](#variant.Unknown) instead of failing.
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "status")]
+#[serde(rename_all = "camelCase", tag = "status", remote = "Self")]
+#[non_exhaustive]
 pub enum UpdateStatus {
     Enqueued {
         #[serde(flatten)]
@@ -260,11 +601,58 @@ pub enum UpdateStatus {
         #[serde(flatten)]
         content: ProcessedUpdateResult,
     },
+    /// A status unknown to this version of the SDK. Contains the raw status.
+    /// It is considered final by [wait_for_pending_update](struct.Progress.html#method.wait_for_pending_update).
+    #[serde(skip)]
+    Unknown(String),
+    /// An update of a list that could not be parsed. Contains the raw update.
+    ///
+    /// Only returned by [get_all_updates](../indexes/struct.Index.html#method.get_all_updates), so that one malformed update doesn't fail the whole list.
+    #[serde(skip)]
+    Other(Value),
+}
+
+impl UpdateStatus {
+    const KNOWN: [&'static str; 4] = ["enqueued", "processing", "failed", "processed"];
+
+    /// The status, as returned by MeiliSearch.
+    pub fn as_str(&self) -> &str {
+        match self {
+            UpdateStatus::Enqueued { .. } => "enqueued",
+            UpdateStatus::Processing { .. } => "processing",
+            UpdateStatus::Failed { .. } => "failed",
+            UpdateStatus::Processed { .. } => "processed",
+            UpdateStatus::Unknown(status) => status,
+            UpdateStatus::Other(value) => value.get("status").and_then(Value::as_str).unwrap_or_default(),
+        }
+    }
+
+    /// Parses an update of a list, keeping it as [Other](#variant.Other) if it is malformed.
+    pub(crate) fn from_list_entry(value: Value) -> UpdateStatus {
+        match serde_json::from_value(value.clone()) {
+            Ok(status) => status,
+            Err(error) => {
+                warn!("Could not parse an update, keeping it raw: {}", error);
+                UpdateStatus::Other(value)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UpdateStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<UpdateStatus, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value.get("status").and_then(|status| status.as_str()) {
+            Some(status) if !UpdateStatus::KNOWN.contains(&status) => Ok(UpdateStatus::Unknown(status.to_string())),
+            // Calls the implementation generated by serde for known statuses
+            _ => UpdateStatus::deserialize(value).map_err(D::Error::custom),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{client::*, document, progress::*};
+    use crate::{client::*, document, errors::Error, progress::*, request::tests::{mock_server, mock_server_on}, tests::TestIndex, Rc};
     use serde::{Serialize, Deserialize};
     use futures_await_test::async_test;
     use std::time;
@@ -287,7 +675,7 @@ mod test {
     #[async_test]
     async fn test_wait_for_pending_updates_with_args() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let movies = client.get_or_create("movies_wait_for_pending_args").await.unwrap();
+        let movies = TestIndex::new(&client, "movies_wait_for_pending_args").await;
         let progress = movies.add_documents(&[
             Document {
                 id: 0,
@@ -304,14 +692,13 @@ mod test {
             Some(Duration::from_millis(1)), Some(Duration::from_millis(6000))
         ).await.unwrap();
 
-        client.delete_index("movies_wait_for_pending_args").await.unwrap();
         assert!(matches!(status.unwrap(), UpdateStatus::Processed { .. }));
     }
 
     #[async_test]
     async fn test_wait_for_pending_updates_time_out() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let movies = client.get_or_create("movies_wait_for_pending_timeout").await.unwrap();
+        let movies = TestIndex::new(&client, "movies_wait_for_pending_timeout").await;
         let progress = movies.add_documents(&[
             Document {
                 id: 0,
@@ -335,13 +722,297 @@ mod test {
          */
         if let Some(Err(err)) = &status {
             println!("{:?}", err);
-            client.delete_index("movies_wait_for_pending_timeout").await.unwrap();
         };
 
-        client.delete_index("movies_wait_for_pending_timeout").await.unwrap();
         assert_eq!(status.is_none(), true);
     }
 
+    /// Run an `_and_wait` method on a fresh index and check that the update was processed.
+    macro_rules! test_and_wait {
+        ($($name:ident: |$index:ident| $call:expr;)*) => {
+            $(
+                #[async_test]
+                async fn $name() {
+                    let client = Client::new("http://localhost:7700", "masterKey");
+                    let $index = TestIndex::new(&client, stringify!($name)).await;
+                    $index.add_documents_and_wait(&[
+                        Document { id: 0, kind: "title".into(), value: "The Social Network".to_string() },
+                        Document { id: 1, kind: "title".into(), value: "Harry Potter and the Sorcerer's Stone".to_string() },
+                    ], Some("id"), None, None).await.unwrap();
+
+                    let result = $call.await;
+                    assert!(result.unwrap().error.is_none());
+                }
+            )*
+        };
+    }
+
+    test_and_wait! {
+        test_add_or_update_and_wait: |index| index.add_or_update_and_wait(&[Document { id: 2, kind: "title".into(), value: "Interstellar".to_string() }], Some("id"), None, None);
+        test_delete_document_and_wait: |index| index.delete_document_and_wait(0, None, None);
+        test_delete_documents_and_wait: |index| index.delete_documents_and_wait(&[0, 1], None, None);
+        test_delete_all_documents_and_wait: |index| index.delete_all_documents_and_wait(None, None);
+        test_set_filterable_attributes_and_wait: |index| index.set_filterable_attributes_and_wait(["kind"], None, None);
+        test_set_settings_and_wait: |index| index.set_settings_and_wait(&crate::settings::Settings::new().with_stop_words(["the"]), None, None);
+        test_reset_ranking_rules_and_wait: |index| index.reset_ranking_rules_and_wait(None, None);
+        test_reset_settings_and_wait: |index| index.reset_settings_and_wait(None, None);
+    }
+
+    #[async_test]
+    async fn test_and_wait_failed_update() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_and_wait_failed_update").await;
+        let result = index
+            .add_documents_and_wait(&[Document { id: 0, kind: "title".into(), value: "The Social Network".to_string() }], Some("unknown"), None, None)
+            .await;
+
+        assert!(matches!(result, Err(Error::MeiliSearchError { .. })));
+    }
+
+    #[async_test]
+    async fn test_and_wait_time_out() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_and_wait_time_out").await;
+        let result = index.delete_all_documents_and_wait(Some(Duration::from_millis(1)), Some(Duration::from_nanos(1))).await;
+
+        assert!(matches!(result, Err(Error::Timeout)));
+    }
+
+    // An update returned by MeiliSearch v0.20
+    const PROCESSED_V0_20: &str = r#"{"status":"processed","updateId":1,"type":{"name":"DocumentsAddition","number":2},"duration":0.07,"enqueuedAt":"2021-06-01T10:00:00Z","processedAt":"2021-06-01T10:00:01Z"}"#;
+    // An update returned by MeiliSearch v0.22, with the error fields
+    const FAILED_V0_22: &str = r#"{"status":"failed","updateId":2,"type":{"name":"Settings","settings":{"stopWords":["the"]}},"error":"Invalid ranking rule","errorType":"invalid_request_error","errorCode":"invalid_request","errorLink":"https://docs.meilisearch.com/errors#invalid_request","duration":0.01,"enqueuedAt":"2021-09-01T10:00:00Z","processedAt":"2021-09-01T10:00:01Z"}"#;
+    // A made up update from a future version of MeiliSearch
+    const FUTURE_TYPE: &str = r#"{"status":"enqueued","updateId":3,"type":{"name":"IndexSwap","indexes":["a","b"]},"enqueuedAt":"2030-01-01T10:00:00Z"}"#;
+    const FUTURE_STATUS: &str = r#"{"status":"canceled","updateId":4,"type":{"name":"ClearAll"},"canceledBy":1}"#;
+
+    #[test]
+    fn test_update_status_fixtures() {
+        let status: UpdateStatus = serde_json::from_str(PROCESSED_V0_20).unwrap();
+        assert_eq!(status.as_str(), "processed");
+        assert!(matches!(status, UpdateStatus::Processed { content } if matches!(content.update_type, UpdateType::DocumentsAddition { number: Some(2) })));
+
+        let status: UpdateStatus = serde_json::from_str(FAILED_V0_22).unwrap();
+        assert_eq!(status.as_str(), "failed");
+        assert!(matches!(status, UpdateStatus::Failed { content } if content.update_type.as_str() == "Settings"));
+    }
+
+    #[test]
+    fn test_unknown_update_type_and_status() {
+        let status: UpdateStatus = serde_json::from_str(FUTURE_TYPE).unwrap();
+        match status {
+            UpdateStatus::Enqueued { content } => {
+                assert!(matches!(&content.update_type, UpdateType::Unknown(name) if name == "IndexSwap"));
+                assert_eq!(content.update_type.as_str(), "IndexSwap");
+            }
+            status => panic!("unexpected status: {}", status.as_str()),
+        }
+
+        let status: UpdateStatus = serde_json::from_str(FUTURE_STATUS).unwrap();
+        assert!(matches!(&status, UpdateStatus::Unknown(raw) if raw == "canceled"));
+        assert_eq!(status.as_str(), "canceled");
+
+        // Known names with an invalid content are still errors
+        assert!(serde_json::from_str::<UpdateStatus>(r#"{"status":"processed"}"#).is_err());
+    }
+
+    // A settings update returned by MeiliSearch v0.20, whose details are in the internal format of that version
+    const SETTINGS_V0_20: &str = r#"{"status":"processed","updateId":2,"type":{"name":"Settings","settings":{"rankingRules":{"Update":["typo","words","proximity","attribute","wordsPosition","exactness"]},"distinctAttribute":"Nothing","primaryKey":"Nothing","searchableAttributes":"Nothing","displayedAttributes":"Nothing","stopWords":"Nothing","synonyms":"Nothing","attributesForFaceting":"Nothing"}},"duration":0.02,"enqueuedAt":"2021-06-01T10:01:00Z","processedAt":"2021-06-01T10:01:01Z"}"#;
+
+    #[test]
+    fn test_malformed_list_entry() {
+        assert!(serde_json::from_str::<UpdateStatus>(SETTINGS_V0_20).is_err());
+
+        let page: Vec<Value> = serde_json::from_str(&format!("[{},{},{}]", PROCESSED_V0_20, SETTINGS_V0_20, FAILED_V0_22)).unwrap();
+        let statuses: Vec<UpdateStatus> = page.into_iter().map(UpdateStatus::from_list_entry).collect();
+        assert_eq!(statuses.len(), 3);
+        assert!(matches!(&statuses[0], UpdateStatus::Processed { content } if content.update_id == 1));
+        match &statuses[1] {
+            UpdateStatus::Other(raw) => assert_eq!(raw["updateId"], 2),
+            status => panic!("unexpected status: {}", status.as_str()),
+        }
+        assert_eq!(statuses[1].as_str(), "processed");
+        assert!(matches!(&statuses[2], UpdateStatus::Failed { content } if content.update_id == 2));
+    }
+
+    #[async_test]
+    async fn test_get_all_updates_malformed_entry() {
+        let (host, _) = mock_server(vec![(200, format!("[{},{}]", SETTINGS_V0_20, PROCESSED_V0_20))]);
+        let client = Client::new(host, "masterKey");
+        let statuses = client.assume_index("movies").get_all_updates().await.unwrap();
+        assert!(matches!(&statuses[0], UpdateStatus::Other(_)));
+        assert!(matches!(&statuses[1], UpdateStatus::Processed { .. }));
+    }
+
+    const PROCESSING: &str = r#"{"status":"processing","updateId":1,"type":{"name":"ClearAll"},"enqueuedAt":"2021-10-18T09:00:00Z"}"#;
+    const PROCESSED: &str = r#"{"status":"processed","updateId":1,"type":{"name":"ClearAll"},"duration":0.1,"enqueuedAt":"2021-10-18T09:00:00Z","processedAt":"2021-10-18T09:00:01Z"}"#;
+    const FAILED: &str = r#"{"status":"failed","updateId":1,"type":{"name":"ClearAll"},"error":"Something broke","errorType":"invalid_request_error","errorCode":"bad_request","errorLink":"https://docs.meilisearch.com/errors#bad_request","duration":0.1,"enqueuedAt":"2021-10-18T09:00:00Z","processedAt":"2021-10-18T09:00:01Z"}"#;
+    const INTERNAL_ERROR: &str = r#"{"message":"Restarting","errorCode":"internal","errorType":"internal_error","errorLink":""}"#;
+    const NOT_FOUND: &str = r#"{"message":"Update 1 not found","errorCode":"not_found","errorType":"invalid_request_error","errorLink":""}"#;
+
+    fn mock_progress(host: String) -> Progress {
+        Progress {
+            id: 1,
+            index_uid: Rc::new(String::from("movies")),
+            host: Rc::new(host),
+            api_key: Rc::new(String::from("masterKey")),
+            cache: None,
+            waits: Rc::new(PendingWaits::default()),
+            max_response_bytes: None,
+        }
+    }
+
+    fn fast_policy(max_poll_retries: usize) -> WaitPolicy {
+        WaitPolicy {
+            interval: time::Duration::from_millis(1),
+            timeout: time::Duration::from_secs(5),
+            max_poll_retries,
+            retry_backoff: time::Duration::from_millis(20),
+        }
+    }
+
+    #[async_test]
+    async fn test_wait_with_policy_retries_refused_connections() {
+        // Nothing listens on the port at first, as if the server was restarting
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let (sender, requests) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(time::Duration::from_millis(50));
+            let listener = std::net::TcpListener::bind(address).unwrap();
+            let _ = sender.send(mock_server_on(listener, vec![(500, INTERNAL_ERROR), (200, PROCESSING), (200, PROCESSED)]));
+        });
+
+        let progress = mock_progress(format!("http://{}", address));
+        let result = progress.wait_with_policy(&fast_policy(10)).await.unwrap();
+        assert_eq!(result.update_id, 1);
+        assert_eq!(requests.recv().unwrap().iter().count(), 3);
+    }
+
+    #[async_test]
+    async fn test_wait_with_policy_gives_up() {
+        // Failures are only counted when consecutive
+        let (host, _requests) = mock_server(vec![
+            (500, INTERNAL_ERROR), (200, PROCESSING), (500, INTERNAL_ERROR), (500, INTERNAL_ERROR), (500, INTERNAL_ERROR),
+        ]);
+        let error = mock_progress(host).wait_with_policy(&fast_policy(2)).await.unwrap_err();
+        match error {
+            Error::UpdateStatusUnavailable { update_id, attempts, last_error } => {
+                assert_eq!(update_id, 1);
+                assert_eq!(attempts, 3);
+                assert_eq!(last_error.code(), "internal");
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        // Errors that can't be fixed by waiting are not retried
+        let (host, _requests) = mock_server(vec![(404, NOT_FOUND)]);
+        let error = mock_progress(host).wait_with_policy(&fast_policy(2)).await.unwrap_err();
+        assert!(matches!(error, Error::UpdateStatusUnavailable { attempts: 1, .. }));
+    }
+
+    #[async_test]
+    async fn test_wait_with_policy_failed_update() {
+        let (host, _requests) = mock_server(vec![(500, INTERNAL_ERROR), (200, FAILED)]);
+        let error = mock_progress(host).wait_with_policy(&fast_policy(2)).await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { ref message, .. } if message == "Something broke"));
+    }
+
+    #[async_test]
+    async fn test_concurrent_waits_share_polls() {
+        // Alone, a waiter polls 4 times; the padding lets 10 independent waiters finish too
+        let mut responses = vec![(200, PROCESSING); 3];
+        responses.extend(vec![(200, PROCESSED); 37]);
+        let (host, requests) = mock_server(responses);
+        let client = Client::new(host, "masterKey");
+        let index = client.assume_index("movies");
+        let progresses: Vec<Progress> = (0..10).map(|_| ProgressJson { update_id: 1 }.into_progress(&index)).collect();
+
+        let interval = Some(time::Duration::from_millis(20));
+        let waits = progresses.iter().map(|progress| progress.wait_for_pending_update(interval, None));
+        for status in futures::future::join_all(waits).await {
+            assert!(matches!(status, Some(Ok(UpdateStatus::Processed { content })) if content.update_id == 1));
+        }
+        assert!(requests.try_iter().count() <= 6);
+        assert!(client.waits.waits().is_empty());
+
+        // A waiter dropped while polling lets the others poll
+        let (host, requests) = mock_server(vec![(200, PROCESSING), (200, PROCESSED)]);
+        let progress = mock_progress(host);
+        let turn = progress.waits.join("movies", 1);
+        let mut first = progress.waits.join("movies", 1);
+        assert!(matches!(first.next(), Turn::Poll));
+        drop(first);
+        let result = progress.wait_with_policy(&fast_policy(0)).await.unwrap();
+        assert_eq!(result.update_id, 1);
+        assert_eq!(requests.try_iter().count(), 2);
+        drop(turn);
+        assert!(progress.waits.waits().is_empty());
+    }
+
+    #[async_test]
+    async fn test_wait_with_policy_honors_timeout() {
+        let policy = WaitPolicy { timeout: time::Duration::from_millis(100), ..fast_policy(1000) };
+        let progress = mock_progress(String::from("http://127.0.0.1:1"));
+        let now = time::Instant::now();
+        let error = progress.wait_with_policy(&policy).await.unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+        assert!(now.elapsed() < time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_wait_policy_backoff() {
+        let policy = WaitPolicy { retry_backoff: time::Duration::from_millis(100), ..WaitPolicy::default() };
+        for (failed_attempts, base) in [(1, 100), (2, 200), (3, 400)] {
+            let backoff = policy.backoff(failed_attempts).as_secs_f64() * 1000.0;
+            assert!(backoff >= base as f64 * 0.75 && backoff <= base as f64 * 1.25, "{} ms", backoff);
+        }
+
+        let policy = WaitPolicy { retry_backoff: time::Duration::MAX, ..WaitPolicy::default() };
+        assert!(policy.backoff(20) >= time::Duration::MAX / 4 * 3);
+    }
+
+    #[async_test]
+    async fn test_cache_bypassed_until_processed() {
+        let (host, requests) = mock_server(vec![
+            (202, r#"{"updateId":1}"#),
+            (200, r#"["the"]"#),
+            (200, PROCESSING),
+            (200, r#"["the"]"#),
+            (200, PROCESSED),
+            (200, r#"["of"]"#),
+        ]);
+        let client = Client::new(host, "masterKey").with_cache(time::Duration::from_secs(60), 16);
+        let index = client.assume_index("movies");
+
+        let progress = index.delete_all_documents().await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("DELETE /indexes/movies/documents "));
+
+        // Reads made while the update is pending are not cached, since they may already be outdated
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+        assert!(matches!(progress.get_status().await.unwrap(), UpdateStatus::Processing { .. }));
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["the"]);
+        assert!(matches!(progress.get_status().await.unwrap(), UpdateStatus::Processed { .. }));
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+        assert_eq!(index.get_stop_words().await.unwrap(), vec!["of"]);
+        assert_eq!(requests.try_iter().filter(|request| request.starts_with("GET /indexes/movies/settings/stop-words ")).count(), 3);
+    }
+
+    #[async_test]
+    async fn test_status_missing_fields() {
+        let (host, _requests) = mock_server(vec![
+            (200, r#"{"status":"processed","updateId":1}"#),
+            (200, r#"{"status":"enqueued"}"#),
+            (200, ""),
+        ]);
+        let progress = mock_progress(host);
+
+        assert!(matches!(progress.get_status().await, Err(Error::ParseError(_))));
+        assert!(matches!(progress.wait_for_pending_update(None, None).await, Some(Err(Error::ParseError(_)))));
+        assert!(matches!(progress.wait_with_policy(&fast_policy(3)).await, Err(Error::UpdateStatusUnavailable { .. })));
+    }
+
     #[async_test]
     async fn test_async_sleep() {
         let sleep_duration = time::Duration::from_millis(10);
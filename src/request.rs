@@ -1,81 +1,308 @@
-use crate::errors::Error;
+use crate::errors::{Error, ErrorCode, ErrorType};
 use log::{error, trace, warn};
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::{from_str, to_string};
+use serde_json::{from_str, to_vec, Value};
+use std::time::Duration;
 
+/// The HTTP method of a [raw request](../client/struct.Client.html#method.raw_request), holding the body of the request if there is one.
 #[derive(Debug)]
-pub(crate) enum Method<T: Serialize> {
+pub enum Method<T: Serialize> {
+    /// A `GET` request, without body
     Get,
+    /// A `POST` request, with its body
     Post(T),
+    /// A `PUT` request, with its body
     Put(T),
+    /// A `PATCH` request, with its body
+    Patch(T),
+    /// A `DELETE` request, without body
     Delete,
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+/// The compression of the bodies of the requests sent by a [client](../client/struct.Client.html#method.with_request_compression).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Compression {
+    /// [gzip](https://datatracker.ietf.org/doc/html/rfc1952), sent with `Content-Encoding: gzip`
+    Gzip,
+}
+
+impl Compression {
+    fn content_encoding(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+        }
+    }
+}
+
+/// The size under which the bodies are sent uncompressed, since compressing them would save little or nothing.
+pub(crate) const COMPRESSION_THRESHOLD: usize = 1024;
+
 pub(crate) async fn request<Input: Serialize + std::fmt::Debug, Output: 'static + DeserializeOwned>(
     url: &str,
     apikey: &str,
     method: Method<Input>,
-    expected_status_code: u16
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
 ) -> Result<Output, Error> {
-    use isahc::*;
+    request_with_timeout(url, apikey, method, expected_status_code, max_response_bytes, None).await
+}
 
+/// Measures of a successful request, returned along with the response by the `with_metadata` methods,
+/// such as [Query::execute_with_metadata](../search/struct.Query.html#method.execute_with_metadata).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// The time between the start of the request and the end of the reading of the response
+    pub elapsed: Duration,
+    /// The size of the body of the request, in bytes
+    pub request_bytes: usize,
+    /// The size of the body of the response, in bytes
+    pub response_bytes: usize,
+    /// The HTTP status code of the response
+    pub status: u16,
+}
+
+/// Same as [request], failing with [Error::RequestTimeout] if the whole round-trip takes longer than `timeout`.
+pub(crate) async fn request_with_timeout<Input: Serialize + std::fmt::Debug, Output: 'static + DeserializeOwned>(
+    url: &str,
+    apikey: &str,
+    method: Method<Input>,
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<Output, Error> {
+    request_with_metrics(url, apikey, method, expected_status_code, max_response_bytes, timeout)
+        .await
+        .map(|(output, _)| output)
+}
+
+/// Same as [request_with_timeout], also returning the [measures](RequestMetrics) of the request.
+pub(crate) async fn request_with_metrics<Input: Serialize + std::fmt::Debug, Output: 'static + DeserializeOwned>(
+    url: &str,
+    apikey: &str,
+    method: Method<Input>,
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<(Output, RequestMetrics), Error> {
     trace!("{:?} on {}", method, url);
+    let method = match method {
+        Method::Get => Method::Get,
+        Method::Delete => Method::Delete,
+        Method::Post(body) => Method::Post(to_vec(&body).map_err(Error::ParseError)?),
+        Method::Put(body) => Method::Put(to_vec(&body).map_err(Error::ParseError)?),
+        Method::Patch(body) => Method::Patch(to_vec(&body).map_err(Error::ParseError)?),
+    };
+    send_request(url, apikey, method, "application/json", None, expected_status_code, max_response_bytes, timeout).await
+}
+
+/// Same as [request], sending the body as is with the given `content_type` instead of serializing it to JSON.
+///
+/// With a `compression`, the bodies of at least [COMPRESSION_THRESHOLD] bytes are compressed.
+pub(crate) async fn request_bytes<Output: 'static + DeserializeOwned>(
+    url: &str,
+    apikey: &str,
+    method: Method<Vec<u8>>,
+    content_type: &str,
+    compression: Option<Compression>,
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
+) -> Result<Output, Error> {
+    match &method {
+        Method::Post(body) | Method::Put(body) | Method::Patch(body) => trace!("{} bytes of {} on {}", body.len(), content_type, url),
+        Method::Get | Method::Delete => trace!("{:?} on {}", method, url),
+    }
+    let (method, content_encoding) = match compression {
+        Some(compression) => compress(method, compression)?,
+        None => (method, None),
+    };
+    send_request(url, apikey, method, content_type, content_encoding, expected_status_code, max_response_bytes, None)
+        .await
+        .map(|(output, _)| output)
+}
+
+/// Compress the body of a request if it is large enough, returning the `Content-Encoding` of the body if it was compressed.
+#[cfg(all(not(target_arch = "wasm32"), feature = "documents-io"))]
+fn compress(method: Method<Vec<u8>>, compression: Compression) -> Result<(Method<Vec<u8>>, Option<&'static str>), Error> {
+    use std::io::Write;
+
+    let encode = |body: Vec<u8>| -> Result<Vec<u8>, Error> {
+        let encoded = match compression {
+            Compression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::with_capacity(body.len() / 4), flate2::Compression::default());
+                encoder.write_all(&body).and_then(|_| encoder.finish())
+            }
+        };
+        encoded.map_err(|error| Error::CompressionFailed { compression, error })
+    };
+    let content_encoding = Some(compression.content_encoding());
+    Ok(match method {
+        Method::Post(body) if body.len() >= COMPRESSION_THRESHOLD => (Method::Post(encode(body)?), content_encoding),
+        Method::Put(body) if body.len() >= COMPRESSION_THRESHOLD => (Method::Put(encode(body)?), content_encoding),
+        Method::Patch(body) if body.len() >= COMPRESSION_THRESHOLD => (Method::Patch(encode(body)?), content_encoding),
+        method => (method, None),
+    })
+}
+
+/// The fetch API can't compress the bodies, and sending them uncompressed would go unnoticed.
+/// Without the `documents-io` feature, a client can't enable the compression in the first place.
+#[cfg(any(target_arch = "wasm32", not(feature = "documents-io")))]
+fn compress(_method: Method<Vec<u8>>, compression: Compression) -> Result<(Method<Vec<u8>>, Option<&'static str>), Error> {
+    Err(Error::UnsupportedCompression { compression })
+}
+
+/// Send a request whose body, if any, is already encoded in `content_type`.
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::too_many_arguments)]
+async fn send_request<Output: 'static + DeserializeOwned>(
+    url: &str,
+    apikey: &str,
+    method: Method<Vec<u8>>,
+    content_type: &str,
+    content_encoding: Option<&str>,
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<(Output, RequestMetrics), Error> {
+    use isahc::{config::Configurable, http::request::Builder, *};
+
+    let action = required_action(&method, url);
+    let started = std::time::Instant::now();
+    let (method, payload) = match method {
+        Method::Post(body) => (Method::Post(()), body),
+        Method::Put(body) => (Method::Put(()), body),
+        Method::Patch(body) => (Method::Patch(()), body),
+        Method::Get => (Method::Get, Vec::new()),
+        Method::Delete => (Method::Delete, Vec::new()),
+    };
+    let request_bytes = payload.len();
+    // The timeout covers the connection, the request and the reading of the response
+    let with_timeout = |builder: Builder| match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    let with_encoding = |builder: Builder| match content_encoding {
+        Some(content_encoding) => builder.header("Content-Encoding", content_encoding),
+        None => builder,
+    };
 
     let mut response = match &method {
-        Method::Get => Request::get(url)
+        Method::Get => with_timeout(Request::get(url))
             .header("X-Meili-API-Key", apikey)
+            .header("Authorization", format!("Bearer {}", apikey))
             .body(())
             .map_err(|_| crate::errors::Error::InvalidRequest)?
-            .send_async().await?,
-        Method::Delete => Request::delete(url)
+            .send_async().await.map_err(|e| transport_error(e, url, timeout))?,
+        Method::Delete => with_timeout(Request::delete(url))
             .header("X-Meili-API-Key", apikey)
+            .header("Authorization", format!("Bearer {}", apikey))
             .body(())
             .map_err(|_| crate::errors::Error::InvalidRequest)?
-            .send_async().await?,
-        Method::Post(body) => Request::post(url)
+            .send_async().await.map_err(|e| transport_error(e, url, timeout))?,
+        Method::Post(_) => with_encoding(with_timeout(Request::post(url)))
+            .header("X-Meili-API-Key", apikey)
+            .header("Authorization", format!("Bearer {}", apikey))
+            .header("Content-Type", content_type)
+            .body(payload)
+            .map_err(|_| crate::errors::Error::InvalidRequest)?
+            .send_async().await.map_err(|e| transport_error(e, url, timeout))?,
+        Method::Put(_) => with_encoding(with_timeout(Request::put(url)))
             .header("X-Meili-API-Key", apikey)
-            .header("Content-Type", "application/json")
-            .body(to_string(&body).unwrap())
+            .header("Authorization", format!("Bearer {}", apikey))
+            .header("Content-Type", content_type)
+            .body(payload)
             .map_err(|_| crate::errors::Error::InvalidRequest)?
-            .send_async().await?,
-        Method::Put(body) => Request::put(url)
+            .send_async().await.map_err(|e| transport_error(e, url, timeout))?,
+        Method::Patch(_) => with_encoding(with_timeout(Request::patch(url)))
             .header("X-Meili-API-Key", apikey)
-            .header("Content-Type", "application/json")
-            .body(to_string(&body).unwrap())
+            .header("Authorization", format!("Bearer {}", apikey))
+            .header("Content-Type", content_type)
+            .body(payload)
             .map_err(|_| crate::errors::Error::InvalidRequest)?
-            .send_async().await?,
+            .send_async().await.map_err(|e| transport_error(e, url, timeout))?,
     };
 
     let status = response.status().as_u16();
-    let mut body = response.text().await.map_err(|e| crate::errors::Error::HttpError(e.into()))?;
+    let mut body = match max_response_bytes {
+        Some(limit) => read_limited_body(&mut response, url, limit, timeout).await?,
+        None => response.text().await.map_err(|e| transport_error(isahc::Error::from(e), url, timeout))?,
+    };
+    let metrics = RequestMetrics { elapsed: started.elapsed(), request_bytes, response_bytes: body.len(), status };
     if body.is_empty() {
         body = "null".to_string();
     }
 
-    parse_response(status, expected_status_code, body)
+    parse_response(status, expected_status_code, body, action).map(|output| (output, metrics))
+}
+
+/// Read the body of a response, without ever buffering more than `limit + 1` bytes.
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_limited_body(response: &mut isahc::Response<isahc::AsyncBody>, url: &str, limit: usize, timeout: Option<Duration>) -> Result<String, Error> {
+    use futures::AsyncReadExt;
+
+    if let Some(length) = content_length(response.headers().get("Content-Length").and_then(|length| length.to_str().ok())) {
+        if length > limit {
+            return Err(Error::ResponseTooLarge { limit, url: url.to_string(), observed: length });
+        }
+    }
+
+    let mut body = Vec::new();
+    response
+        .body_mut()
+        .take((limit as u64).saturating_add(1))
+        .read_to_end(&mut body)
+        .await
+        .map_err(|e| transport_error(isahc::Error::from(e), url, timeout))?;
+    if body.len() > limit {
+        return Err(Error::ResponseTooLarge { limit, url: url.to_string(), observed: body.len() });
+    }
+
+    String::from_utf8(body).map_err(|e| Error::HttpError(std::io::Error::new(std::io::ErrorKind::InvalidData, e).into()))
 }
 
+/// Convert an error of isahc, telling apart the expiration of the `timeout` of the request.
+#[cfg(not(target_arch = "wasm32"))]
+fn transport_error(error: isahc::Error, url: &str, timeout: Option<Duration>) -> Error {
+    match timeout {
+        Some(timeout) if error.is_timeout() => Error::RequestTimeout { url: url.to_string(), timeout },
+        _ => Error::from(error),
+    }
+}
+
+fn content_length(header: Option<&str>) -> Option<usize> {
+    header.and_then(|length| length.trim().parse().ok())
+}
+
+/// Send a request whose body, if any, is already encoded in `content_type`.
+///
+/// The fetch can't be cancelled here, so it keeps running in the background after the timeout, but its response is ignored.
 #[cfg(target_arch = "wasm32")]
-pub(crate) async fn request<Input: Serialize + std::fmt::Debug, Output: 'static + DeserializeOwned>(
+#[allow(clippy::too_many_arguments)]
+async fn send_request<Output: 'static + DeserializeOwned>(
     url: &str,
     apikey: &str,
-    method: Method<Input>,
-    expected_status_code: u16
-) -> Result<Output, Error> {
-    use wasm_bindgen::JsValue;
-    use web_sys::{Headers, RequestInit, Response};
+    method: Method<Vec<u8>>,
+    content_type: &str,
+    content_encoding: Option<&str>,
+    expected_status_code: u16,
+    max_response_bytes: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<(Output, RequestMetrics), Error> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Headers, RequestInit, Response, Window, WorkerGlobalScope};
     use wasm_bindgen_futures::JsFuture;
 
-    trace!("{:?} on {}", method, url);
-
-    // The 2 following unwraps should not be able to fail
+    let action = required_action(&method, url);
 
-    let headers = Headers::new().unwrap();
-    headers.append("X-Meili-API-Key", apikey).unwrap();
+    // Appending a header fails if the API key contains characters that are not allowed in headers
+    let headers = Headers::new().map_err(|_| Error::InvalidRequest)?;
+    headers.append("X-Meili-API-Key", apikey).map_err(|_| Error::InvalidRequest)?;
+    headers.append("Authorization", &format!("Bearer {}", apikey)).map_err(|_| Error::InvalidRequest)?;
 
     let mut request: RequestInit = RequestInit::new();
     request.headers(&headers);
+    let started = js_sys::Date::now();
+    let mut request_bytes = 0;
 
     match &method {
         Method::Get => {
@@ -84,29 +311,64 @@ pub(crate) async fn request<Input: Serialize + std::fmt::Debug, Output: 'static
         Method::Delete => {
             request.method("DELETE");
         }
-        Method::Post(body) => {
-            request.method("POST");
-            headers.append("Content-Type", "application/json").unwrap();
-            request.body(Some(&JsValue::from_str(&to_string(body).unwrap())));
-        }
-        Method::Put(body) => {
-            request.method("PUT");
-            headers.append("Content-Type", "application/json").unwrap();
-            request.body(Some(&JsValue::from_str(&to_string(body).unwrap())));
+        Method::Post(body) | Method::Put(body) | Method::Patch(body) => {
+            request.method(match &method {
+                Method::Put(_) => "PUT",
+                Method::Patch(_) => "PATCH",
+                _ => "POST",
+            });
+            headers.append("Content-Type", content_type).map_err(|_| Error::InvalidRequest)?;
+            if let Some(content_encoding) = content_encoding {
+                headers.append("Content-Encoding", content_encoding).map_err(|_| Error::InvalidRequest)?;
+            }
+            request_bytes = body.len();
+            request.body(Some(&JsValue::from(js_sys::Uint8Array::from(body.as_slice()))));
         }
     }
 
-    let window = web_sys::window().unwrap(); // TODO remove this unwrap
-    let response = match JsFuture::from(window.fetch_with_str_and_init(url, &request)).await {
-        Ok(response) => Response::from(response),
+    // Both the response and its body are raced against the same timer, which resolves with `undefined`
+    let timer = timeout.map(crate::progress::timer_promise);
+    let race = |promise: js_sys::Promise| match &timer {
+        Some(timer) => js_sys::Promise::race(&js_sys::Array::of2(&promise, timer)),
+        None => promise,
+    };
+
+    // There is no window in web workers
+    let global = js_sys::global();
+    let promise = if let Some(window) = global.dyn_ref::<Window>() {
+        window.fetch_with_str_and_init(url, &request)
+    } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+        worker.fetch_with_str_and_init(url, &request)
+    } else {
+        error!("No fetch function in this JavaScript context");
+        return Err(Error::HttpError("No fetch function in this JavaScript context".to_string()));
+    };
+    let response = match JsFuture::from(race(promise)).await {
+        Ok(response) if timer.is_some() && response.is_undefined() => return Err(Error::RequestTimeout { url: url.to_string(), timeout: timeout.unwrap_or_default() }),
+        Ok(response) => match response.dyn_into::<Response>() {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Invalid response: {:?}", e);
+                return Err(Error::HttpError("Invalid response".to_string()));
+            }
+        },
         Err(e) => {
             error!("Network error: {:?}", e);
             return Err(Error::UnreachableServer);
         }
     };
     let status = response.status() as u16;
+    // The body can't be read by chunks here, so only the announced size is checked before reading it
+    if let Some(limit) = max_response_bytes {
+        if let Some(length) = content_length(response.headers().get("Content-Length").ok().flatten().as_deref()) {
+            if length > limit {
+                return Err(Error::ResponseTooLarge { limit, url: url.to_string(), observed: length });
+            }
+        }
+    }
     let text = match response.text() {
-        Ok(text) => match JsFuture::from(text).await {
+        Ok(text) => match JsFuture::from(race(text)).await {
+            Ok(text) if timer.is_some() && text.is_undefined() => return Err(Error::RequestTimeout { url: url.to_string(), timeout: timeout.unwrap_or_default() }),
             Ok(text) => text,
             Err(e) => {
                 error!("Invalid response: {:?}", e);
@@ -120,21 +382,145 @@ pub(crate) async fn request<Input: Serialize + std::fmt::Debug, Output: 'static
     };
 
     if let Some(t) = text.as_string() {
-        if t.is_empty() {
-            parse_response(status, expected_status_code, String::from("null"))
+        let metrics = RequestMetrics {
+            elapsed: Duration::from_secs_f64((js_sys::Date::now() - started).max(0.0) / 1000.0),
+            request_bytes,
+            response_bytes: t.len(),
+            status,
+        };
+        let output = if t.is_empty() {
+            parse_response(status, expected_status_code, String::from("null"), action)
         } else {
-            parse_response(status, expected_status_code, t)
-        }
+            parse_response(status, expected_status_code, t, action)
+        };
+        output.map(|output| (output, metrics))
     } else {
         error!("Invalid response");
         Err(Error::HttpError("Invalid utf8".to_string()))
     }
 }
 
+/// Percent-encode a query parameter value as described by [RFC 3986](https://datatracker.ietf.org/doc/html/rfc3986#section-2.1).
+///
+/// Every byte of the UTF-8 representation is encoded except the unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`).
+/// In particular, `&`, `=`, `+` and `#` are always encoded and a space becomes `%20`, never `+`.
+pub(crate) fn encode_query_component(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => {
+                encoded.push('%');
+                encoded.push(HEX[(byte >> 4) as usize] as char);
+                encoded.push(HEX[(byte & 0xF) as usize] as char);
+            }
+        }
+    }
+    encoded
+}
+
+/// Append query parameters to an url that doesn't have any yet.
+///
+/// Parameter names are expected to be plain ASCII identifiers and are appended as is.
+/// Values are encoded with [encode_query_component] so that they reach the server unaltered, whatever they contain.
+pub(crate) fn add_query_parameters(url: &str, parameters: &[(&str, &str)]) -> String {
+    let mut url = url.to_string();
+    for (i, (name, value)) in parameters.iter().enumerate() {
+        url.push(if i == 0 { '?' } else { '&' });
+        url.push_str(name);
+        url.push('=');
+        url.push_str(&encode_query_component(value));
+    }
+    url
+}
+
+/// Turn a serializable value into query parameters, one for each of its fields.
+///
+/// The value must serialize to a map or to nothing (`()` or `None`), whose `null` fields are skipped.
+/// Lists are joined with commas, as MeiliSearch expects for `fields` for example.
+pub(crate) fn query_parameters<Q: Serialize>(query: &Q) -> Result<Vec<(String, String)>, Error> {
+    fn to_parameter(value: &Value) -> Option<String> {
+        match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(_) | Value::Bool(_) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    let fields = match serde_json::to_value(query).map_err(Error::ParseError)? {
+        Value::Null => return Ok(Vec::new()),
+        Value::Object(fields) => fields,
+        _ => return Err(Error::InvalidRequest),
+    };
+    let mut parameters = Vec::new();
+    for (name, value) in fields {
+        let value = match &value {
+            Value::Null => continue,
+            Value::Array(values) => values.iter().map(to_parameter).collect::<Option<Vec<String>>>().map(|values| values.join(",")),
+            value => to_parameter(value),
+        };
+        parameters.push((name, value.ok_or(Error::InvalidRequest)?));
+    }
+    Ok(parameters)
+}
+
+/// Whether a request to a route (relative to the host, without query string) only reads data.
+/// The searches and document fetches sent with `POST` are reads, any other request than `GET` is considered a write.
+pub(crate) fn is_read_request<T: Serialize>(method: &Method<T>, route: &str) -> bool {
+    let segments: Vec<&str> = route.trim_matches('/').split('/').collect();
+    match method {
+        Method::Get => true,
+        Method::Post(_) => matches!(
+            segments.as_slice(),
+            ["multi-search"] | ["indexes", _, "search"] | ["indexes", _, "facet-search"] | ["indexes", _, "similar"] | ["indexes", _, "documents", "fetch"]
+        ),
+        _ => false,
+    }
+}
+
+/// Guess the [API key action](https://docs.meilisearch.com/reference/api/keys.html#actions) required by a route.
+fn required_action<T: Serialize>(method: &Method<T>, url: &str) -> Option<&'static str> {
+    let path = url.split('?').next().unwrap_or(url);
+    // Skip the scheme and the host
+    let segments: Vec<&str> = path.split('/').skip(3).collect();
+
+    if let Some(position) = segments.iter().position(|segment| *segment == "indexes") {
+        let route = &segments[position + 1..];
+        return Some(match (route.get(1).copied(), method) {
+            (None, Method::Get) => "indexes.get",
+            (None, Method::Post(_)) => "indexes.create",
+            (None, Method::Put(_)) => "indexes.update",
+            (None, Method::Delete) => "indexes.delete",
+            (Some("search"), _) => "search",
+            (Some("documents"), Method::Get) => "documents.get",
+            (Some("documents"), Method::Delete) => "documents.delete",
+            (Some("documents"), _) if route.last() == Some(&"delete-batch") => "documents.delete",
+            (Some("documents"), _) => "documents.add",
+            (Some("settings"), Method::Get) => "settings.get",
+            (Some("settings"), _) => "settings.update",
+            (Some("updates"), _) => "tasks.get",
+            (Some("stats"), _) => "stats.get",
+            _ => return None,
+        });
+    }
+
+    match (segments.first().copied(), method) {
+        (Some("stats"), _) => Some("stats.get"),
+        (Some("version"), _) => Some("version"),
+        (Some("keys"), _) => Some("keys.get"),
+        (Some("dumps"), Method::Post(_)) => Some("dumps.create"),
+        (Some("dumps"), _) => Some("dumps.get"),
+        _ => None,
+    }
+}
+
 fn parse_response<Output: DeserializeOwned>(
     status_code: u16,
     expected_status_code: u16,
     body: String,
+    action: Option<&'static str>,
 ) -> Result<Output, Error> {
     if status_code == expected_status_code {
         match from_str::<Output>(&body) {
@@ -149,8 +535,405 @@ fn parse_response<Output: DeserializeOwned>(
         };
     }
     warn!("Expected response code {}, got {}", expected_status_code, status_code);
-    match from_str(&body) {
-        Ok(e) => Err(Error::from(&e)),
-        Err(e) => Err(Error::ParseError(e)),
+    let error = match from_str::<serde_json::Value>(&body) {
+        Ok(e) if e.is_object() => Error::from(&e),
+        // Some proxies (Meilisearch Cloud for example) don't answer with JSON when the authentication fails
+        _ if status_code == 401 || status_code == 403 => Error::MeiliSearchError {
+            message: body,
+            error_code: if status_code == 401 { ErrorCode::MissingAuthorizationHeader } else { ErrorCode::InvalidToken },
+            error_type: ErrorType::Authentication,
+            error_link: String::new(),
+        },
+        // Older versions of MeiliSearch answer unknown routes with an empty body,
+        // and a 405 when the path happens to match a route of another method (`/documents/{id}` for `/documents/delete` for example)
+        _ if status_code == 404 || status_code == 405 => Error::MeiliSearchError {
+            message: body,
+            error_code: ErrorCode::NotFound,
+            error_type: ErrorType::InvalidRequest,
+            error_link: String::new(),
+        },
+        Ok(e) => Error::from(&e),
+        Err(e) => return Err(Error::ParseError(e)),
+    };
+
+    match (error, action) {
+        (Error::MeiliSearchError { message, error_code, error_type: ErrorType::Authentication, error_link }, Some(action)) => {
+            Err(Error::MeiliSearchError {
+                message: format!("{} (the API key may be missing the `{}` action)", message, action),
+                error_code,
+                error_type: ErrorType::Authentication,
+                error_link,
+            })
+        }
+        (error, _) => Err(error),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use futures_await_test::async_test;
+    use serde_json::Value;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::mpsc::{channel, Receiver},
+    };
+
+    /// Start a fake server answering each request with the next response of the list.
+    /// Returns the host of the server and a channel receiving the raw requests.
+    /// The bodies can be borrowed or owned strings.
+    pub(crate) fn mock_server<B: AsRef<str> + Send + 'static>(responses: Vec<(u16, B)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        (host, mock_server_on(listener, responses))
+    }
+
+    /// Same as [mock_server], on an existing listener.
+    pub(crate) fn mock_server_on<B: AsRef<str> + Send + 'static>(listener: TcpListener, responses: Vec<(u16, B)>) -> Receiver<String> {
+        let (sender, receiver) = channel();
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let body = body.as_ref();
+                let (mut stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                while let Ok(read) = stream.read(&mut buffer) {
+                    request.extend_from_slice(&buffer[..read]);
+                    if read == 0 || request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        break;
+                    }
+                }
+                let _ = sender.send(String::from_utf8_lossy(&request).into_owned());
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        receiver
+    }
+
+    #[test]
+    fn test_required_action() {
+        let host = "http://localhost:7700";
+        assert_eq!(required_action::<()>(&Method::Post(()), &format!("{}/indexes/movies/search", host)), Some("search"));
+        assert_eq!(required_action::<()>(&Method::Get, &format!("{}/indexes/movies/documents?limit=2", host)), Some("documents.get"));
+        assert_eq!(required_action::<()>(&Method::Post(()), &format!("{}/indexes/movies/documents/delete-batch", host)), Some("documents.delete"));
+        assert_eq!(required_action::<()>(&Method::Put(()), &format!("{}/indexes/movies/settings/stop-words", host)), Some("settings.update"));
+        assert_eq!(required_action::<()>(&Method::Delete, &format!("{}/indexes/movies", host)), Some("indexes.delete"));
+        assert_eq!(required_action::<()>(&Method::Get, &format!("{}/version", host)), Some("version"));
+        assert_eq!(required_action::<()>(&Method::Get, &format!("{}/health", host)), None);
+    }
+
+    #[test]
+    fn test_is_read_request() {
+        assert!(is_read_request::<()>(&Method::Get, "indexes/movies/settings"));
+        assert!(is_read_request::<()>(&Method::Post(()), "indexes/movies/search"));
+        assert!(is_read_request::<()>(&Method::Post(()), "/indexes/movies/facet-search"));
+        assert!(is_read_request::<()>(&Method::Post(()), "indexes/movies/documents/fetch"));
+        assert!(is_read_request::<()>(&Method::Post(()), "multi-search"));
+        assert!(!is_read_request::<()>(&Method::Post(()), "indexes/movies/documents"));
+        assert!(!is_read_request::<()>(&Method::Put(()), "indexes/movies/search"));
+        assert!(!is_read_request::<()>(&Method::Post(()), "swap-indexes"));
+        assert!(!is_read_request::<()>(&Method::Delete, "indexes/movies"));
+    }
+
+    /// Reverse of [encode_query_component], failing on anything a strict server wouldn't accept.
+    fn decode_query_component(encoded: &str) -> Option<String> {
+        let mut bytes = Vec::new();
+        let mut chars = encoded.bytes();
+        while let Some(byte) = chars.next() {
+            match byte {
+                b'%' => {
+                    let hex = [chars.next()?, chars.next()?];
+                    bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+                }
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => bytes.push(byte),
+                _ => return None,
+            }
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    const NASTY_VALUES: &[&str] = &[
+        "",
+        "genres = horror & year > 2000",
+        "title = \"a+b=c\"",
+        "tag = 'C++' OR tag = \"C#\"",
+        "path = /a/b?c=d#e",
+        "100% = 50%25",
+        "name = 'Amélie' AND city = '東京'",
+        "emoji = 🦀 AND space = ' '",
+        "[a, b] {c} |d| ^e` \\f",
+        "line\nbreak\ttab\r\0null",
+    ];
+
+    #[async_test]
+    async fn test_request_metrics() {
+        const RESPONSE: &str = r#"{"updateId":12}"#;
+        let (host, _requests) = mock_server(vec![(202, RESPONSE), (204, ""), (404, r#"{"message":"Index movies not found","code":"index_not_found","type":"invalid_request","link":""}"#)]);
+
+        let body = serde_json::json!({"title": "Dune"});
+        let (output, metrics) =
+            request_with_metrics::<_, Value>(&format!("{}/indexes/movies/documents", host), "masterKey", Method::Post(&body), 202, None, None).await.unwrap();
+        assert_eq!(output["updateId"], 12);
+        assert_eq!(metrics.status, 202);
+        assert_eq!(metrics.request_bytes, r#"{"title":"Dune"}"#.len());
+        assert_eq!(metrics.response_bytes, RESPONSE.len());
+        assert!(metrics.elapsed > Duration::from_secs(0) && metrics.elapsed < Duration::from_secs(10));
+
+        let (_, metrics) =
+            request_with_metrics::<(), Value>(&format!("{}/indexes/movies", host), "masterKey", Method::Delete, 204, Some(1024), None).await.unwrap();
+        assert_eq!((metrics.status, metrics.request_bytes, metrics.response_bytes), (204, 0, 0));
+
+        // No measures for a failed request
+        let error = request_with_metrics::<(), Value>(&format!("{}/indexes/movies", host), "masterKey", Method::Get, 200, None, None).await.unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { error_code: ErrorCode::IndexNotFound, .. }));
+    }
+
+    #[test]
+    fn test_query_parameters() {
+        let parameters = query_parameters(&serde_json::json!({"limit": 20, "fields": ["id", "title"], "from": null, "name": "a b", "pending": true})).unwrap();
+        assert_eq!(
+            parameters,
+            [("fields", "id,title"), ("limit", "20"), ("name", "a b"), ("pending", "true")]
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        );
+        assert!(query_parameters(&()).unwrap().is_empty());
+        assert!(query_parameters(&None::<()>).unwrap().is_empty());
+        assert!(matches!(query_parameters(&"limit=20"), Err(Error::InvalidRequest)));
+        assert!(matches!(query_parameters(&serde_json::json!({"filter": {"id": 1}})), Err(Error::InvalidRequest)));
+    }
+
+    #[test]
+    fn test_encode_query_component() {
+        assert_eq!(encode_query_component("a b"), "a%20b");
+        assert_eq!(encode_query_component("a+b"), "a%2Bb");
+        assert_eq!(encode_query_component("a&b=c"), "a%26b%3Dc");
+        assert_eq!(encode_query_component("é"), "%C3%A9");
+        assert_eq!(encode_query_component("Az09-._~"), "Az09-._~");
+
+        for value in NASTY_VALUES {
+            let encoded = encode_query_component(value);
+            assert!(!encoded.contains(|c: char| "&=+ ?#/".contains(c) || !c.is_ascii()), "{:?} encoded as {:?}", value, encoded);
+            assert_eq!(decode_query_component(&encoded).as_deref(), Some(*value));
+        }
+
+        // Every single byte sequence of a char survives the round trip
+        for c in (0..0x800).filter_map(std::char::from_u32).chain(['\u{FFFF}', '\u{10FFFF}'].iter().copied()) {
+            let value = format!("{}x{}", c, c);
+            assert_eq!(decode_query_component(&encode_query_component(&value)), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_add_query_parameters() {
+        assert_eq!(add_query_parameters("http://localhost:7700/indexes", &[]), "http://localhost:7700/indexes");
+        assert_eq!(
+            add_query_parameters("http://localhost:7700/indexes", &[("filter", "a = 1 & b = 2"), ("limit", "3")]),
+            "http://localhost:7700/indexes?filter=a%20%3D%201%20%26%20b%20%3D%202&limit=3"
+        );
+    }
+
+    #[async_test]
+    async fn test_query_parameters_reach_the_server() {
+        let responses = NASTY_VALUES.iter().map(|_| (200, "null")).collect();
+        let (host, requests) = mock_server(responses);
+
+        for value in NASTY_VALUES {
+            let url = add_query_parameters(&format!("{}/indexes/movies/documents", host), &[("filter", value), ("limit", "1")]);
+            request::<(), ()>(&url, "masterKey", Method::Get, 200, None).await.unwrap();
+
+            let raw_request = requests.recv().unwrap();
+            let target = raw_request.split(' ').nth(1).unwrap();
+            let query = target.split_once('?').unwrap().1;
+            let parameters: Vec<(&str, String)> = query
+                .split('&')
+                .map(|parameter| {
+                    let (name, value) = parameter.split_once('=').unwrap();
+                    (name, decode_query_component(value).unwrap())
+                })
+                .collect();
+            assert_eq!(parameters, vec![("filter", value.to_string()), ("limit", "1".to_string())]);
+        }
+    }
+
+    /// Start a fake server streaming a JSON array of `size` bytes, without announcing its size.
+    fn streaming_server(size: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = write!(stream, "HTTP/1.1 200 Mock\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n[0");
+            let chunk = ",0".repeat(4096);
+            let mut written = 3;
+            while written + chunk.len() < size {
+                // The client hangs up once it has read enough
+                if stream.write_all(chunk.as_bytes()).is_err() {
+                    return;
+                }
+                written += chunk.len();
+            }
+            let _ = stream.write_all(b"]");
+        });
+
+        host
+    }
+
+    #[async_test]
+    async fn test_max_response_bytes() {
+        const BODY: &str = r#"{"status":"available","padding":"0123456789012345678901234567890123456789"}"#;
+
+        // The announced size is checked before reading the body
+        let (host, _requests) = mock_server(vec![(200, BODY)]);
+        let url = format!("{}/health", host);
+        let error = request::<(), Value>(&url, "masterKey", Method::Get, 200, Some(16)).await.unwrap_err();
+        assert!(matches!(error, Error::ResponseTooLarge { limit: 16, observed, url: ref error_url } if observed == BODY.len() && *error_url == url));
+
+        // Responses up to the limit are accepted
+        let (host, _requests) = mock_server(vec![(200, BODY), (200, BODY)]);
+        let url = format!("{}/health", host);
+        request::<(), Value>(&url, "masterKey", Method::Get, 200, Some(BODY.len())).await.unwrap();
+        request::<(), Value>(&url, "masterKey", Method::Get, 200, None).await.unwrap();
+
+        // Streamed responses are dropped as soon as they exceed the limit
+        let url = format!("{}/indexes/movies/documents", streaming_server(64 * 1024 * 1024));
+        let error = request::<(), Value>(&url, "masterKey", Method::Get, 200, Some(1024)).await.unwrap_err();
+        assert!(matches!(error, Error::ResponseTooLarge { limit: 1024, observed: 1025, .. }));
+
+        let url = format!("{}/indexes/movies/documents", streaming_server(10 * 1024));
+        let documents = request::<(), Vec<usize>>(&url, "masterKey", Method::Get, 200, Some(1024 * 1024)).await.unwrap();
+        assert!(documents.len() > 1000);
+    }
+
+    /// Start a fake server accepting connections and reading the requests, but answering after `delay` only.
+    pub(crate) fn slow_server(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                std::thread::spawn(move || {
+                    let mut buffer = [0; 4096];
+                    let _ = stream.read(&mut buffer);
+                    std::thread::sleep(delay);
+                    let _ = write!(stream, "HTTP/1.1 200 Mock\r\nContent-Type: application/json\r\nContent-Length: 4\r\nConnection: close\r\n\r\nnull");
+                });
+            }
+        });
+
+        host
+    }
+
+    #[async_test]
+    async fn test_request_timeout() {
+        let url = format!("{}/health", slow_server(Duration::from_secs(10)));
+        let start = std::time::Instant::now();
+        let error = request_with_timeout::<(), Value>(&url, "masterKey", Method::Get, 200, None, Some(Duration::from_millis(200))).await.unwrap_err();
+        assert!(matches!(&error, Error::RequestTimeout { url: error_url, timeout } if error_url == &url && *timeout == Duration::from_millis(200)));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        // Requests answered in time are not affected
+        let url = format!("{}/health", slow_server(Duration::from_millis(10)));
+        let response = request_with_timeout::<(), Value>(&url, "masterKey", Method::Get, 200, None, Some(Duration::from_secs(5))).await.unwrap();
+        assert_eq!(response, Value::Null);
+    }
+
+    /// Start a fake server answering a single request with `body`, which doesn't have to be UTF-8.
+    fn raw_server(body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = write!(stream, "HTTP/1.1 200 Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len());
+            let _ = stream.write_all(body);
+        });
+
+        host
+    }
+
+    #[async_test]
+    async fn test_empty_body() {
+        let (host, _requests) = mock_server(vec![(200, ""), (202, "")]);
+        let url = format!("{}/version", host);
+        let error = request::<(), crate::client::Version>(&url, "masterKey", Method::Get, 200, None).await.unwrap_err();
+        assert!(matches!(error, Error::ParseError(_)));
+
+        let url = format!("{}/indexes/movies/documents", host);
+        let result = request::<(), crate::progress::ProgressJson>(&url, "masterKey", Method::Delete, 202, None).await;
+        assert!(matches!(result, Err(Error::ParseError(_))));
+    }
+
+    #[async_test]
+    async fn test_non_utf8_body() {
+        const BODY: &[u8] = b"{\"status\":\"\xff\xfe\"}";
+
+        let url = format!("{}/health", raw_server(BODY));
+        let error = request::<(), Value>(&url, "masterKey", Method::Get, 200, Some(1024)).await.unwrap_err();
+        assert!(matches!(error, Error::HttpError(_)));
+
+        // Without a limit, isahc decodes the body lossily
+        let url = format!("{}/health", raw_server(BODY));
+        let health = request::<(), Value>(&url, "masterKey", Method::Get, 200, None).await.unwrap();
+        assert_eq!(health["status"], "\u{fffd}\u{fffd}");
+    }
+
+    #[test]
+    fn test_non_json_not_found_error() {
+        let error = parse_response::<()>(404, 202, String::from("null"), None).unwrap_err();
+        assert!(matches!(error, Error::MeiliSearchError { error_code: ErrorCode::NotFound, error_type: ErrorType::InvalidRequest, .. }));
+    }
+
+    #[test]
+    fn test_non_json_authentication_error() {
+        let error = parse_response::<()>(401, 200, String::from("Unauthorized"), Some("search")).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MeiliSearchError { error_type: ErrorType::Authentication, error_code: ErrorCode::MissingAuthorizationHeader, ref message, .. }
+                if message.contains("`search`")
+        ));
+    }
+
+    #[cfg(feature = "documents-io")]
+    #[test]
+    fn test_compress() {
+        let body = br#"{"id":1,"title":"Dune"}"#.repeat(100);
+        let (method, content_encoding) = compress(Method::Post(body.clone()), Compression::Gzip).unwrap();
+        assert_eq!(content_encoding, Some("gzip"));
+        let compressed = match method {
+            Method::Post(compressed) => compressed,
+            method => panic!("{:?}", method),
+        };
+        assert!(compressed.len() < body.len());
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, body);
+
+        // Small bodies and requests without a body are sent as they are
+        let small = vec![b'x'; COMPRESSION_THRESHOLD - 1];
+        assert!(matches!(compress(Method::Put(small.clone()), Compression::Gzip).unwrap(), (Method::Put(body), None) if body == small));
+        assert!(matches!(compress(Method::Get, Compression::Gzip).unwrap(), (Method::Get, None)));
     }
 }
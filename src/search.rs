@@ -1,82 +1,351 @@
-use crate::{errors::Error, indexes::Index};
+use crate::{client::Client, errors::Error, indexes::Index, request::*};
 use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::{HashMap, HashSet}, marker::PhantomData, time::Duration};
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MatchRange {
     pub start: usize,
     pub length: usize,
 }
 
+/// Details of the `words` ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WordsScoreDetails {
+    pub order: usize,
+    pub matching_words: usize,
+    pub max_matching_words: usize,
+    pub score: f64,
+}
+
+/// Details of the `typo` ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoScoreDetails {
+    pub order: usize,
+    pub typo_count: usize,
+    pub max_typo_count: usize,
+    pub score: f64,
+}
+
+/// Details of the `proximity` ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProximityScoreDetails {
+    pub order: usize,
+    pub score: f64,
+}
+
+/// Details of the `attribute` ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeScoreDetails {
+    pub order: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute_ranking_order_score: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_word_distance_score: Option<f64>,
+    pub score: f64,
+}
+
+/// Details of the `exactness` ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExactnessScoreDetails {
+    pub order: usize,
+    pub match_type: String,
+    pub score: f64,
+}
+
+/// The contribution of each ranking rule to the [ranking score](struct.SearchResult.html#structfield.ranking_score) of a result.
+///
+/// A rule is `None` when it was not used to rank the result.
+/// Custom rules (like `release_date:desc`) and rules unknown to this version of the SDK are stored in [other](#structfield.other).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RankingScoreDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<WordsScoreDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typo: Option<TypoScoreDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proximity: Option<ProximityScoreDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<AttributeScoreDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exactness: Option<ExactnessScoreDetails>,
+    /// Details of the other rules, indexed by rule name.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
 /// A single result.
 /// Contains the complete object, optionally the formatted object, and optionally an object that contains information about the matches.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct SearchResult<T> {
     /// The full result.
     #[serde(flatten)]
     pub result: T,
     /// The formatted result.
-    #[serde(rename = "_formatted")]
-    pub formatted_result: Option<T>,
+    ///
+    /// It is kept as raw JSON because MeiliSearch may turn numbers, booleans and arrays into strings when they are highlighted or cropped.
+    /// See [formatted_field](#method.formatted_field) and [try_formatted_as](#method.try_formatted_as).
+    #[serde(rename = "_formatted", skip_serializing_if = "Option::is_none")]
+    pub formatted_result: Option<serde_json::Map<String, serde_json::Value>>,
     /// The object that contains information about the matches.
-    #[serde(rename = "_matchesInfo")]
+    #[serde(rename = "_matchesInfo", skip_serializing_if = "Option::is_none")]
     pub matches_info: Option<HashMap<String, Vec<MatchRange>>>,
+    /// The position of the matches in each attribute, as byte offsets in the raw value of the attribute.
+    /// Only returned if [show_matches_position](struct.Query.html#structfield.show_matches_position) is set.
+    #[serde(rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
+    pub matches_position: Option<HashMap<String, Vec<MatchRange>>>,
+    /// The relevancy score of the result, between `0.0` and `1.0`.
+    /// Only returned if [show_ranking_score](struct.Query.html#structfield.show_ranking_score) is set.
+    #[serde(rename = "_rankingScore", skip_serializing_if = "Option::is_none")]
+    pub ranking_score: Option<f64>,
+    /// The contribution of each ranking rule to the ranking score.
+    /// Only returned if [show_ranking_score_details](struct.Query.html#structfield.show_ranking_score_details) is set.
+    #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
+    pub ranking_score_details: Option<RankingScoreDetails>,
+    /// Where the result comes from.
+    /// Only returned by a [federated multi search](struct.MultiSearchQuery.html#method.with_federation).
+    #[serde(rename = "_federation", skip_serializing_if = "Option::is_none")]
+    pub federation: Option<FederationHitInfo>,
+    /// The vectors of the document, indexed by embedder name.
+    /// Depending on the embedder, a value is either an array of vectors or an object like `{"embeddings": [...], "regenerate": false}`.
+    /// Only returned if [retrieve_vectors](struct.Query.html#structfield.retrieve_vectors) is set.
+    #[serde(rename = "_vectors", skip_serializing_if = "Option::is_none")]
+    pub vectors: Option<HashMap<String, serde_json::Value>>,
+    /// The distance in meters between the `_geo` field of the document and the point used to sort the results.
+    /// Only returned when the results are [sorted by distance](struct.Query.html#method.with_sort_by_distance).
+    ///
+    /// Since the SDK reads it, it can't be read by a `_geoDistance` field of `T` anymore.
+    #[serde(rename = "_geoDistance", skip_serializing_if = "Option::is_none")]
+    pub geo_distance: Option<u64>,
+}
+
+impl<T> SearchResult<T> {
+    /// Get a string field of the [formatted result](#structfield.formatted_result), if it exists.
+    pub fn formatted_field(&self, name: &str) -> Option<&str> {
+        self.formatted_result.as_ref()?.get(name)?.as_str()
+    }
+
+    /// Deserialize the [formatted result](#structfield.formatted_result) into a custom type.
+    ///
+    /// Returns `None` if there is no formatted result.
+    ///
+    /// **Caveat**: when highlighting or cropping non-string fields (with a [wildcard](enum.Selectors.html#variant.All) for example),
+    /// MeiliSearch may return them as strings, so deserializing into the type of your documents can fail.
+    /// Prefer a type where formatted fields are `String`s, or use [formatted_field](#method.formatted_field).
+    pub fn try_formatted_as<U: DeserializeOwned>(&self) -> Option<Result<U, Error>> {
+        self.formatted_result.as_ref().map(|formatted| {
+            serde_json::from_value(serde_json::Value::Object(formatted.clone())).map_err(Error::ParseError)
+        })
+    }
+}
+
+impl<T: DeserializeOwned> SearchResult<T> {
+    /// Deserialize the [formatted result](#structfield.formatted_result) into the type of the documents.
+    ///
+    /// Returns `None` unless attributes to [highlight](struct.Query.html#method.with_attributes_to_highlight) or to [crop](struct.Query.html#method.with_attributes_to_crop) were requested.
+    /// The same caveat as [try_formatted_as](#method.try_formatted_as) applies to non-string fields.
+    pub fn formatted(&self) -> Option<Result<T, Error>> {
+        self.try_formatted_as()
+    }
 }
 
-#[derive(Deserialize, Debug)]
+/// Where a result of a [federated multi search](struct.MultiSearchQuery.html#method.with_federation) comes from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+pub struct FederationHitInfo {
+    /// The index containing the document
+    pub index_uid: String,
+    /// The position of the query that matched the document in the [multi search](struct.MultiSearchQuery.html#structfield.queries)
+    pub queries_position: usize,
+    /// The ranking score of the document, multiplied by the [weight](struct.QueryFederationOptions.html#structfield.weight) of the query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weighted_ranking_score: Option<f64>,
+}
+
+/// The lowest and highest values of a numeric facet among the matching documents.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FacetStats {
+    /// The lowest value
+    pub min: f64,
+    /// The highest value
+    pub max: f64,
+}
+
 /// A struct containing search results and other information about the search.
+///
+/// It is serialized in the format of MeiliSearch, so that it can be cached or forwarded as is.
+/// The fields missing from the response are omitted, except [offset](#structfield.offset) and [limit](#structfield.limit) which default to `0` and are always present.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct SearchResults<T> {
     /// Results of the query
     pub hits: Vec<SearchResult<T>>,
-    /// Number of documents skipped
+    /// Number of documents skipped (`0` in the exhaustive pagination mode, see [page](#structfield.page))
+    #[serde(default)]
     pub offset: usize,
-    /// Number of results returned
+    /// Number of results returned (`0` in the exhaustive pagination mode, see [hits_per_page](#structfield.hits_per_page))
+    #[serde(default)]
     pub limit: usize,
-    /// Total number of matches
-    pub nb_hits: usize,
-    /// Whether nb_hits is exhaustive
-    pub exhaustive_nb_hits: bool,
-    /// Distribution of the given facets
+    /// Total number of matches.
+    /// Only returned by MeiliSearch v0.2x, see [estimated_total_hits](#structfield.estimated_total_hits) and [total_hits](#structfield.total_hits) for the newer versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nb_hits: Option<usize>,
+    /// Whether nb_hits is exhaustive.
+    /// Only returned by MeiliSearch v0.2x.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exhaustive_nb_hits: Option<bool>,
+    /// Estimated total number of matches.
+    /// Only returned by newer versions of MeiliSearch, when the exhaustive pagination mode is not used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_total_hits: Option<usize>,
+    /// Exact total number of matches.
+    /// Only returned in the exhaustive pagination mode, enabled with [Query::with_page](struct.Query.html#method.with_page) or [Query::with_hits_per_page](struct.Query.html#method.with_hits_per_page).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_hits: Option<usize>,
+    /// Total number of pages.
+    /// Only returned in the exhaustive pagination mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<usize>,
+    /// The current page.
+    /// Only returned in the exhaustive pagination mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// Number of results in each page.
+    /// Only returned in the exhaustive pagination mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_per_page: Option<usize>,
+    /// Distribution of the facets requested in [facets_distribution](struct.Query.html#structfield.facets_distribution).
+    /// Only returned by MeiliSearch v0.2x, see [facet_distribution](#structfield.facet_distribution) for the newer versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub facets_distribution: Option<HashMap<String, HashMap<String, usize>>>,
     /// Whether facet_distribution is exhaustive
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub exhaustive_facets_count: Option<bool>,
+    /// Distribution of the facets requested in [facets](struct.Query.html#structfield.facets).
+    /// Only returned by MeiliSearch v1 and newer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_distribution: Option<HashMap<String, HashMap<String, usize>>>,
+    /// The lowest and highest values of the numeric facets requested in [facets](struct.Query.html#structfield.facets).
+    /// Only returned by MeiliSearch v1 and newer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_stats: Option<HashMap<String, FacetStats>>,
     /// Processing time of the query
     pub processing_time_ms: usize,
     /// Query originating the response
     pub query: String,
+    /// The index the results come from.
+    /// Only returned by [multi search](struct.MultiSearchQuery.html).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_uid: Option<String>,
+    /// Whether the search was stopped by the `searchCutoffMs` setting before ranking all the documents.
+    /// Only returned by some versions of MeiliSearch, see [is_degraded](#method.is_degraded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degraded: Option<bool>,
 }
 
-fn serialize_with_wildcard<S: Serializer, T: Serialize>(
-    data: &Option<Selectors<T>>,
-    s: S,
-) -> Result<S::Ok, S::Error> {
-    match data {
-        Some(Selectors::All) => ["*"].serialize(s),
-        Some(Selectors::Some(data)) => data.serialize(s),
-        None => s.serialize_none(),
+/// The HTTP method used to send a search, see [Query::execute_with_method](struct.Query.html#method.execute_with_method).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMethod {
+    /// The parameters are sent as a JSON body. This is the default.
+    Post,
+    /// The parameters are sent in the query string, which allows HTTP caches to cache the results.
+    ///
+    /// Query [vectors](struct.Query.html#structfield.vector), [array filters](struct.Query.html#method.with_array_filter)
+    /// and list items containing a comma can't be sent this way.
+    Get,
+}
+
+/// Format a scalar JSON value as a query string value.
+fn get_parameter_value(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(value) => Some(value.clone()),
+        serde_json::Value::Number(value) => Some(value.to_string()),
+        serde_json::Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Search results as raw JSON, returned by [Query::execute_raw](struct.Query.html#method.execute_raw).
+///
+/// The hits are kept exactly as sent by MeiliSearch, with the special fields like `_formatted` or `_rankingScore`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RawSearchResults {
+    /// Results of the query
+    pub hits: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// All the other fields of the response (`query`, `processingTimeMs`, `estimatedTotalHits`...)
+    #[serde(flatten)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<T> SearchResults<T> {
+    /// Whether the search was stopped by the `searchCutoffMs` setting, making the ranking partial.
+    ///
+    /// The flag is read from the response, or from the [ranking score details](struct.SearchResult.html#structfield.ranking_score_details) of the hits
+    /// depending on the version of MeiliSearch. Returns `None` if the server didn't send it.
+    pub fn is_degraded(&self) -> Option<bool> {
+        if self.degraded.is_some() {
+            return self.degraded;
+        }
+        let mut flags = self.hits.iter().filter_map(|hit| {
+            hit.ranking_score_details.as_ref()?.other.get("degraded")?.as_bool()
+        });
+        let first = flags.next()?;
+        Some(first || flags.any(|degraded| degraded))
+    }
+
+    /// Deserialize the distribution of the facets into a custom type, typically a struct with a `HashMap<String, usize>` field per facet.
+    ///
+    /// The distribution is read from [facet_distribution](#structfield.facet_distribution) with MeiliSearch v1 and newer,
+    /// whose facets are requested with [with_facets](struct.Query.html#method.with_facets),
+    /// and from [facets_distribution](#structfield.facets_distribution) with MeiliSearch v0.2x.
+    /// Returns `None` if no facets distribution was requested.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, search::*};
+    /// # use serde::Deserialize;
+    /// # use std::collections::HashMap;
+    /// #[derive(Deserialize)]
+    /// struct MovieFacets {
+    ///     genres: HashMap<String, usize>,
+    ///     release_year: HashMap<String, usize>,
+    /// }
+    ///
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_facets_distribution_as").await.unwrap();
+    /// movies.set_filterable_attributes_and_wait(["genres", "release_year"], None, None).await.unwrap();
+    ///
+    /// let results = movies.search()
+    ///     .with_facets_distribution(Selectors::Some(&["genres", "release_year"]))
+    ///     .execute::<serde_json::Value>()
+    ///     .await
+    ///     .unwrap();
+    /// let facets: MovieFacets = results.facets_distribution_as().unwrap().unwrap();
+    /// # client.delete_index("movies_facets_distribution_as").await.unwrap();
+    /// # });
+    /// ```
+    pub fn facets_distribution_as<F: DeserializeOwned>(&self) -> Option<Result<F, Error>> {
+        self.facet_distribution.as_ref().or(self.facets_distribution.as_ref()).map(|distribution| {
+            serde_json::to_value(distribution)
+                .and_then(serde_json::from_value)
+                .map_err(Error::ParseError)
+        })
     }
 }
 
-fn serialize_attributes_to_crop_with_wildcard<S: Serializer>(
-    data: &Option<Selectors<&[AttributeToCrop]>>,
+fn serialize_with_wildcard<S: Serializer, T: Serialize>(
+    data: &Option<Selectors<T>>,
     s: S,
 ) -> Result<S::Ok, S::Error> {
     match data {
         Some(Selectors::All) => ["*"].serialize(s),
-        Some(Selectors::Some(data)) => {
-            let mut results = Vec::new();
-            for (name, value) in data.iter() {
-                let mut result = String::new();
-                result.push_str(name);
-                if let Some(value) = value {
-                    result.push(':');
-                    result.push_str(value.to_string().as_str());
-                }
-                results.push(result)
-            }
-            results.serialize(s)
-        }
+        Some(Selectors::Some(data)) => data.serialize(s),
         None => s.serialize_none(),
     }
 }
@@ -91,7 +360,416 @@ pub enum Selectors<T> {
     All,
 }
 
-type AttributeToCrop<'a> = (&'a str, Option<usize>);
+/// An attribute to [crop](struct.Query.html#structfield.attributes_to_crop), with an optional length overwriting the [crop length](struct.Query.html#structfield.crop_length) of the query.
+///
+/// It can be built from the name of the attribute, or from a `(name, length)` pair.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::{client::Client, search::*};
+/// # let client = Client::new("http://localhost:7700", "masterKey");
+/// # let index = client.assume_index("movies");
+/// let attributes_to_crop = [("description", 30).into(), "title".into()];
+/// let query = index.search()
+///     .with_attributes_to_crop(Selectors::Some(&attributes_to_crop))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttributeToCrop<'a> {
+    /// The name of the attribute
+    pub name: &'a str,
+    /// The number of characters to keep on each side of the start of the matching word
+    pub length: Option<usize>,
+}
+
+impl<'a> From<&'a str> for AttributeToCrop<'a> {
+    fn from(name: &'a str) -> AttributeToCrop<'a> {
+        AttributeToCrop { name, length: None }
+    }
+}
+
+impl<'a> From<(&'a str, usize)> for AttributeToCrop<'a> {
+    fn from((name, length): (&'a str, usize)) -> AttributeToCrop<'a> {
+        AttributeToCrop { name, length: Some(length) }
+    }
+}
+
+impl<'a> From<(&'a str, Option<usize>)> for AttributeToCrop<'a> {
+    fn from((name, length): (&'a str, Option<usize>)) -> AttributeToCrop<'a> {
+        AttributeToCrop { name, length }
+    }
+}
+
+/// Serialized as `name` or `name:length`.
+impl Serialize for AttributeToCrop<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self.length {
+            Some(length) => s.serialize_str(&format!("{}:{}", self.name, length)),
+            None => s.serialize_str(self.name),
+        }
+    }
+}
+
+/// The direction of a [Sort](struct.Sort.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ascending => "asc",
+            Direction::Descending => "desc",
+        }
+    }
+}
+
+/// A [sort](struct.Query.html#structfield.sort) criterion.
+///
+/// It can be built from a raw string like `"price:asc"`, or with the constructors to avoid typos.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::{client::Client, search::*};
+/// # let client = Client::new("http://localhost:7700", "masterKey");
+/// # let index = client.assume_index("restaurants");
+/// let query = index.search()
+///     .with_sort(&[Sort::geo_point(48.8561446, 2.2978204, Direction::Ascending), Sort::descending("rating")])
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Sort<'a>(Cow<'a, str>);
+
+impl<'a> Sort<'a> {
+    /// Sort by `attribute` in ascending order (`attribute:asc`).
+    pub fn ascending(attribute: &str) -> Sort<'a> {
+        Sort::attribute(attribute, Direction::Ascending)
+    }
+
+    /// Sort by `attribute` in descending order (`attribute:desc`).
+    pub fn descending(attribute: &str) -> Sort<'a> {
+        Sort::attribute(attribute, Direction::Descending)
+    }
+
+    /// Sort by `attribute` in the given direction.
+    pub fn attribute(attribute: &str, direction: Direction) -> Sort<'a> {
+        Sort(Cow::Owned(format!("{}:{}", attribute, direction.as_str())))
+    }
+
+    /// Sort by the distance between the `_geo` field of the documents and a point (`_geoPoint(lat,lng):asc`).
+    pub fn geo_point(lat: f64, lng: f64, direction: Direction) -> Sort<'a> {
+        Sort(Cow::Owned(format!("_geoPoint({},{}):{}", lat, lng, direction.as_str())))
+    }
+
+    /// The criterion, as sent to the server.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> From<&'a str> for Sort<'a> {
+    fn from(sort: &'a str) -> Sort<'a> {
+        Sort(Cow::Borrowed(sort))
+    }
+}
+
+impl<'a> From<&&'a str> for Sort<'a> {
+    fn from(sort: &&'a str) -> Sort<'a> {
+        Sort(Cow::Borrowed(*sort))
+    }
+}
+
+impl<'a> From<String> for Sort<'a> {
+    fn from(sort: String) -> Sort<'a> {
+        Sort(Cow::Owned(sort))
+    }
+}
+
+/// Escape the backslashes and double quotes of `query`, so that it can be put between double quotes in a [phrase search](struct.Query.html#method.with_phrase).
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::search::escape_query;
+/// assert_eq!(escape_query(r#"the "best" \ movie"#), r#"the \"best\" \\ movie"#);
+/// ```
+pub fn escape_query(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+    for c in query.chars() {
+        if c == '"' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quote `value` so that it can be compared to a field in a [filter](struct.Query.html#method.with_filter), whatever it contains.
+///
+/// In a quoted value, MeiliSearch only unescapes the quote character, but a backslash always protects the character following it.
+/// The value is thus put between double quotes, or between single quotes when a backslash precedes a double quote in it.
+/// A value ending with an odd number of backslashes can't be written exactly: a backslash is added so that the quote still closes it.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::search::escape_value;
+/// assert_eq!(escape_value("Harry Potter"), r#""Harry Potter""#);
+/// assert_eq!(escape_value(r#"the "best" movie"#), r#""the \"best\" movie""#);
+/// let filter = format!("title = {} AND year > 2000", escape_value(r#"Rock" OR title = "Roll"#));
+/// assert_eq!(filter, r#"title = "Rock\" OR title = \"Roll" AND year > 2000"#);
+/// ```
+pub fn escape_value(value: &str) -> String {
+    let quote = if quotable(value, '"') || !quotable(value, '\'') { '"' } else { '\'' };
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push(quote);
+    let mut backslashes = 0;
+    for c in value.chars() {
+        if c == quote {
+            // Only when no quote character fits the value
+            if backslashes % 2 == 1 {
+                escaped.push('\\');
+            }
+            escaped.push('\\');
+        }
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+        escaped.push(c);
+    }
+    if backslashes % 2 == 1 {
+        escaped.push('\\');
+    }
+    escaped.push(quote);
+    escaped
+}
+
+/// Whether no odd sequence of backslashes precedes `quote` in `value`, which would then escape the backslash protecting `quote`.
+fn quotable(value: &str, quote: char) -> bool {
+    let mut backslashes = 0;
+    for c in value.chars() {
+        if c == quote && backslashes % 2 == 1 {
+            return false;
+        }
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+    }
+    true
+}
+
+/// A geographic filter on the `_geo` field of the documents.
+///
+/// The `_geo` attribute must be [filterable](../settings/struct.Settings.html#structfield.filterable_attributes).
+/// It can be passed to [with_filter](struct.Query.html#method.with_filter), or formatted into a larger filter expression.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::{client::Client, search::*};
+/// # let client = Client::new("http://localhost:7700", "masterKey");
+/// # let index = client.assume_index("restaurants");
+/// let around_eiffel_tower = GeoFilter::radius(48.8583701, 2.2922926, 2000).unwrap();
+/// let query = index.search()
+///     .with_filter(format!("{} AND rating >= 4", around_eiffel_tower))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeoFilter {
+    /// Documents within `meters` of a point (`_geoRadius(lat, lng, meters)`).
+    Radius { lat: f64, lng: f64, meters: u64 },
+    /// Documents within a rectangle (`_geoBoundingBox([lat, lng], [lat, lng])`).
+    BoundingBox { top_right: (f64, f64), bottom_left: (f64, f64) },
+}
+
+impl GeoFilter {
+    /// Select the documents within `meters` of the point at `lat`, `lng`.
+    ///
+    /// Fails with [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) if the coordinates are out of range.
+    pub fn radius(lat: f64, lng: f64, meters: u64) -> Result<GeoFilter, Error> {
+        check_coordinates((lat, lng))?;
+        Ok(GeoFilter::Radius { lat, lng, meters })
+    }
+
+    /// Select the documents within the rectangle delimited by its `top_right` and `bottom_left` corners, as `(lat, lng)` pairs.
+    ///
+    /// Fails with [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) if the coordinates are out of range.
+    pub fn bounding_box(top_right: (f64, f64), bottom_left: (f64, f64)) -> Result<GeoFilter, Error> {
+        check_coordinates(top_right)?;
+        check_coordinates(bottom_left)?;
+        Ok(GeoFilter::BoundingBox { top_right, bottom_left })
+    }
+}
+
+fn check_coordinates((lat, lng): (f64, f64)) -> Result<(), Error> {
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return Err(Error::InvalidRequest);
+    }
+    Ok(())
+}
+
+/// Coordinates are written with the shortest representation that parses back to the exact same number.
+impl std::fmt::Display for GeoFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeoFilter::Radius { lat, lng, meters } => write!(f, "_geoRadius({:?}, {:?}, {})", lat, lng, meters),
+            GeoFilter::BoundingBox { top_right, bottom_left } => write!(
+                f,
+                "_geoBoundingBox([{:?}, {:?}], [{:?}, {:?}])",
+                top_right.0, top_right.1, bottom_left.0, bottom_left.1
+            ),
+        }
+    }
+}
+
+impl<'a> From<GeoFilter> for Cow<'a, str> {
+    fn from(filter: GeoFilter) -> Cow<'a, str> {
+        Cow::Owned(filter.to_string())
+    }
+}
+
+/// A [filter](struct.Query.html#structfield.filter) applied to the documents of a search.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Filter<'a> {
+    /// A filter expression, like `genres = horror AND year > 2000`.
+    Expression(Cow<'a, str>),
+    /// Filter expressions organized as an AND of ORs.
+    ///
+    /// `[["genres = horror", "genres = comedy"], ["year > 2000"]]` is equivalent to `(genres = horror OR genres = comedy) AND year > 2000`.
+    Array(Vec<Vec<&'a str>>),
+}
+
+impl<'a> Filter<'a> {
+    /// The expression of the filter, if it is not an [array](#variant.Array).
+    pub fn as_expression(&self) -> Option<&str> {
+        match self {
+            Filter::Expression(expression) => Some(expression),
+            Filter::Array(_) => None,
+        }
+    }
+
+    /// The fields the filter depends on, without duplicates, in order of appearance.
+    ///
+    /// The geographic filters reference the `_geo` field. Quoted field names are returned without their quotes.
+    /// The expressions are not validated: a malformed filter gives a best-effort result.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::search::Filter;
+    /// let filter = Filter::Expression("(genres = horror OR 'release year' > 2000) AND NOT genres IN [a, b] AND _geoRadius(45.4, 9.1, 2000)".into());
+    /// assert_eq!(filter.referenced_fields(), ["genres", "release year", "_geo"]);
+    /// ```
+    pub fn referenced_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        match self {
+            Filter::Expression(expression) => collect_filter_fields(expression, &mut fields),
+            Filter::Array(expressions) => {
+                for expression in expressions.iter().flatten() {
+                    collect_filter_fields(expression, &mut fields);
+                }
+            }
+        }
+        fields
+    }
+}
+
+/// A token of a filter expression, with whether it was quoted.
+fn tokenize_filter(expression: &str) -> Vec<(String, bool)> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '(' | ')' | '[' | ']' | ',' => tokens.push((c.to_string(), false)),
+            '"' | '\'' => {
+                let mut token = String::new();
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => token.extend(chars.next()),
+                        next if next == c => break,
+                        next => token.push(next),
+                    }
+                }
+                tokens.push((token, true));
+            }
+            '=' | '!' | '<' | '>' => {
+                let mut token = c.to_string();
+                while let Some(next) = chars.next_if(|next| matches!(next, '=' | '!' | '<' | '>')) {
+                    token.push(next);
+                }
+                tokens.push((token, false));
+            }
+            c => {
+                let mut token = c.to_string();
+                while let Some(next) = chars.next_if(|next| !next.is_whitespace() && !"()[],\"'=!<>".contains(*next)) {
+                    token.push(next);
+                }
+                tokens.push((token, false));
+            }
+        }
+    }
+    tokens
+}
+
+fn is_symbol((token, quoted): &(String, bool), symbol: &str) -> bool {
+    !quoted && token == symbol
+}
+
+/// Add the fields referenced by a filter expression to `fields`, skipping the ones already there.
+fn collect_filter_fields(expression: &str, fields: &mut Vec<String>) {
+    let tokens = tokenize_filter(expression);
+    let mut expect_field = true;
+    let mut i = 0;
+    while i < tokens.len() {
+        let (token, quoted) = &tokens[i];
+        let keyword = |keyword: &str| !quoted && token.eq_ignore_ascii_case(keyword);
+        i += 1;
+        if keyword("(") || keyword(")") {
+            continue;
+        }
+        if keyword("AND") || keyword("OR") {
+            expect_field = true;
+            continue;
+        }
+        if !expect_field {
+            // Skip the lists of values, which may contain anything
+            if keyword("[") {
+                while i < tokens.len() && !is_symbol(&tokens[i], "]") {
+                    i += 1;
+                }
+            }
+            continue;
+        }
+        if keyword("NOT") {
+            continue;
+        }
+        let field = if !quoted && token.starts_with("_geo") && tokens.get(i).map(|(next, _)| next.as_str()) == Some("(") {
+            // Skip the arguments of the geographic filter
+            while i < tokens.len() && !is_symbol(&tokens[i], ")") {
+                i += 1;
+            }
+            i += 1;
+            "_geo"
+        } else {
+            token.as_str()
+        };
+        if !fields.iter().any(|known| known == field) {
+            fields.push(field.to_string());
+        }
+        expect_field = false;
+    }
+}
+
+/// Parameters of a [hybrid search](struct.Query.html#structfield.hybrid), mixing keyword and semantic search.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HybridSearch<'a> {
+    /// The name of the embedder to use, as configured in the settings of the index.
+    pub embedder: &'a str,
+    /// The proportion of semantic search in the results, between `0.0` (keyword search only) and `1.0` (semantic search only).
+    pub semantic_ratio: f32,
+}
 
 /// A struct representing a query.
 /// You can add search parameters using the builder syntax.
@@ -120,7 +798,7 @@ type AttributeToCrop<'a> = (&'a str, Option<usize>);
 ///     .with_limit(21)
 ///     .build(); // you can also execute() instead of build()
 /// ```
-#[derive(Debug, Serialize, Clone)]
+#[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Query<'a> {
     #[serde(skip_serializing)]
@@ -128,7 +806,7 @@ pub struct Query<'a> {
     /// The text that will be searched for among the documents.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "q")]
-    pub query: Option<&'a str>,
+    pub query: Option<Cow<'a, str>>,
     /// The number of documents to skip.
     /// If the value of the parameter `offset` is `n`, the `n` first documents (ordered by relevance) will not be returned.
     /// This is helpful for pagination.
@@ -144,10 +822,24 @@ pub struct Query<'a> {
     /// Default: `20`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    /// The page to return, starting from `1`.
+    /// Setting it (or [hits_per_page](#structfield.hits_per_page)) enables the exhaustive pagination mode, in which MeiliSearch counts all the matching documents:
+    /// see [SearchResults::total_hits](struct.SearchResults.html#structfield.total_hits).
+    /// It should not be combined with [offset](#structfield.offset) and [limit](#structfield.limit).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// The number of documents in each page.
+    /// See [page](#structfield.page).
+    ///
+    /// Default: `20`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_per_page: Option<usize>,
     /// Filter applied to documents.
     /// Read the [dedicated guide](https://docs.meilisearch.com/reference/features/filtering.html) to learn the syntax.
+    ///
+    /// Set it with [with_filter](#method.with_filter) or [with_array_filter](#method.with_array_filter).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub filter: Option<&'a str>,
+    pub filter: Option<Filter<'a>>,
     /// Facets for which to retrieve the matching count.
     ///
     /// Can be set to a [wildcard value](enum.Selectors.html#variant.All) that will select all existing attributes.
@@ -155,9 +847,17 @@ pub struct Query<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(serialize_with = "serialize_with_wildcard")]
     pub facets_distribution: Option<Selectors<&'a [&'a str]>>,
+    /// Facets for which to retrieve the matching count, and the [lowest and highest values](struct.SearchResults.html#structfield.facet_stats) if they are numeric.
+    ///
+    /// Same as [facets_distribution](#structfield.facets_distribution), for MeiliSearch v1 and newer which renamed the parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_with_wildcard")]
+    pub facets: Option<Selectors<&'a [&'a str]>>,
     /// Attributes to sort.
+    ///
+    /// See [Sort](struct.Sort.html) to build them.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sort: Option<&'a [&'a str]>,
+    pub sort: Option<Vec<Sort<'a>>>,
     /// Attributes to display in the returned documents.
     ///
     /// Can be set to a [wildcard value](enum.Selectors.html#variant.All) that will select all existing attributes.
@@ -166,11 +866,11 @@ pub struct Query<'a> {
     #[serde(serialize_with = "serialize_with_wildcard")]
     pub attributes_to_retrieve: Option<Selectors<&'a [&'a str]>>,
     /// Attributes whose values have to be cropped.
-    /// Attributes are composed by the attribute name and an optional `usize` that overwrites the `crop_length` parameter.
+    /// Attributes are composed by the attribute name and an optional `usize` that overwrites the `crop_length` parameter, see [AttributeToCrop](struct.AttributeToCrop.html).
     ///
     /// Can be set to a [wildcard value](enum.Selectors.html#variant.All) that will select all existing attributes.
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(serialize_with = "serialize_attributes_to_crop_with_wildcard")]
+    #[serde(serialize_with = "serialize_with_wildcard")]
     pub attributes_to_crop: Option<Selectors<&'a [AttributeToCrop<'a>]>>,
     /// Number of characters to keep on each side of the start of the matching word.
     /// See [attributes_to_crop](#structfield.attributes_to_crop).
@@ -178,17 +878,135 @@ pub struct Query<'a> {
     /// Default: `200`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub crop_length: Option<usize>,
+    /// The string marking the boundaries of a cropped value. An empty string disables the marker.
+    /// See [attributes_to_crop](#structfield.attributes_to_crop).
+    ///
+    /// Default: `"…"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crop_marker: Option<&'a str>,
     /// Attributes whose values will contain **highlighted matching terms**.
+    /// The highlighted values are in the [formatted result](struct.SearchResult.html#structfield.formatted_result) of each hit.
     ///
-    /// Can be set to a [wildcard value](enum.Selectors.html#variant.All) that will select all existing attributes.
+    /// Can be set to a [wildcard value](enum.Selectors.html#variant.All) that will select all existing attributes
+    /// (a `"*"` in the list of attributes has the same effect).
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(serialize_with = "serialize_with_wildcard")]
     pub attributes_to_highlight: Option<Selectors<&'a [&'a str]>>,
-    /// Defines whether an object that contains information about the matches should be returned or not.
+    /// The string inserted before the highlighted terms.
+    /// See [attributes_to_highlight](#structfield.attributes_to_highlight).
     ///
-    /// Default: `false`
+    /// Default: `"<em>"`
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub matches: Option<bool>,
+    pub highlight_pre_tag: Option<&'a str>,
+    /// The string inserted after the highlighted terms.
+    /// See [attributes_to_highlight](#structfield.attributes_to_highlight).
+    ///
+    /// Default: `"</em>"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_post_tag: Option<&'a str>,
+    /// Defines whether an object that contains information about the matches should be returned or not.
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<bool>,
+    /// Defines whether the position of the matches should be returned or not.
+    /// See [matches_position](struct.SearchResult.html#structfield.matches_position).
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_matches_position: Option<bool>,
+    /// Defines whether the ranking score of each document should be returned or not.
+    /// See [ranking_score](struct.SearchResult.html#structfield.ranking_score).
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_ranking_score: Option<bool>,
+    /// Defines whether the contribution of each ranking rule to the ranking score should be returned or not.
+    /// See [ranking_score_details](struct.SearchResult.html#structfield.ranking_score_details).
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_ranking_score_details: Option<bool>,
+    /// Excludes the documents whose [ranking score](struct.SearchResult.html#structfield.ranking_score) is lower than this value.
+    /// Must be between `0.0` and `1.0`, otherwise [execute](#method.execute) returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) without sending the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_score_threshold: Option<f64>,
+    /// Mix keyword search with semantic search. Requires an embedder to be configured on the index.
+    /// The semantic ratio must be between `0.0` and `1.0`, otherwise [execute](#method.execute) returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) without sending the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<HybridSearch<'a>>,
+    /// A query vector computed by yourself, searched among the vectors of the documents.
+    /// Combine it with a [hybrid search](#structfield.hybrid) to select the embedder (a semantic ratio of `1.0` performs a pure vector search).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector: Option<Cow<'a, [f32]>>,
+    /// Defines whether the vectors of the documents should be returned or not.
+    /// See [vectors](struct.SearchResult.html#structfield.vectors).
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retrieve_vectors: Option<bool>,
+    /// The languages of the query, as [ISO 639-3](https://en.wikipedia.org/wiki/ISO_639-3) codes (`"jpn"`, `"cmn"`...).
+    /// Helps MeiliSearch to tokenize queries in multilingual indexes. Default: detected automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locales: Option<Vec<String>>,
+    /// Return at most one document for each value of this attribute, overriding the distinct attribute of the index for this search.
+    /// The attribute must be in the [filterable attributes](../settings/struct.Settings.html#structfield.filterable_attributes) of the index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct: Option<&'a str>,
+    /// Options applied to this query when it is part of a [federated multi search](struct.MultiSearchQuery.html#method.with_federation).
+    /// Ignored otherwise.
+    #[serde(skip_serializing)]
+    pub federation_options: Option<QueryFederationOptions>,
+    /// An API key used instead of the key of the client to send this query.
+    /// Ignored in [multi searches](struct.MultiSearchQuery.html). See also [Index::with_key](../indexes/struct.Index.html#method.with_key).
+    #[serde(skip_serializing)]
+    pub api_key_override: Option<&'a str>,
+    /// The maximum duration of the HTTP round-trip of this query, after which it fails with [Error::RequestTimeout](../errors/enum.Error.html#variant.RequestTimeout).
+    /// Unlike a server-side cutoff, it also bounds the time spent connecting to a slow or unreachable server.
+    /// Ignored in [multi searches](struct.MultiSearchQuery.html).
+    #[serde(skip_serializing)]
+    pub request_timeout: Option<Duration>,
+}
+
+/// The value shown instead of an [API key override](struct.Query.html#structfield.api_key_override), which must never be logged.
+const REDACTED: &str = "<redacted>";
+
+// The queries are traced with their Debug representation: the overriding API key must not appear in it, nor the key of the index
+impl std::fmt::Debug for Query<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Query")
+            .field("index", &self.index.uid)
+            .field("query", &self.query)
+            .field("offset", &self.offset)
+            .field("limit", &self.limit)
+            .field("page", &self.page)
+            .field("hits_per_page", &self.hits_per_page)
+            .field("filter", &self.filter)
+            .field("facets_distribution", &self.facets_distribution)
+            .field("facets", &self.facets)
+            .field("sort", &self.sort)
+            .field("attributes_to_retrieve", &self.attributes_to_retrieve)
+            .field("attributes_to_crop", &self.attributes_to_crop)
+            .field("crop_length", &self.crop_length)
+            .field("crop_marker", &self.crop_marker)
+            .field("attributes_to_highlight", &self.attributes_to_highlight)
+            .field("highlight_pre_tag", &self.highlight_pre_tag)
+            .field("highlight_post_tag", &self.highlight_post_tag)
+            .field("matches", &self.matches)
+            .field("show_matches_position", &self.show_matches_position)
+            .field("show_ranking_score", &self.show_ranking_score)
+            .field("show_ranking_score_details", &self.show_ranking_score_details)
+            .field("ranking_score_threshold", &self.ranking_score_threshold)
+            .field("hybrid", &self.hybrid)
+            .field("vector", &self.vector)
+            .field("retrieve_vectors", &self.retrieve_vectors)
+            .field("locales", &self.locales)
+            .field("distinct", &self.distinct)
+            .field("federation_options", &self.federation_options)
+            .field("api_key_override", &self.api_key_override.map(|_| REDACTED))
+            .field("request_timeout", &self.request_timeout)
+            .finish()
+    }
 }
 
 #[allow(missing_docs)]
@@ -199,18 +1017,42 @@ impl<'a> Query<'a> {
             query: None,
             offset: None,
             limit: None,
+            page: None,
+            hits_per_page: None,
             filter: None,
             sort: None,
             facets_distribution: None,
+            facets: None,
             attributes_to_retrieve: None,
             attributes_to_crop: None,
             crop_length: None,
+            crop_marker: None,
             attributes_to_highlight: None,
+            highlight_pre_tag: None,
+            highlight_post_tag: None,
             matches: None,
+            show_matches_position: None,
+            show_ranking_score: None,
+            show_ranking_score_details: None,
+            ranking_score_threshold: None,
+            hybrid: None,
+            vector: None,
+            retrieve_vectors: None,
+            locales: None,
+            distinct: None,
+            federation_options: None,
+            api_key_override: None,
+            request_timeout: None,
         }
     }
-    pub fn with_query<'b>(&'b mut self, query: &'a str) -> &'b mut Query<'a> {
-        self.query = Some(query);
+    pub fn with_query<'b>(&'b mut self, query: impl Into<Cow<'a, str>>) -> &'b mut Query<'a> {
+        self.query = Some(query.into());
+        self
+    }
+    /// Search for documents containing the words of `phrase` next to each other and in the same order.
+    /// The phrase is put between double quotes and [escaped](fn.escape_query.html), so it may contain quotes.
+    pub fn with_phrase<'b>(&'b mut self, phrase: &str) -> &'b mut Query<'a> {
+        self.query = Some(Cow::Owned(format!("\"{}\"", escape_query(phrase))));
         self
     }
     pub fn with_offset<'b>(&'b mut self, offset: usize) -> &'b mut Query<'a> {
@@ -221,8 +1063,33 @@ impl<'a> Query<'a> {
         self.limit = Some(limit);
         self
     }
-    pub fn with_filter<'b>(&'b mut self, filter: &'a str) -> &'b mut Query<'a> {
-        self.filter = Some(filter);
+    pub fn with_page<'b>(&'b mut self, page: usize) -> &'b mut Query<'a> {
+        self.page = Some(page);
+        self
+    }
+    pub fn with_hits_per_page<'b>(&'b mut self, hits_per_page: usize) -> &'b mut Query<'a> {
+        self.hits_per_page = Some(hits_per_page);
+        self
+    }
+    pub fn with_filter<'b>(&'b mut self, filter: impl Into<Cow<'a, str>>) -> &'b mut Query<'a> {
+        self.filter = Some(Filter::Expression(filter.into()));
+        self
+    }
+    /// Set the filter as an AND of ORs, replacing any filter previously set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::Client, search::*};
+    /// # let client = Client::new("http://localhost:7700", "masterKey");
+    /// # let index = client.assume_index("movies");
+    /// // (genres = horror OR genres = comedy) AND year > 2000
+    /// let query = index.search()
+    ///     .with_array_filter(vec![vec!["genres = horror", "genres = comedy"], vec!["year > 2000"]])
+    ///     .build();
+    /// ```
+    pub fn with_array_filter<'b>(&'b mut self, filter: Vec<Vec<&'a str>>) -> &'b mut Query<'a> {
+        self.filter = Some(Filter::Array(filter));
         self
     }
     pub fn with_facets_distribution<'b>(
@@ -232,13 +1099,26 @@ impl<'a> Query<'a> {
         self.facets_distribution = Some(facets_distribution);
         self
     }
-    pub fn with_sort<'b>(
+    /// Request the distribution of these facets from MeiliSearch v1 or newer, see [facets](#structfield.facets).
+    pub fn with_facets<'b>(&'b mut self, facets: Selectors<&'a [&'a str]>) -> &'b mut Query<'a> {
+        self.facets = Some(facets);
+        self
+    }
+    /// Set the sort criteria, either as raw strings (`&["price:asc"]`) or as [Sort](struct.Sort.html) values.
+    pub fn with_sort<'b, S: Clone + Into<Sort<'a>>>(
         &'b mut self,
-        sort: &'a [&'a str],
+        sort: &[S],
     ) -> &'b mut Query<'a> {
-        self.sort = Some(sort);
+        self.sort = Some(sort.iter().cloned().map(Into::into).collect());
         self
     }
+    /// Sort the results by their distance to a point, replacing any other sort criterion.
+    ///
+    /// The distance of each result is then available in [geo_distance](struct.SearchResult.html#structfield.geo_distance).
+    /// To combine it with other criteria, use [with_sort](#method.with_sort) and [Sort::geo_point](struct.Sort.html#method.geo_point).
+    pub fn with_sort_by_distance<'b>(&'b mut self, lat: f64, lng: f64, direction: Direction) -> &'b mut Query<'a> {
+        self.with_sort(&[Sort::geo_point(lat, lng, direction)])
+    }
     pub fn with_attributes_to_retrieve<'b>(
         &'b mut self,
         attributes_to_retrieve: Selectors<&'a [&'a str]>,
@@ -248,7 +1128,7 @@ impl<'a> Query<'a> {
     }
     pub fn with_attributes_to_crop<'b>(
         &'b mut self,
-        attributes_to_crop: Selectors<&'a [(&'a str, Option<usize>)]>,
+        attributes_to_crop: Selectors<&'a [AttributeToCrop<'a>]>,
     ) -> &'b mut Query<'a> {
         self.attributes_to_crop = Some(attributes_to_crop);
         self
@@ -260,165 +1140,1105 @@ impl<'a> Query<'a> {
         self.attributes_to_highlight = Some(attributes_to_highlight);
         self
     }
+    pub fn with_highlight_pre_tag<'b>(&'b mut self, highlight_pre_tag: &'a str) -> &'b mut Query<'a> {
+        self.highlight_pre_tag = Some(highlight_pre_tag);
+        self
+    }
+    pub fn with_highlight_post_tag<'b>(&'b mut self, highlight_post_tag: &'a str) -> &'b mut Query<'a> {
+        self.highlight_post_tag = Some(highlight_post_tag);
+        self
+    }
     pub fn with_crop_length<'b>(&'b mut self, crop_length: usize) -> &'b mut Query<'a> {
         self.crop_length = Some(crop_length);
         self
     }
+    pub fn with_crop_marker<'b>(&'b mut self, crop_marker: &'a str) -> &'b mut Query<'a> {
+        self.crop_marker = Some(crop_marker);
+        self
+    }
     pub fn with_matches<'b>(&'b mut self, matches: bool) -> &'b mut Query<'a> {
         self.matches = Some(matches);
         self
     }
+    pub fn with_show_matches_position<'b>(&'b mut self, show_matches_position: bool) -> &'b mut Query<'a> {
+        self.show_matches_position = Some(show_matches_position);
+        self
+    }
+    pub fn with_show_ranking_score<'b>(&'b mut self, show_ranking_score: bool) -> &'b mut Query<'a> {
+        self.show_ranking_score = Some(show_ranking_score);
+        self
+    }
+    pub fn with_show_ranking_score_details<'b>(&'b mut self, show_ranking_score_details: bool) -> &'b mut Query<'a> {
+        self.show_ranking_score_details = Some(show_ranking_score_details);
+        self
+    }
+    pub fn with_ranking_score_threshold<'b>(&'b mut self, ranking_score_threshold: f64) -> &'b mut Query<'a> {
+        self.ranking_score_threshold = Some(ranking_score_threshold);
+        self
+    }
+    pub fn with_hybrid<'b>(&'b mut self, embedder: &'a str, semantic_ratio: f32) -> &'b mut Query<'a> {
+        self.hybrid = Some(HybridSearch { embedder, semantic_ratio });
+        self
+    }
+    pub fn with_vector<'b>(&'b mut self, vector: impl Into<Cow<'a, [f32]>>) -> &'b mut Query<'a> {
+        self.vector = Some(vector.into());
+        self
+    }
+    pub fn with_retrieve_vectors<'b>(&'b mut self, retrieve_vectors: bool) -> &'b mut Query<'a> {
+        self.retrieve_vectors = Some(retrieve_vectors);
+        self
+    }
+    /// Set the [languages of the query](#structfield.locales). An empty list unsets them.
+    pub fn with_locales<'b>(&'b mut self, locales: impl IntoIterator<Item = impl Into<String>>) -> &'b mut Query<'a> {
+        let locales: Vec<String> = locales.into_iter().map(Into::into).collect();
+        self.locales = if locales.is_empty() { None } else { Some(locales) };
+        self
+    }
+    pub fn with_distinct<'b>(&'b mut self, distinct: &'a str) -> &'b mut Query<'a> {
+        self.distinct = Some(distinct);
+        self
+    }
+    pub fn with_federation_options<'b>(&'b mut self, federation_options: QueryFederationOptions) -> &'b mut Query<'a> {
+        self.federation_options = Some(federation_options);
+        self
+    }
+    pub fn with_api_key_override<'b>(&'b mut self, api_key: &'a str) -> &'b mut Query<'a> {
+        self.api_key_override = Some(api_key);
+        self
+    }
+    pub fn with_request_timeout<'b>(&'b mut self, request_timeout: Duration) -> &'b mut Query<'a> {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
     pub fn build(&mut self) -> Query<'a> {
         self.clone()
     }
 
+    /// Generate a `curl` command sending this query, to reproduce an issue outside of Rust.
+    ///
+    /// The API key is **not** included: the command reads it from the `MEILI_KEY` environment variable instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::Client, search::Query};
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let index = client.assume_index("movies");
+    /// let command = index.search().with_query("space").to_curl();
+    ///
+    /// assert_eq!(command, r#"curl -X POST 'http://localhost:7700/indexes/movies/search' -H 'Content-Type: application/json' -H "X-Meili-API-Key: $MEILI_KEY" --data-binary '{"q":"space"}'"#);
+    /// ```
+    pub fn to_curl(&self) -> String {
+        // The body is single-quoted, so single quotes have to be closed, escaped and reopened
+        let body = serde_json::to_string(self).unwrap_or_default().replace('\'', "'\\''");
+        format!(
+            "curl -X POST '{}/indexes/{}/search' -H 'Content-Type: application/json' -H \"X-Meili-API-Key: $MEILI_KEY\" --data-binary '{}'",
+            self.index.host, self.index.uid, body
+        )
+    }
+
     /// Execute the query and fetch the results.
     pub async fn execute<T: 'static + DeserializeOwned>(
         &'a self,
     ) -> Result<SearchResults<T>, Error> {
         self.index.execute_query::<T>(self).await
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{client::*, document, search::*};
-    use serde::{Deserialize, Serialize};
-    use std::thread::sleep;
-    use std::time::Duration;
-    use futures_await_test::async_test;
 
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct Document {
-        id: usize,
-        value: String,
-        kind: String,
+    /// Same as [execute](#method.execute), also returning the [measures](../client/struct.RequestMetrics.html) of the request,
+    /// to feed client-side metrics for example.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, search::*};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let (results, metrics) = movies.search()
+    ///     .with_query("space")
+    ///     .execute_with_metadata::<serde_json::Value>()
+    ///     .await
+    ///     .unwrap();
+    /// println!("{} hits in {:?}, {} bytes received", results.hits.len(), metrics.elapsed, metrics.response_bytes);
+    /// # });
+    /// ```
+    pub async fn execute_with_metadata<T: 'static + DeserializeOwned>(&'a self) -> Result<(SearchResults<T>, RequestMetrics), Error> {
+        self.index.send_query_with_metrics::<SearchResults<T>>(self).await
     }
 
-    impl document::Document for Document {
-        type UIDType = usize;
+    /// Execute the query with the given HTTP method and fetch the results.
+    ///
+    /// With [SearchMethod::Get](enum.SearchMethod.html#variant.Get), the parameters are sent in the query string, so that the request can be cached by HTTP caches.
+    /// Some parameters can't be sent that way (see [SearchMethod::Get](enum.SearchMethod.html#variant.Get)):
+    /// they make this method return [Error::UnsupportedGetParameter](../errors/enum.Error.html#variant.UnsupportedGetParameter) without sending anything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, search::*};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let results = movies.search()
+    ///     .with_query("space")
+    ///     .with_filter("year > 2000")
+    ///     .execute_with_method::<serde_json::Value>(SearchMethod::Get)
+    ///     .await
+    ///     .unwrap();
+    /// # });
+    /// ```
+    pub async fn execute_with_method<T: 'static + DeserializeOwned>(&'a self, method: SearchMethod) -> Result<SearchResults<T>, Error> {
+        match method {
+            SearchMethod::Post => self.execute().await,
+            SearchMethod::Get => self.index.execute_query_get(self).await,
+        }
+    }
 
-        fn get_uid(&self) -> &Self::UIDType {
-            &self.id
+    /// Paginate through the results sorted by `key`, `page_size` results at a time, with [StablePages::next_page](struct.StablePages.html#method.next_page).
+    ///
+    /// Unlike paginating with [offset](#structfield.offset), documents added or deleted meanwhile can't make a result appear twice or be skipped:
+    /// each page only selects the documents whose key is greater than the key of the last result of the previous page.
+    /// `key` must be unique, typically the primary key, and [filterable and sortable](../settings/struct.IndexCapabilities.html).
+    /// This is checked before searching, and [Error::InvalidPaginationKey](../errors/enum.Error.html#variant.InvalidPaginationKey) is returned otherwise.
+    ///
+    /// The filter of the query is kept, but its sort, offset, limit and page are replaced.
+    /// Number keys are compared numerically. String keys are compared lexicographically, which requires a version of MeiliSearch able to compare strings in filters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # use serde_json::Value;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_stable_pages").await.unwrap();
+    /// movies.set_filterable_attributes_and_wait(["id"], None, None).await.unwrap();
+    /// movies.set_sortable_attributes_and_wait(["id"], None, None).await.unwrap();
+    ///
+    /// let query = movies.search().with_filter("year > 2000").build();
+    /// let mut pages = query.execute_stable_pages::<Value>("id", 100).await.unwrap();
+    /// while let Some(page) = pages.next_page().await.unwrap() {
+    ///     // each movie is seen at most once, even if movies are added meanwhile
+    /// }
+    /// # client.delete_index("movies_stable_pages").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn execute_stable_pages<T: 'static + DeserializeOwned>(&self, key: &str, page_size: usize) -> Result<StablePages<'a, T>, Error> {
+        if page_size == 0 {
+            return Err(Error::InvalidRequest);
+        }
+        let capabilities = self.index.capabilities().await?;
+        if !capabilities.can_filter_on(key) || !capabilities.can_sort_on(key) {
+            return Err(Error::InvalidPaginationKey { key: key.to_string() });
         }
+
+        let filter = self.filter.as_ref().map(|filter| match filter {
+            Filter::Expression(expression) => expression.to_string(),
+            Filter::Array(filters) => filters.iter().map(|filters| format!("({})", filters.join(" OR "))).collect::<Vec<String>>().join(" AND "),
+        });
+        let mut query = self.clone();
+        query.offset = None;
+        query.page = None;
+        query.hits_per_page = None;
+        query.with_limit(page_size).with_sort(&[Sort::ascending(key)]);
+        Ok(StablePages {
+            query,
+            key: key.to_string(),
+            filter,
+            page_size,
+            last_key: None,
+            done: false,
+            result_type: PhantomData,
+        })
     }
 
-    #[allow(unused_must_use)]
-    async fn setup_test_index<'a>(client: &'a Client, name: &'a str) -> Index {
-        // try to delete
-        client.delete_index(name).await;
+    /// Convert the query to the parameters of a GET search request.
+    ///
+    /// The parameters are taken from the JSON body: lists are joined with commas and the hybrid search is split into two parameters.
+    pub(crate) fn to_get_parameters(&self) -> Result<Vec<(String, String)>, Error> {
+        let unsupported = |parameter: &str| Error::UnsupportedGetParameter { parameter: parameter.to_string() };
 
-        let index = client.create_index(name, None).await.unwrap();
-        index.add_documents(&[
-            Document { id: 0, kind: "text".into(), value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.".to_string() },
-            Document { id: 1, kind: "text".into(), value: "dolor sit amet, consectetur adipiscing elit".to_string() },
-            Document { id: 2, kind: "title".into(), value: "The Social Network".to_string() },
-            Document { id: 3, kind: "title".into(), value: "Harry Potter and the Sorcerer's Stone".to_string() },
-            Document { id: 4, kind: "title".into(), value: "Harry Potter and the Chamber of Secrets".to_string() },
-            Document { id: 5, kind: "title".into(), value: "Harry Potter and the Prisoner of Azkaban".to_string() },
-            Document { id: 6, kind: "title".into(), value: "Harry Potter and the Goblet of Fire".to_string() },
-            Document { id: 7, kind: "title".into(), value: "Harry Potter and the Order of the Phoenix".to_string() },
-            Document { id: 8, kind: "title".into(), value: "Harry Potter and the Half-Blood Prince".to_string() },
-            Document { id: 9, kind: "title".into(), value: "Harry Potter and the Deathly Hallows".to_string() },
-        ], None).await.unwrap();
-        index.set_filterable_attributes(["kind", "value"]).await.unwrap();
-        index.set_sortable_attributes(["title"]).await.unwrap();
-        sleep(Duration::from_secs(1));
-        index
+        let body = match serde_json::to_value(self).map_err(Error::ParseError)? {
+            serde_json::Value::Object(body) => body,
+            _ => return Ok(Vec::new()),
+        };
+        let mut parameters = Vec::new();
+        for (name, value) in body {
+            match (name.as_str(), value) {
+                (_, serde_json::Value::Null) => {}
+                ("vector", _) => return Err(unsupported("vector")),
+                ("hybrid", serde_json::Value::Object(hybrid)) => {
+                    for (field, parameter) in [("embedder", "hybridEmbedder"), ("semanticRatio", "hybridSemanticRatio")] {
+                        if let Some(value) = hybrid.get(field) {
+                            parameters.push((parameter.to_string(), get_parameter_value(value).ok_or_else(|| unsupported("hybrid"))?));
+                        }
+                    }
+                }
+                (_, serde_json::Value::Array(values)) => {
+                    let values = values
+                        .iter()
+                        .map(|value| get_parameter_value(value).filter(|value| !value.contains(',')))
+                        .collect::<Option<Vec<String>>>()
+                        .ok_or_else(|| unsupported(&name))?;
+                    parameters.push((name, values.join(",")));
+                }
+                (_, value) => {
+                    let value = get_parameter_value(&value).ok_or_else(|| unsupported(&name))?;
+                    parameters.push((name, value));
+                }
+            }
+        }
+        Ok(parameters)
     }
 
-    #[async_test]
-    async fn test_query_string() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_string").await;
+    /// Execute the query and fetch the results as raw JSON, without deserializing the documents.
+    /// See [Index::search_raw](../indexes/struct.Index.html#method.search_raw).
+    pub async fn execute_raw(&'a self) -> Result<RawSearchResults, Error> {
+        self.index.search_raw(self).await
+    }
 
-        let results: SearchResults<Document> =
-            index.search().with_query("dolor").execute().await.unwrap();
-        assert_eq!(results.hits.len(), 2);
+    /// Get an [OwnedQuery](struct.OwnedQuery.html) with the same parameters, which doesn't borrow the index or the parameters anymore.
+    ///
+    /// Returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) if MeiliSearch would reject the query.
+    pub fn to_owned_query(&self) -> Result<OwnedQuery, Error> {
+        self.validate()?;
+        let body = match serde_json::to_value(self).map_err(Error::ParseError)? {
+            serde_json::Value::Object(body) => body,
+            _ => serde_json::Map::new(),
+        };
+        Ok(OwnedQuery {
+            body,
+            api_key_override: self.api_key_override.map(String::from),
+            request_timeout: self.request_timeout,
+        })
+    }
 
-        client.delete_index("test_query_string").await.unwrap();
+    /// Check the parameters that MeiliSearch would reject, without sending anything.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if let Some(threshold) = self.ranking_score_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(Error::InvalidRequest);
+            }
+        }
+        if let Some(hybrid) = &self.hybrid {
+            if !(0.0..=1.0).contains(&hybrid.semantic_ratio) {
+                return Err(Error::InvalidRequest);
+            }
+        }
+        Ok(())
     }
+}
 
-    #[async_test]
-    async fn test_query_limit() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_limit").await;
+/// A search query owning its parameters, so that it can be stored and executed later, on any index.
+///
+/// It is built from a [Query](struct.Query.html) with [to_owned_query](struct.Query.html#method.to_owned_query),
+/// and keeps its parameters as the JSON body sent to MeiliSearch.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::{client::*, indexes::*, search::*};
+/// # use serde_json::Value;
+/// fn recent_movies(index: &Index) -> OwnedQuery {
+///     index.search().with_query("space").with_filter("year > 2000").to_owned_query().unwrap()
+/// }
+///
+/// # futures::executor::block_on(async move {
+/// let client = Client::new("http://localhost:7700", "masterKey");
+/// let query = recent_movies(&client.assume_index("movies"));
+///
+/// let movies = client.get_or_create("movies_owned_query").await.unwrap();
+/// let results = query.execute::<Value>(&movies).await.unwrap();
+/// # client.delete_index("movies_owned_query").await.unwrap();
+/// # });
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct OwnedQuery {
+    /// The parameters of the query, as sent to MeiliSearch
+    pub body: serde_json::Map<String, serde_json::Value>,
+    /// See [Query::api_key_override](struct.Query.html#structfield.api_key_override)
+    pub api_key_override: Option<String>,
+    /// See [Query::request_timeout](struct.Query.html#structfield.request_timeout)
+    pub request_timeout: Option<Duration>,
+}
 
-        let results: SearchResults<Document> =
-            index.search().with_limit(5).execute().await.unwrap();
-        assert_eq!(results.hits.len(), 5);
+impl std::fmt::Debug for OwnedQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OwnedQuery")
+            .field("body", &self.body)
+            .field("api_key_override", &self.api_key_override.as_ref().map(|_| REDACTED))
+            .field("request_timeout", &self.request_timeout)
+            .finish()
+    }
+}
 
-        client.delete_index("test_query_limit").await.unwrap();
+impl OwnedQuery {
+    /// Execute the query on `index` and fetch the results.
+    /// See [Index::execute_owned_query](../indexes/struct.Index.html#method.execute_owned_query).
+    pub async fn execute<T: 'static + DeserializeOwned>(&self, index: &Index) -> Result<SearchResults<T>, Error> {
+        index.execute_owned_query::<T>(self).await
     }
+}
 
-    #[async_test]
-    async fn test_query_offset() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_offset").await;
+/// A search query with `{{name}}` placeholders, [rendered](#method.render) into an [OwnedQuery](struct.OwnedQuery.html) with runtime values.
+///
+/// Like an owned query, a template is the JSON body of the search, so it can be deserialized from any format supported by serde.
+/// The placeholders are replaced in `q`, `filter` and the entries of `sort`. In a filter, a placeholder stands for a whole value,
+/// which is [quoted](fn.escape_value.html) when rendered: don't put quotes around it.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::search::*;
+/// # use std::collections::HashMap;
+/// let template: SearchTemplate = serde_json::from_str(r#"{
+///     "q": "{{terms}}",
+///     "filter": "genres = {{genre}} AND year > 2000",
+///     "sort": ["rating:desc"],
+///     "limit": 10
+/// }"#).unwrap();
+///
+/// let mut parameters = HashMap::new();
+/// parameters.insert(String::from("terms"), String::from("space"));
+/// parameters.insert(String::from("genre"), String::from("science fiction"));
+/// let query = template.render(&parameters).unwrap();
+/// assert_eq!(query.body["q"], "space");
+/// assert_eq!(query.body["filter"], r#"genres = "science fiction" AND year > 2000"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SearchTemplate {
+    /// The parameters of the query, with their placeholders
+    pub body: serde_json::Map<String, serde_json::Value>,
+}
 
-        let results: SearchResults<Document> =
-            index.search().with_offset(6).execute().await.unwrap();
-        assert_eq!(results.hits.len(), 4);
+impl SearchTemplate {
+    /// Replace the placeholders of the template with the values of `parameters`.
+    ///
+    /// Fails with [Error::MissingTemplateParameters](../errors/enum.Error.html#variant.MissingTemplateParameters)
+    /// listing all the placeholders without a value, from `q`, then `filter`, then `sort`.
+    pub fn render(&self, parameters: &HashMap<String, String>) -> Result<OwnedQuery, Error> {
+        let mut missing = Vec::new();
+        let mut body = self.body.clone();
+        // The keys of the body are sorted, so the parameters are walked in a fixed order instead
+        for key in ["q", "filter", "sort"] {
+            let value = match body.get_mut(key) {
+                Some(value) => value,
+                None => continue,
+            };
+            match (key, value) {
+                ("q", serde_json::Value::String(text)) => *text = render_placeholders(text, parameters, false, &mut missing),
+                ("filter", filter) => render_filter(filter, parameters, &mut missing),
+                ("sort", serde_json::Value::Array(entries)) => {
+                    for entry in entries {
+                        if let serde_json::Value::String(text) = entry {
+                            *text = render_placeholders(text, parameters, false, &mut missing);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(Error::MissingTemplateParameters { names: missing });
+        }
+        Ok(OwnedQuery { body, api_key_override: None, request_timeout: None })
+    }
+}
 
-        client.delete_index("test_query_offset").await.unwrap();
+/// Render the placeholders of a filter, which may be an expression or nested arrays of expressions.
+fn render_filter(filter: &mut serde_json::Value, parameters: &HashMap<String, String>, missing: &mut Vec<String>) {
+    match filter {
+        serde_json::Value::String(text) => *text = render_placeholders(text, parameters, true, missing),
+        serde_json::Value::Array(filters) => {
+            for filter in filters {
+                render_filter(filter, parameters, missing);
+            }
+        }
+        _ => (),
     }
+}
 
-    #[async_test]
-    async fn test_query_filter() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_filter").await;
+/// Replace the `{{name}}` placeholders of `text`, adding the names without a value to `missing`.
+/// The values are [quoted](escape_value) if `quote` is set.
+fn render_placeholders(text: &str, parameters: &HashMap<String, String>, quote: bool, missing: &mut Vec<String>) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start + 2..].find("}}") {
+            Some(length) => start + 2 + length,
+            None => break,
+        };
+        rendered.push_str(&rest[..start]);
+        let name = rest[start + 2..end].trim();
+        match parameters.get(name) {
+            Some(value) if quote => rendered.push_str(&escape_value(value)),
+            Some(value) => rendered.push_str(value),
+            None if !missing.iter().any(|known| known == name) => missing.push(name.to_string()),
+            None => (),
+        }
+        rest = &rest[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
 
-        let results: SearchResults<Document> = index
-            .search()
-            .with_filter("value = \"The Social Network\"")
-            .execute()
-            .await
-            .unwrap();
-        assert_eq!(results.hits.len(), 1);
+/// Pages of search results sorted by a unique key, returned by [Query::execute_stable_pages](struct.Query.html#method.execute_stable_pages).
+#[derive(Debug)]
+pub struct StablePages<'a, T> {
+    query: Query<'a>,
+    key: String,
+    filter: Option<String>,
+    page_size: usize,
+    /// The key of the last result, formatted as a filter value
+    last_key: Option<String>,
+    done: bool,
+    result_type: PhantomData<T>,
+}
 
-        let results: SearchResults<Document> = index
-            .search()
-            .with_filter("NOT value = \"The Social Network\"")
-            .execute()
-            .await
-            .unwrap();
-        assert_eq!(results.hits.len(), 9);
+impl<'a, T: 'static + DeserializeOwned> StablePages<'a, T> {
+    /// Fetch the next page of results, or `None` once all the results have been seen.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<SearchResult<T>>>, Error> {
+        if self.done {
+            return Ok(None);
+        }
+        let after = self.last_key.as_ref().map(|last_key| format!("{} > {}", self.key, last_key));
+        let filter = match (&self.filter, after) {
+            (Some(filter), Some(after)) => Some(format!("({}) AND {}", filter, after)),
+            (Some(filter), None) => Some(filter.clone()),
+            (None, after) => after,
+        };
+        self.query.filter = filter.map(|filter| Filter::Expression(Cow::Owned(filter)));
 
-        client.delete_index("test_query_filter").await.unwrap();
+        let hits = self.query.index.search_raw(&self.query).await?.hits;
+        self.done = hits.len() < self.page_size;
+        if let Some(last) = hits.last() {
+            let last_key = match last.get(&self.key) {
+                Some(serde_json::Value::Number(key)) => key.to_string(),
+                Some(serde_json::Value::String(key)) => escape_value(key),
+                _ => return Err(Error::InvalidPaginationKey { key: self.key.clone() }),
+            };
+            self.last_key = Some(last_key);
+        }
+        if hits.is_empty() {
+            return Ok(None);
+        }
+        hits.into_iter()
+            .map(|hit| serde_json::from_value(serde_json::Value::Object(hit)).map_err(Error::ParseError))
+            .collect::<Result<Vec<SearchResult<T>>, Error>>()
+            .map(Some)
     }
+}
 
-    #[async_test]
-    async fn test_query_facet_distribution() {
-        let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_facet_distribution").await;
+/// Options of a [federated multi search](struct.MultiSearchQuery.html#method.with_federation), applied to the merged list of results.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FederationOptions {
+    /// The maximum number of results in the merged list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// The number of results to skip in the merged list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// The facets to distribute, by index uid. The facets are returned separately for each index,
+    /// in [facets_by_index](struct.FederatedSearchResults.html#structfield.facets_by_index), unless [merge_facets](#structfield.merge_facets) is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets_by_index: Option<HashMap<String, Vec<String>>>,
+    /// Merge the facets of all the indexes into [facet_distribution](struct.FederatedSearchResults.html#structfield.facet_distribution) and [facet_stats](struct.FederatedSearchResults.html#structfield.facet_stats)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_facets: Option<MergeFacets>,
+}
 
-        let mut query = Query::new(&index);
-        query.with_facets_distribution(Selectors::All);
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(
-            results
-                .facets_distribution
-                .unwrap()
-                .get("kind")
-                .unwrap()
-                .get("title")
-                .unwrap(),
-            &8
-        );
+/// How the facets of a [federated multi search](struct.FederationOptions.html#structfield.merge_facets) are merged.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeFacets {
+    /// The maximum number of values returned for each facet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_values_per_facet: Option<usize>,
+}
 
-        let mut query = Query::new(&index);
-        query.with_facets_distribution(Selectors::Some(&["kind"]));
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(
-            results
-                .facets_distribution
-                .clone()
-                .unwrap()
-                .get("kind")
-                .unwrap()
-                .get("title")
-                .unwrap(),
-            &8
+/// Options of a single query in a [federated multi search](struct.MultiSearchQuery.html#method.with_federation).
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryFederationOptions {
+    /// The factor applied to the ranking scores of the results of this query before merging. Default: `1.0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f32>,
+}
+
+/// A query carrying the uid of its index, as expected by the `/multi-search` route.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedQuery<'q, 'b> {
+    index_uid: &'q str,
+    #[serde(flatten)]
+    query: &'q Query<'b>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation_options: Option<&'q QueryFederationOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct MultiSearchBody<'q, 'b> {
+    queries: Vec<IndexedQuery<'q, 'b>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation: Option<&'q FederationOptions>,
+}
+
+/// A batch of [queries](struct.Query.html), possibly on different indexes, sent in a single request.
+///
+/// Obtained with [Client::multi_search](../client/struct.Client.html#method.multi_search).
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::{client::Client, document::Document, search::*};
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// # #[derive(Serialize, Deserialize, Debug)]
+/// # struct Movie {
+/// #    id: usize,
+/// #    title: String,
+/// # }
+/// #
+/// # impl Document for Movie {
+/// #    type UIDType = usize;
+/// #    fn get_uid(&self) -> &Self::UIDType {
+/// #        &self.id
+/// #    }
+/// # }
+/// #
+/// # futures::executor::block_on(async move {
+/// let client = Client::new("http://localhost:7700", "masterKey");
+/// let movies = client.get_or_create("movies_multi_search").await.unwrap();
+/// # movies.add_documents(&[Movie { id: 1, title: String::from("Interstellar") }], None).await.unwrap().wait_for_pending_update(None, None).await.unwrap();
+///
+/// let response = client.multi_search()
+///     .with_search_query(movies.search().with_query("Interstellar").build())
+///     .with_search_query(movies.search().with_query("Apollo").build())
+///     .execute::<Movie>()
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(response.results.len(), 2);
+/// # client.delete_index("movies_multi_search").await.unwrap();
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSearchQuery<'a, 'b> {
+    client: &'a Client,
+    /// The queries, in the order their results will be returned
+    pub queries: Vec<Query<'b>>,
+    /// Merge the results of all the queries into a single list.
+    /// See [with_federation](#method.with_federation).
+    pub federation: Option<FederationOptions>,
+}
+
+impl<'a, 'b> MultiSearchQuery<'a, 'b> {
+    /// Create an empty batch of queries.
+    pub fn new(client: &'a Client) -> MultiSearchQuery<'a, 'b> {
+        MultiSearchQuery {
+            client,
+            queries: Vec::new(),
+            federation: None,
+        }
+    }
+
+    /// Add a query to the batch. Its results will be at the same position in the [response](struct.MultiSearchResponse.html).
+    pub fn with_search_query<'c>(&'c mut self, query: Query<'b>) -> &'c mut MultiSearchQuery<'a, 'b> {
+        self.queries.push(query);
+        self
+    }
+
+    /// Merge the results of all the queries into a single list, ranked by (weighted) relevancy.
+    /// Use [execute_federated](#method.execute_federated) to fetch the results.
+    ///
+    /// The weight of each query can be set with [Query::with_federation_options](struct.Query.html#method.with_federation_options).
+    pub fn with_federation<'c>(&'c mut self, federation: FederationOptions) -> &'c mut MultiSearchQuery<'a, 'b> {
+        self.federation = Some(federation);
+        self
+    }
+
+    fn body<'q>(&'q self, federation: Option<&'q FederationOptions>) -> MultiSearchBody<'q, 'b> {
+        MultiSearchBody {
+            queries: self
+                .queries
+                .iter()
+                .map(|query| IndexedQuery {
+                    index_uid: query.index.uid.as_str(),
+                    query,
+                    // Per-query options are rejected by MeiliSearch outside of a federated search
+                    federation_options: federation.and(query.federation_options.as_ref()),
+                })
+                .collect(),
+            federation,
+        }
+    }
+
+    async fn send<Output: 'static + DeserializeOwned>(&self, federation: Option<&FederationOptions>) -> Result<Output, Error> {
+        for query in &self.queries {
+            query.validate()?;
+        }
+        request::<MultiSearchBody, Output>(
+            &format!("{}/multi-search", self.client.host),
+            &self.client.api_key,
+            Method::Post(self.body(federation)),
+            200,
+            self.client.max_response_bytes,
+        ).await
+    }
+
+    /// Execute the queries and fetch the results, all documents being deserialized into the same type.
+    ///
+    /// The [federation](#structfield.federation) options are ignored: use [execute_federated](#method.execute_federated) instead.
+    pub async fn execute<T: 'static + DeserializeOwned>(&self) -> Result<MultiSearchResponse<T>, Error> {
+        self.send::<MultiSearchResponse<T>>(None).await
+    }
+
+    /// Execute the queries and fetch a single list of results merged from all the queries.
+    ///
+    /// The default [federation](#structfield.federation) options are used if none were set.
+    /// Each result tells which index and which query it comes from, see [SearchResult::federation](struct.SearchResult.html#structfield.federation).
+    pub async fn execute_federated<T: 'static + DeserializeOwned>(&self) -> Result<FederatedSearchResults<T>, Error> {
+        let default_federation = FederationOptions::default();
+        let federation = self.federation.as_ref().unwrap_or(&default_federation);
+        self.send::<FederatedSearchResults<T>>(Some(federation)).await
+    }
+
+    /// Execute the queries and fetch the raw results, one JSON object per query.
+    ///
+    /// Use this when the indexes contain different types of documents:
+    /// each result can then be deserialized into a [SearchResults](struct.SearchResults.html) of the right type with `serde_json::from_value`.
+    pub async fn execute_raw(&self) -> Result<Vec<serde_json::Value>, Error> {
+        Ok(self.send::<RawMultiSearchResponse>(None).await?.results)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawMultiSearchResponse {
+    results: Vec<serde_json::Value>,
+}
+
+/// The results of a [MultiSearchQuery](struct.MultiSearchQuery.html).
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultiSearchResponse<T> {
+    /// The results of each query, in the order of the queries
+    pub results: Vec<SearchResults<T>>,
+}
+
+/// The results of a [federated multi search](struct.MultiSearchQuery.html#method.execute_federated).
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedSearchResults<T> {
+    /// The results of all the queries, merged and ranked
+    pub hits: Vec<SearchResult<T>>,
+    /// Number of results skipped
+    pub offset: usize,
+    /// Number of results returned
+    pub limit: usize,
+    /// Estimated total number of matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_total_hits: Option<usize>,
+    /// Processing time of the queries
+    pub processing_time_ms: usize,
+    /// The facets of each index, by index uid.
+    /// Only returned if [facets_by_index](struct.FederationOptions.html#structfield.facets_by_index) is set without [merge_facets](struct.FederationOptions.html#structfield.merge_facets).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets_by_index: Option<HashMap<String, FacetsForIndex>>,
+    /// Distribution of the facets of all the indexes.
+    /// Only returned if [merge_facets](struct.FederationOptions.html#structfield.merge_facets) is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_distribution: Option<HashMap<String, HashMap<String, usize>>>,
+    /// The lowest and highest values of the numeric facets of all the indexes.
+    /// Only returned if [merge_facets](struct.FederationOptions.html#structfield.merge_facets) is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_stats: Option<HashMap<String, FacetStats>>,
+}
+
+impl<T> FederatedSearchResults<T> {
+    /// The facets of all the indexes, whether they were [merged](struct.FederationOptions.html#structfield.merge_facets) by MeiliSearch or not.
+    ///
+    /// Facets returned by index are merged here: the counts of a value are added up, and the stats cover the values of all the indexes.
+    pub fn facets(&self) -> FacetsForIndex {
+        let mut facets = FacetsForIndex {
+            distribution: self.facet_distribution.clone().unwrap_or_default(),
+            stats: self.facet_stats.clone().unwrap_or_default(),
+        };
+        for index_facets in self.facets_by_index.iter().flat_map(HashMap::values) {
+            for (facet, values) in &index_facets.distribution {
+                let distribution = facets.distribution.entry(facet.clone()).or_default();
+                for (value, count) in values {
+                    let total = distribution.entry(value.clone()).or_default();
+                    *total = total.saturating_add(*count);
+                }
+            }
+            for (facet, stats) in &index_facets.stats {
+                facets.stats
+                    .entry(facet.clone())
+                    .and_modify(|merged| {
+                        merged.min = merged.min.min(stats.min);
+                        merged.max = merged.max.max(stats.max);
+                    })
+                    .or_insert(*stats);
+            }
+        }
+        facets
+    }
+}
+
+/// The facets of the results of an index, in a [federated multi search](struct.FederatedSearchResults.html#structfield.facets_by_index).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FacetsForIndex {
+    /// Distribution of the facets
+    #[serde(default)]
+    pub distribution: HashMap<String, HashMap<String, usize>>,
+    /// The lowest and highest values of the numeric facets
+    #[serde(default)]
+    pub stats: HashMap<String, FacetStats>,
+}
+
+/// What would happen to a term of a query, according to [Index::explain_query_terms](../indexes/struct.Index.html#method.explain_query_terms).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TermStatus {
+    /// The term is a stop word: it is ignored.
+    StopWord,
+    /// The term is searched along with its synonyms.
+    Synonyms(Vec<String>),
+    /// The term is searched as is.
+    Kept,
+}
+
+/// A term of a query and what would happen to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermExplanation {
+    /// The term, lowercased
+    pub term: String,
+    /// What would happen to the term
+    pub status: TermStatus,
+}
+
+/// The result of [Index::explain_query_terms](../indexes/struct.Index.html#method.explain_query_terms).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermReport {
+    /// The terms of the query, in order
+    pub terms: Vec<TermExplanation>,
+}
+
+impl TermReport {
+    /// The terms that would be ignored because they are stop words.
+    pub fn dropped(&self) -> impl Iterator<Item = &str> {
+        self.terms
+            .iter()
+            .filter(|explanation| explanation.status == TermStatus::StopWord)
+            .map(|explanation| explanation.term.as_str())
+    }
+
+    /// The terms that would be searched, with or without synonyms.
+    pub fn surviving(&self) -> impl Iterator<Item = &str> {
+        self.terms
+            .iter()
+            .filter(|explanation| explanation.status != TermStatus::StopWord)
+            .map(|explanation| explanation.term.as_str())
+    }
+
+    /// The terms that have synonyms, with their synonyms.
+    pub fn with_synonyms(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.terms.iter().filter_map(|explanation| match &explanation.status {
+            TermStatus::Synonyms(synonyms) => Some((explanation.term.as_str(), synonyms.as_slice())),
+            _ => None,
+        })
+    }
+}
+
+/// Split a query into lowercased terms and match them against the stop words and the synonyms of an index.
+pub(crate) fn explain_terms(
+    query: &str,
+    stop_words: &[String],
+    synonyms: &HashMap<String, Vec<String>>,
+) -> TermReport {
+    let stop_words: HashSet<String> = stop_words.iter().map(|word| word.to_lowercase()).collect();
+    let synonyms: HashMap<String, &Vec<String>> = synonyms
+        .iter()
+        .map(|(word, synonyms)| (word.to_lowercase(), synonyms))
+        .collect();
+
+    let terms = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let term = term.to_lowercase();
+            let status = if stop_words.contains(&term) {
+                TermStatus::StopWord
+            } else if let Some(synonyms) = synonyms.get(&term) {
+                TermStatus::Synonyms(synonyms.to_vec())
+            } else {
+                TermStatus::Kept
+            };
+            TermExplanation { term, status }
+        })
+        .collect();
+
+    TermReport { terms }
+}
+
+impl Index {
+    /// Explain which terms of a query would be ignored as stop words, which would be searched with their synonyms, and which would be searched as is.
+    ///
+    /// **This is an approximation** computed by the SDK from the [stop words](#method.get_stop_words) and the [synonyms](#method.get_synonyms) of the index.
+    /// The query is split on whitespace and punctuation, which is simpler than the tokenizer of MeiliSearch
+    /// (multi-word synonyms, compound words and non-latin scripts are not handled the same way).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_explain_query_terms").await.unwrap();
+    /// movies.set_stop_words_and_wait(["the"], None, None).await.unwrap();
+    ///
+    /// let report = movies.explain_query_terms("The Matrix").await.unwrap();
+    /// assert_eq!(report.dropped().collect::<Vec<_>>(), vec!["the"]);
+    /// # client.delete_index("movies_explain_query_terms").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn explain_query_terms(&self, query: &str) -> Result<TermReport, Error> {
+        let stop_words = self.get_stop_words().await?;
+        let synonyms = self.get_synonyms().await?;
+        Ok(explain_terms(query, &stop_words, &synonyms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{client::*, document, request::tests::{mock_server, slow_server}, search::*, tests::TestIndex};
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use futures_await_test::async_test;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Document {
+        id: usize,
+        value: String,
+        kind: String,
+    }
+
+    impl document::Document for Document {
+        type UIDType = usize;
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    async fn setup_test_index(client: &Client, name: &str) -> TestIndex {
+        let index = TestIndex::new(client, name).await;
+        index.add_documents_and_wait(&[
+            Document { id: 0, kind: "text".into(), value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.".to_string() },
+            Document { id: 1, kind: "text".into(), value: "dolor sit amet, consectetur adipiscing elit".to_string() },
+            Document { id: 2, kind: "title".into(), value: "The Social Network".to_string() },
+            Document { id: 3, kind: "title".into(), value: "Harry Potter and the Sorcerer's Stone".to_string() },
+            Document { id: 4, kind: "title".into(), value: "Harry Potter and the Chamber of Secrets".to_string() },
+            Document { id: 5, kind: "title".into(), value: "Harry Potter and the Prisoner of Azkaban".to_string() },
+            Document { id: 6, kind: "title".into(), value: "Harry Potter and the Goblet of Fire".to_string() },
+            Document { id: 7, kind: "title".into(), value: "Harry Potter and the Order of the Phoenix".to_string() },
+            Document { id: 8, kind: "title".into(), value: "Harry Potter and the Half-Blood Prince".to_string() },
+            Document { id: 9, kind: "title".into(), value: "Harry Potter and the Deathly Hallows".to_string() },
+        ], None, None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["kind", "value"], None, None).await.unwrap();
+        index.set_sortable_attributes_and_wait(["title"], None, None).await.unwrap();
+        index
+    }
+
+    #[test]
+    fn test_escape_query() {
+        assert_eq!(escape_query("Harry Potter"), "Harry Potter");
+        assert_eq!(escape_query(r#"the "best" movie"#), r#"the \"best\" movie"#);
+        assert_eq!(escape_query(r#"C:\movies\"#), r#"C:\\movies\\"#);
+        assert_eq!(escape_query(r#"\""#), r#"\\\""#);
+        assert_eq!(escape_query("Amélie « Poulain » 東京物語 🎬"), "Amélie « Poulain » 東京物語 🎬");
+        assert_eq!(escape_query(""), "");
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+        let query = index.search().with_phrase(r#"say "hi""#).build();
+        assert_eq!(query.query.as_deref(), Some(r#""say \"hi\"""#));
+        assert_eq!(serde_json::to_value(&query).unwrap()["q"], r#""say \"hi\"""#);
+    }
+
+    fn template_parameters(parameters: &[(&str, &str)]) -> HashMap<String, String> {
+        parameters.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_render_template() {
+        let template: SearchTemplate = serde_json::from_value(serde_json::json!({
+            "q": "{{terms}} movies",
+            "sort": ["{{ order }}:desc", "title:asc"],
+            "filter": [["year > {{year}}"], ["genres = {{genre}}", "genres = drama"]],
+            "limit": 10,
+            "attributesToRetrieve": ["{{terms}}"]
+        })).unwrap();
+        let query = template.render(&template_parameters(&[("terms", "space"), ("order", "rating"), ("year", "2000"), ("genre", "sci-fi")])).unwrap();
+        assert_eq!(serde_json::Value::Object(query.body), serde_json::json!({
+            "q": "space movies",
+            "sort": ["rating:desc", "title:asc"],
+            "filter": [["year > \"2000\""], ["genres = \"sci-fi\"", "genres = drama"]],
+            "limit": 10,
+            "attributesToRetrieve": ["{{terms}}"]
+        }));
+        assert_eq!(query.api_key_override, None);
+
+        // Unclosed placeholders and unused parameters are left alone
+        let template: SearchTemplate = serde_json::from_str(r#"{"q": "{{terms}} {{"}"#).unwrap();
+        let query = template.render(&template_parameters(&[("terms", "a"), ("unused", "b")])).unwrap();
+        assert_eq!(query.body["q"], "a {{");
+    }
+
+    #[test]
+    fn test_render_template_escapes_filters() {
+        let template: SearchTemplate = serde_json::from_str(r#"{"q": "{{title}}", "filter": "title = {{title}} AND year > 2000"}"#).unwrap();
+        let query = template.render(&template_parameters(&[("title", r#"Rock" OR title = "Roll"#)])).unwrap();
+        assert_eq!(query.body["q"], r#"Rock" OR title = "Roll"#);
+        assert_eq!(query.body["filter"], r#"title = "Rock\" OR title = \"Roll" AND year > 2000"#);
+
+        let query = template.render(&template_parameters(&[("title", r#"C:\"movies"#)])).unwrap();
+        assert_eq!(query.body["filter"], r#"title = 'C:\"movies' AND year > 2000"#);
+    }
+
+    #[test]
+    fn test_render_template_missing_parameters() {
+        let template: SearchTemplate = serde_json::from_str(r#"{"q": "{{terms}}", "filter": "genres = {{genre}} OR genres = {{ genre }}", "sort": ["{{order}}:asc"]}"#).unwrap();
+        let error = template.render(&template_parameters(&[("terms", "space")])).unwrap_err();
+        assert!(matches!(&error, Error::MissingTemplateParameters { names } if names == &["genre", "order"]), "{:?}", error);
+        assert_eq!(error.code(), "missing_template_parameters");
+        assert_eq!(error.to_string(), "The search template has no value for the parameters `genre`, `order`.");
+
+        // The names come from `q`, then `filter`, then `sort`, whatever the order of the keys
+        let template: SearchTemplate = serde_json::from_str(r#"{"sort": ["{{order}}:asc"], "filter": "genres = {{genre}}", "q": "{{terms}}"}"#).unwrap();
+        let error = template.render(&HashMap::new()).unwrap_err();
+        assert!(matches!(&error, Error::MissingTemplateParameters { names } if names == &["terms", "genre", "order"]), "{:?}", error);
+    }
+
+    #[test]
+    fn test_escape_value() {
+        assert_eq!(escape_value("Harry Potter"), r#""Harry Potter""#);
+        assert_eq!(escape_value(""), r#""""#);
+        assert_eq!(escape_value(r#"the "best" movie"#), r#""the \"best\" movie""#);
+        assert_eq!(escape_value("Sorcerer's Stone"), r#""Sorcerer's Stone""#);
+        assert_eq!(escape_value("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_value("Amélie « Poulain » 東京物語 🎬"), "\"Amélie « Poulain » 東京物語 🎬\"");
+        // Backslashes are kept as they are, unless they would escape a quote
+        assert_eq!(escape_value(r#"C:\movies\new"#), r#""C:\movies\new""#);
+        assert_eq!(escape_value(r#"\\""#), r#""\\\"""#);
+        assert_eq!(escape_value(r#"a\"b"#), r#"'a\"b'"#);
+        assert_eq!(escape_value(r#"a\"b'c"#), r#"'a\"b\'c'"#);
+        // Values that can't be written exactly
+        assert_eq!(escape_value(r#"a\"b\'c"#), r#""a\\\"b\'c""#);
+        assert_eq!(escape_value(r#"C:\"#), r#""C:\\""#);
+        assert_eq!(escape_value(r#"C:\\"#), r#""C:\\""#);
+    }
+
+    #[async_test]
+    async fn test_query_phrase() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_phrase").await;
+
+        let results: SearchResults<Document> = index.search().with_phrase("Harry Potter").execute().await.unwrap();
+        assert_eq!(results.hits.len(), 7);
+        // The words match in any order without a phrase
+        let results: SearchResults<Document> = index.search().with_query("Potter Harry").execute().await.unwrap();
+        assert_eq!(results.hits.len(), 7);
+        let results: SearchResults<Document> = index.search().with_phrase("Potter Harry").execute().await.unwrap();
+        assert!(results.hits.is_empty());
+    }
+
+    #[async_test]
+    async fn test_query_string() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_string").await;
+
+        let results: SearchResults<Document> =
+            index.search().with_query("dolor").execute().await.unwrap();
+        assert_eq!(results.hits.len(), 2);
+    }
+
+    #[async_test]
+    async fn test_query_limit() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_limit").await;
+
+        let results: SearchResults<Document> =
+            index.search().with_limit(5).execute().await.unwrap();
+        assert_eq!(results.hits.len(), 5);
+    }
+
+    #[async_test]
+    async fn test_query_offset() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_offset").await;
+
+        let results: SearchResults<Document> =
+            index.search().with_offset(6).execute().await.unwrap();
+        assert_eq!(results.hits.len(), 4);
+    }
+
+    #[async_test]
+    async fn test_query_filter() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_filter").await;
+
+        let results: SearchResults<Document> = index
+            .search()
+            .with_filter("value = \"The Social Network\"")
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(results.hits.len(), 1);
+
+        let results: SearchResults<Document> = index
+            .search()
+            .with_filter("NOT value = \"The Social Network\"")
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(results.hits.len(), 9);
+    }
+
+    #[async_test]
+    async fn test_query_facet_distribution() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_facet_distribution").await;
+
+        let mut query = Query::new(&index);
+        query.with_facets_distribution(Selectors::All);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results
+                .facets_distribution
+                .unwrap()
+                .get("kind")
+                .unwrap()
+                .get("title")
+                .unwrap(),
+            &8
+        );
+
+        let mut query = Query::new(&index);
+        query.with_facets_distribution(Selectors::Some(&["kind"]));
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results
+                .facets_distribution
+                .clone()
+                .unwrap()
+                .get("kind")
+                .unwrap()
+                .get("title")
+                .unwrap(),
+            &8
         );
         assert_eq!(
             results
@@ -430,181 +2250,1687 @@ mod tests {
                 .unwrap(),
             &2
         );
+    }
+
+    #[async_test]
+    async fn test_query_attributes_to_retrieve() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_attributes_to_retrieve").await;
+
+        let results: SearchResults<Document> = index
+            .search()
+            .with_attributes_to_retrieve(Selectors::All)
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(results.hits.len(), 10);
+
+        let mut query = Query::new(&index);
+        query.with_attributes_to_retrieve(Selectors::Some(&["kind", "id"])); // omit the "value" field
+        assert!(index.execute_query::<Document>(&query).await.is_err()); // error: missing "value" field
+
+    }
+
+    #[async_test]
+    async fn test_query_sort() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_sort").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("harry potter");
+        query.with_sort(&["title:desc"]);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits.len(), 7);
+    }
+
+    #[test]
+    fn test_sort_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        // Raw slices are still accepted
+        let sort: &[&str] = &["title:desc"];
+        let query = index.search().with_sort(sort).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"sort": ["title:desc"]}));
+
+        let query = index.search()
+            .with_sort(&[
+                Sort::ascending("price"),
+                Sort::descending("rank"),
+                Sort::geo_point(48.8561446, -2.2978204, Direction::Ascending),
+                Sort::geo_point(0.1 + 0.2, 45.0, Direction::Descending),
+            ])
+            .build();
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({"sort": [
+                "price:asc",
+                "rank:desc",
+                "_geoPoint(48.8561446,-2.2978204):asc",
+                "_geoPoint(0.30000000000000004,45):desc",
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_filter_referenced_fields() {
+        let fields = |expression: &'static str| Filter::Expression(expression.into()).referenced_fields();
+        assert_eq!(fields("genres = horror"), ["genres"]);
+        assert_eq!(fields("year>2000 AND year<=2010 OR rating!=3"), ["year", "rating"]);
+        assert_eq!(fields("NOT (genres IN [horror, 'AND year', comedy] OR director EXISTS)"), ["genres", "director"]);
+        assert_eq!(fields("release_date 2000 TO 2010 and poster IS NOT NULL or tags IS EMPTY"), ["release_date", "poster", "tags"]);
+        assert_eq!(fields("\"release year\" = 'it\\'s' AND _geoRadius(45.4, 9.1, 2000)"), ["release year", "_geo"]);
+        assert_eq!(fields("_geoBoundingBox([45.5, 9.1], [45.4, 9.2]) OR genres = 'OR'"), ["_geo", "genres"]);
+        assert!(fields("").is_empty());
+
+        let filter = Filter::Array(vec![vec!["genres = horror", "genres = comedy"], vec!["year > 2000"]]);
+        assert_eq!(filter.referenced_fields(), ["genres", "year"]);
+    }
+
+    #[test]
+    fn test_geo_filter() {
+        let radius = GeoFilter::radius(48.8561446, 2.2978204, 1000).unwrap();
+        assert_eq!(radius.to_string(), "_geoRadius(48.8561446, 2.2978204, 1000)");
+        let bounding_box = GeoFilter::bounding_box((45.0, 0.1 + 0.2), (-12.5, -180.0)).unwrap();
+        assert_eq!(bounding_box.to_string(), "_geoBoundingBox([45.0, 0.30000000000000004], [-12.5, -180.0])");
+
+        assert!(matches!(GeoFilter::radius(90.1, 0.0, 10), Err(Error::InvalidRequest)));
+        assert!(matches!(GeoFilter::radius(0.0, -180.5, 10), Err(Error::InvalidRequest)));
+        assert!(matches!(GeoFilter::radius(f64::NAN, 0.0, 10), Err(Error::InvalidRequest)));
+        assert!(matches!(GeoFilter::bounding_box((0.0, 0.0), (-91.0, 0.0)), Err(Error::InvalidRequest)));
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("restaurants");
+        let query = index.search().with_filter(radius).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"filter": "_geoRadius(48.8561446, 2.2978204, 1000)"}));
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Restaurant {
+        id: usize,
+        #[serde(rename = "_geo")]
+        geo: serde_json::Value,
+    }
+
+    impl document::Document for Restaurant {
+        type UIDType = usize;
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    async fn setup_restaurants(client: &Client, name: &str) -> TestIndex {
+        let index = TestIndex::new(client, name).await;
+        let restaurants = [
+            Restaurant { id: 0, geo: serde_json::json!({"lat": 48.8561446, "lng": 2.2978204}) }, // Paris
+            Restaurant { id: 1, geo: serde_json::json!({"lat": 48.8826517, "lng": 2.3352748}) }, // Paris, 5km away
+            Restaurant { id: 2, geo: serde_json::json!({"lat": 45.4777599, "lng": 9.1967508}) }, // Milan
+        ];
+        index.add_documents_and_wait(&restaurants, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["_geo"], None, None).await.unwrap();
+        index.set_sortable_attributes_and_wait(["_geo"], None, None).await.unwrap();
+        index
+    }
+
+    #[async_test]
+    async fn test_query_geo_filter() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_restaurants(&client, "test_query_geo_filter").await;
+
+        let filter = GeoFilter::radius(48.8561446, 2.2978204, 2000).unwrap();
+        let results: SearchResults<Restaurant> = index.search().with_filter(filter).execute().await.unwrap();
+        let ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+        assert_eq!(ids, vec![0]);
+
+        let filter = GeoFilter::bounding_box((49.0, 3.0), (48.0, 2.0)).unwrap();
+        let results: SearchResults<Restaurant> = index.search().with_filter(filter).execute().await.unwrap();
+        let mut ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![0, 1]);
+    }
+
+    #[async_test]
+    async fn test_query_sort_by_distance() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_restaurants(&client, "test_query_sort_by_distance").await;
+
+        let results: SearchResults<Restaurant> = index.search()
+            .with_sort_by_distance(48.8561446, 2.2978204, Direction::Ascending)
+            .execute()
+            .await
+            .unwrap();
+        let ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        let distances: Vec<u64> = results.hits.iter().map(|hit| hit.geo_distance.unwrap()).collect();
+        assert_eq!(distances[0], 0);
+        assert!(distances.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", distances);
+
+        let results: SearchResults<Restaurant> = index.search()
+            .with_sort_by_distance(48.8561446, 2.2978204, Direction::Descending)
+            .execute()
+            .await
+            .unwrap();
+        let distances: Vec<u64> = results.hits.iter().map(|hit| hit.geo_distance.unwrap()).collect();
+        assert!(distances.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", distances);
+
+        // Without a geo sort, there is no distance
+        let results: SearchResults<Restaurant> = index.search().execute().await.unwrap();
+        assert!(results.hits.iter().all(|hit| hit.geo_distance.is_none()));
+    }
+
+    #[test]
+    fn test_sort_by_distance_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("restaurants");
+        let query = index.search().with_sort(&["rating:desc"]).with_sort_by_distance(48.8561446, 2.2978204, Direction::Ascending).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"sort": ["_geoPoint(48.8561446,2.2978204):asc"]}));
+
+        let hit: SearchResult<Restaurant> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "_geo": {"lat": 48.8826517, "lng": 2.3352748},
+            "_geoDistance": 3624
+        })).unwrap();
+        assert_eq!(hit.geo_distance, Some(3624));
+    }
+
+    #[async_test]
+    async fn test_query_sort_numeric() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_sort_numeric").await;
+        index.set_sortable_attributes_and_wait(["id"], None, None).await.unwrap();
+
+        let results: SearchResults<Document> = index.search().with_sort(&[Sort::ascending("id")]).execute().await.unwrap();
+        let ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+        assert_eq!(ids, (0..10).collect::<Vec<_>>());
+
+        let results: SearchResults<Document> = index.search().with_sort(&[Sort::descending("id")]).execute().await.unwrap();
+        let ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+        assert_eq!(ids, (0..10).rev().collect::<Vec<_>>());
+    }
+
+    #[async_test]
+    async fn test_query_attributes_to_crop() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_attributes_to_crop").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("lorem ipsum");
+        query.with_attributes_to_crop(Selectors::All);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(), Document {
+            id: 0,
+            value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip".to_string(),
+            kind: "text".to_string()
+        });
+
+        let mut query = Query::new(&index);
+        query.with_query("lorem ipsum");
+        let attributes_to_crop = [("value", 50).into(), "kind".into()];
+        query.with_attributes_to_crop(Selectors::Some(&attributes_to_crop));
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(),
+            Document {
+                id: 0,
+                value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit".to_string(),
+                kind: "text".to_string()
+            }
+        );
+    }
+
+    #[async_test]
+    async fn test_query_crop_lenght() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_crop_lenght").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("lorem ipsum");
+        query.with_attributes_to_crop(Selectors::All);
+        query.with_crop_length(200);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(), Document {
+            id: 0,
+            value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip".to_string(),
+            kind: "text".to_string()
+        });
+
+        let mut query = Query::new(&index);
+        query.with_query("lorem ipsum");
+        query.with_attributes_to_crop(Selectors::All);
+        query.with_crop_length(50);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(),
+            Document {
+                id: 0,
+                value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit".to_string(),
+                kind: "text".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_attributes_to_crop_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let attributes_to_crop = [("description", 30).into(), "title".into(), ("overview", None).into()];
+        let query = index.search().with_attributes_to_crop(Selectors::Some(&attributes_to_crop)).build();
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({"attributesToCrop": ["description:30", "title", "overview"]})
+        );
+
+        let query = index.search().with_attributes_to_crop(Selectors::All).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"attributesToCrop": ["*"]}));
+    }
+
+    #[test]
+    fn test_attributes_to_highlight_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let query = index.search().with_attributes_to_highlight(Selectors::Some(&["title", "overview"])).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"attributesToHighlight": ["title", "overview"]}));
+
+        let query = index.search().with_attributes_to_highlight(Selectors::All).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"attributesToHighlight": ["*"]}));
+
+        let query = index.search().with_attributes_to_highlight(Selectors::Some(&["*", "title"])).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"attributesToHighlight": ["*", "title"]}));
+    }
+
+    #[test]
+    fn test_array_filter_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let query = index.search().with_array_filter(vec![vec!["genres = horror", "genres = comedy"], vec!["year > 2000"]]).build();
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({"filter": [["genres = horror", "genres = comedy"], ["year > 2000"]]})
+        );
+
+        let query = index.search().with_filter("year > 2000").build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"filter": "year > 2000"}));
+
+        // The last filter set replaces the previous one
+        let query = index.search().with_filter("year > 2000").with_array_filter(vec![vec!["genres = horror"]]).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"filter": [["genres = horror"]]}));
+        let query = index.search().with_array_filter(vec![vec!["genres = horror"]]).with_filter("year > 2000").build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"filter": "year > 2000"}));
+        assert_eq!(query.filter.as_ref().and_then(Filter::as_expression), Some("year > 2000"));
+    }
+
+    #[test]
+    fn test_query_locales_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let query = index.search().with_locales(["jpn", "cmn"]).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"locales": ["jpn", "cmn"]}));
+
+        let query = index.search().with_locales(vec![String::from("fra")]).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"locales": ["fra"]}));
+
+        // An empty list is never sent
+        let query = index.search().with_locales(["jpn"]).with_locales(Vec::<&str>::new()).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_query_distinct_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let query = index.search().build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({}));
+
+        let query = index.search().with_distinct("owner").build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"distinct": "owner"}));
+    }
+
+    #[async_test]
+    async fn test_query_distinct() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Car {
+            id: usize,
+            owner: String,
+        }
+        impl document::Document for Car {
+            type UIDType = usize;
+            fn get_uid(&self) -> &Self::UIDType {
+                &self.id
+            }
+        }
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_query_distinct").await;
+        let cars: Vec<Car> = (0..6).map(|id| Car { id, owner: ["alice", "bob", "carol"][id % 3].to_string() }).collect();
+        index.add_documents_and_wait(&cars, Some("id"), None, None).await.unwrap();
+        index.set_filterable_attributes_and_wait(["owner"], None, None).await.unwrap();
+
+        let results: SearchResults<Car> = index.search().execute().await.unwrap();
+        assert_eq!(results.hits.len(), 6);
+
+        let results: SearchResults<Car> = index.search().with_distinct("owner").execute().await.unwrap();
+        assert_eq!(results.hits.len(), 3);
+        let owners: HashSet<&str> = results.hits.iter().map(|hit| hit.result.owner.as_str()).collect();
+        assert_eq!(owners.len(), 3);
+    }
+
+    #[test]
+    fn test_query_crop_marker_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let query = index.search().with_crop_length(10).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"cropLength": 10}));
+
+        let query = index.search().with_crop_marker("").build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"cropMarker": ""}));
+    }
+
+    #[async_test]
+    async fn test_query_crop_marker() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_crop_marker").await;
+        let attributes_to_crop = ["value".into()];
+
+        let mut query = Query::new(&index);
+        query.with_query("consequat");
+        query.with_attributes_to_crop(Selectors::Some(&attributes_to_crop));
+        query.with_crop_length(5);
+        query.with_crop_marker("[…]");
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert!(results.hits[0].formatted_field("value").unwrap().contains("[…]"));
+
+        let mut query = Query::new(&index);
+        query.with_query("consequat");
+        query.with_attributes_to_crop(Selectors::Some(&attributes_to_crop));
+        query.with_crop_length(5);
+        query.with_crop_marker("");
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert!(!results.hits[0].formatted_field("value").unwrap().contains('…'));
+    }
+
+    #[async_test]
+    async fn test_query_attributes_to_highlight() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_attributes_to_highlight").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("dolor text");
+        query.with_attributes_to_highlight(Selectors::All);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(),
+            Document {
+                id: 1,
+                value: "<em>dolor</em> sit amet, consectetur adipiscing elit".to_string(),
+                kind: "<em>text</em>".to_string()
+            }
+        );
+
+        let mut query = Query::new(&index);
+        query.with_query("dolor text");
+        query.with_attributes_to_highlight(Selectors::Some(&["value"]));
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(
+            results.hits[0].try_formatted_as::<Document>().unwrap().unwrap(),
+            Document {
+                id: 1,
+                value: "<em>dolor</em> sit amet, consectetur adipiscing elit".to_string(),
+                kind: "text".to_string()
+            }
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Movie {
+        id: usize,
+        title: String,
+        genres: Vec<String>,
+    }
+
+    impl crate::document::Document for Movie {
+        type UIDType = usize;
+
+        fn get_uid(&self) -> &Self::UIDType {
+            &self.id
+        }
+    }
+
+    #[test]
+    fn test_formatted_non_string_fields() {
+        let hit: SearchResult<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Interstellar",
+            "genres": ["Adventure", "Drama"],
+            "_formatted": {
+                "id": "1",
+                "title": "<em>Interstellar</em>",
+                "genres": ["Adventure", "Drama"]
+            }
+        })).unwrap();
+
+        assert_eq!(hit.result.id, 1);
+        assert_eq!(hit.formatted_field("title"), Some("<em>Interstellar</em>"));
+        assert_eq!(hit.formatted_field("genres"), None);
+        assert!(hit.try_formatted_as::<Movie>().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_formatted() {
+        let hit: SearchResult<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Interstellar",
+            "genres": ["Adventure", "Drama"],
+            "_formatted": {
+                "id": 1,
+                "title": "<em>Interstellar</em>",
+                "genres": ["Adventure", "Drama"]
+            }
+        })).unwrap();
+        assert_eq!(hit.formatted().unwrap().unwrap(), Movie {
+            id: 1,
+            title: "<em>Interstellar</em>".to_string(),
+            genres: vec!["Adventure".to_string(), "Drama".to_string()],
+        });
+
+        let hit: SearchResult<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Interstellar",
+            "genres": ["Adventure", "Drama"]
+        })).unwrap();
+        assert!(hit.formatted().is_none());
+    }
+
+    #[test]
+    fn test_facet_stats() {
+        // A response of MeiliSearch v1, to a query with `facets`
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "offset": 0,
+            "limit": 20,
+            "estimatedTotalHits": 3,
+            "facetDistribution": {"release_year": {"1999": 1, "2014": 2}},
+            "facetStats": {"release_year": {"min": 1999, "max": 2014}},
+            "processingTimeMs": 1,
+            "query": ""
+        })).unwrap();
+
+        assert!(results.facets_distribution.is_none());
+        assert_eq!(results.facet_distribution.unwrap()["release_year"]["2014"], 2);
+        assert_eq!(results.facet_stats.unwrap()["release_year"], FacetStats { min: 1999.0, max: 2014.0 });
+    }
+
+    #[test]
+    fn test_facets_distribution_as() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct MovieFacets {
+            genres: HashMap<String, usize>,
+            release_year: HashMap<String, usize>,
+        }
+
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "offset": 0,
+            "limit": 20,
+            "nbHits": 0,
+            "exhaustiveNbHits": false,
+            "facetsDistribution": {"genres": {"Drama": 3, "Comedy": 1}, "release_year": {"1999": 1, "2014": 2}},
+            "processingTimeMs": 1,
+            "query": ""
+        })).unwrap();
+
+        let facets: MovieFacets = results.facets_distribution_as().unwrap().unwrap();
+        assert_eq!(facets.genres["Drama"], 3);
+        assert_eq!(facets.release_year["2014"], 2);
+        let raw: HashMap<String, HashMap<String, usize>> = results.facets_distribution_as().unwrap().unwrap();
+        assert_eq!(&raw, results.facets_distribution.as_ref().unwrap());
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct MissingFacet {
+            director: HashMap<String, usize>,
+        }
+        assert!(matches!(results.facets_distribution_as::<MissingFacet>(), Some(Err(Error::ParseError(_)))));
+
+        // MeiliSearch v1 renamed the field
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "offset": 0,
+            "limit": 20,
+            "estimatedTotalHits": 4,
+            "facetDistribution": {"genres": {"Drama": 3, "Comedy": 1}, "release_year": {"1999": 1, "2014": 2}},
+            "processingTimeMs": 1,
+            "query": ""
+        })).unwrap();
+        let facets: MovieFacets = results.facets_distribution_as().unwrap().unwrap();
+        assert_eq!(facets.genres["Comedy"], 1);
+        assert_eq!(facets.release_year["1999"], 1);
+
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "offset": 0,
+            "limit": 20,
+            "nbHits": 0,
+            "exhaustiveNbHits": false,
+            "processingTimeMs": 1,
+            "query": ""
+        })).unwrap();
+        assert!(results.facets_distribution_as::<MovieFacets>().is_none());
+    }
+
+    #[test]
+    fn test_pagination_modes() {
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "offset": 10,
+            "limit": 5,
+            "estimatedTotalHits": 42,
+            "processingTimeMs": 1,
+            "query": "star"
+        })).unwrap();
+        assert_eq!((results.offset, results.limit, results.estimated_total_hits), (10, 5, Some(42)));
+        assert_eq!((results.total_hits, results.total_pages, results.page, results.hits_per_page), (None, None, None, None));
+
+        let results: SearchResults<Movie> = serde_json::from_value(serde_json::json!({
+            "hits": [],
+            "page": 2,
+            "hitsPerPage": 5,
+            "totalHits": 42,
+            "totalPages": 9,
+            "processingTimeMs": 1,
+            "query": "star"
+        })).unwrap();
+        assert_eq!((results.total_hits, results.total_pages, results.page, results.hits_per_page), (Some(42), Some(9), Some(2), Some(5)));
+        assert_eq!(results.estimated_total_hits, None);
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+        let query = index.search().with_page(2).with_hits_per_page(5).build();
+        assert_eq!(serde_json::to_value(&query).unwrap(), serde_json::json!({"page": 2, "hitsPerPage": 5}));
+    }
+
+    #[async_test]
+    async fn test_query_pagination() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_pagination").await;
+
+        let results: SearchResults<Document> = index.search().with_query("harry").with_page(2).with_hits_per_page(3).execute().await.unwrap();
+        assert_eq!(results.hits.len(), 3);
+        assert_eq!(results.total_hits, Some(7));
+        assert_eq!(results.total_pages, Some(3));
+        assert_eq!(results.page, Some(2));
+        assert_eq!(results.hits_per_page, Some(3));
+    }
+
+    #[async_test]
+    async fn test_query_facet_stats() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Film {
+            id: usize,
+            title: String,
+            release_year: usize,
+        }
+
+        impl crate::document::Document for Film {
+            type UIDType = usize;
+
+            fn get_uid(&self) -> &Self::UIDType {
+                &self.id
+            }
+        }
+
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_query_facet_stats").await;
+        index.set_filterable_attributes_and_wait(["release_year"], None, None).await.unwrap();
+        index.add_documents_and_wait(&[
+            Film { id: 1, title: "The Matrix".to_string(), release_year: 1999 },
+            Film { id: 2, title: "Interstellar".to_string(), release_year: 2014 },
+            Film { id: 3, title: "Alien".to_string(), release_year: 1979 },
+        ], Some("id"), None, None).await.unwrap();
+
+        // The facet stats are only returned by MeiliSearch v1 and newer, which read the `facets` parameter
+        let results: SearchResults<Film> = index.search()
+            .with_facets(Selectors::Some(&["release_year"]))
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(results.facet_distribution.unwrap()["release_year"]["2014"], 1);
+        assert_eq!(results.facet_stats.unwrap()["release_year"], FacetStats { min: 1979.0, max: 2014.0 });
+    }
+
+    #[async_test]
+    async fn test_query_highlight_tags() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_query_highlight_tags").await;
+        index.add_documents_and_wait(&[
+            Movie { id: 1, title: "Interstellar".to_string(), genres: vec![] },
+            Movie { id: 2, title: "Apollo 13".to_string(), genres: vec![] },
+        ], Some("id"), None, None).await.unwrap();
+
+        let mut query = Query::new(&index);
+        query.with_query("interstellar");
+        query.with_attributes_to_highlight(Selectors::Some(&["title"]));
+        query.with_highlight_pre_tag("<mark class=\"hit\">");
+        query.with_highlight_post_tag("</mark>");
+        let results: SearchResults<Movie> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits[0].formatted_field("title"), Some("<mark class=\"hit\">Interstellar</mark>"));
+    }
+
+    #[async_test]
+    async fn test_query_highlight_non_string_fields() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_query_highlight_non_string_fields").await;
+        index.add_documents(&[
+            Movie { id: 1, title: "Interstellar".to_string(), genres: vec!["Adventure".to_string(), "Drama".to_string()] },
+            Movie { id: 2, title: "Apollo 13".to_string(), genres: vec!["Drama".to_string()] },
+        ], Some("id")).await.unwrap().wait_for_pending_update(None, None).await.unwrap().unwrap();
+
+        let mut query = Query::new(&index);
+        query.with_query("interstellar");
+        query.with_attributes_to_highlight(Selectors::All);
+        let results: SearchResults<Movie> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits[0].result.id, 1);
+        assert_eq!(results.hits[0].formatted_field("title"), Some("<em>Interstellar</em>"));
+        assert!(results.hits[0].formatted_result.as_ref().unwrap().contains_key("genres"));
+    }
+
+    #[test]
+    fn test_multi_search_body() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let movies = client.assume_index("movies");
+        let books = client.assume_index("books");
+        let mut multi_search = client.multi_search();
+        multi_search
+            .with_search_query(books.search().with_query("dune").build())
+            .with_search_query(movies.search().with_limit(3).build());
+
+        assert_eq!(serde_json::to_value(multi_search.body(None)).unwrap(), serde_json::json!({
+            "queries": [
+                {"indexUid": "books", "q": "dune"},
+                {"indexUid": "movies", "limit": 3},
+            ]
+        }));
+    }
+
+    #[test]
+    fn test_federated_multi_search_body() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let movies = client.assume_index("movies");
+        let books = client.assume_index("books");
+        let mut multi_search = client.multi_search();
+        multi_search
+            .with_search_query(books.search().with_query("dune").with_federation_options(QueryFederationOptions { weight: Some(2.0) }).build())
+            .with_search_query(movies.search().with_query("dune").build())
+            .with_federation(FederationOptions { limit: Some(5), ..FederationOptions::default() });
+
+        assert_eq!(serde_json::to_value(multi_search.body(multi_search.federation.as_ref())).unwrap(), serde_json::json!({
+            "queries": [
+                {"indexUid": "books", "q": "dune", "federationOptions": {"weight": 2.0}},
+                {"indexUid": "movies", "q": "dune"},
+            ],
+            "federation": {"limit": 5}
+        }));
+        // Per-query options are not sent without federation
+        assert_eq!(serde_json::to_value(multi_search.body(None)).unwrap(), serde_json::json!({
+            "queries": [
+                {"indexUid": "books", "q": "dune"},
+                {"indexUid": "movies", "q": "dune"},
+            ]
+        }));
+    }
+
+    #[test]
+    fn test_federated_hit() {
+        let hit: SearchResult<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Interstellar",
+            "genres": [],
+            "_federation": {"indexUid": "movies", "queriesPosition": 1, "weightedRankingScore": 0.9}
+        })).unwrap();
+
+        assert_eq!(hit.federation, Some(FederationHitInfo {
+            index_uid: "movies".to_string(),
+            queries_position: 1,
+            weighted_ranking_score: Some(0.9),
+        }));
+    }
+
+    #[async_test]
+    async fn test_federated_multi_search() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let movies = TestIndex::new(&client, "test_federated_multi_search_movies").await;
+        let series = TestIndex::new(&client, "test_federated_multi_search_series").await;
+        movies.add_documents_and_wait(&[
+            Movie { id: 1, title: "Star Wars".to_string(), genres: vec![] },
+            Movie { id: 2, title: "Star Trek".to_string(), genres: vec![] },
+        ], Some("id"), None, None).await.unwrap();
+        series.add_documents_and_wait(&[
+            Movie { id: 1, title: "Star Wars: The Clone Wars".to_string(), genres: vec![] },
+            Movie { id: 2, title: "Star Trek: Discovery".to_string(), genres: vec![] },
+        ], Some("id"), None, None).await.unwrap();
+
+        let results = client.multi_search()
+            .with_search_query(movies.search().with_query("star").build())
+            .with_search_query(series.search().with_query("star").build())
+            .with_federation(FederationOptions::default())
+            .execute_federated::<Movie>()
+            .await
+            .unwrap();
+
+        assert_eq!(results.hits.len(), 4);
+        let index_uids: Vec<&str> = results.hits.iter().map(|hit| hit.federation.as_ref().unwrap().index_uid.as_str()).collect();
+        assert!(index_uids.contains(&movies.uid.as_str()));
+        assert!(index_uids.contains(&series.uid.as_str()));
+    }
+
+    #[async_test]
+    async fn test_multi_search() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let documents = setup_test_index(&client, "test_multi_search_documents").await;
+        let movies = TestIndex::new(&client, "test_multi_search_movies").await;
+        movies.add_documents(&[
+            Movie { id: 1, title: "Interstellar".to_string(), genres: vec!["Adventure".to_string()] },
+            Movie { id: 2, title: "Apollo 13".to_string(), genres: vec!["Drama".to_string()] },
+        ], Some("id")).await.unwrap().wait_for_pending_update(None, None).await.unwrap().unwrap();
+
+        let mut multi_search = client.multi_search();
+        multi_search
+            .with_search_query(movies.search().with_query("apollo").build())
+            .with_search_query(documents.search().with_query("Social Network").build())
+            .with_search_query(movies.search().with_query("interstellar").build());
+
+        let results = multi_search.execute_raw().await.unwrap();
+        assert_eq!(results.len(), 3);
+        let first: SearchResults<Movie> = serde_json::from_value(results[0].clone()).unwrap();
+        let second: SearchResults<Document> = serde_json::from_value(results[1].clone()).unwrap();
+        let third: SearchResults<Movie> = serde_json::from_value(results[2].clone()).unwrap();
+        assert_eq!(first.hits[0].result.id, 2);
+        assert_eq!(second.hits[0].result.id, 2);
+        assert_eq!(second.index_uid.as_deref(), Some(documents.uid.as_str()));
+        assert_eq!(third.hits[0].result.id, 1);
+
+        let results = client.multi_search()
+            .with_search_query(movies.search().with_query("interstellar").build())
+            .with_search_query(movies.search().with_query("apollo").build())
+            .execute::<Movie>()
+            .await
+            .unwrap()
+            .results;
+        assert_eq!(results[0].hits[0].result.title, "Interstellar");
+        assert_eq!(results[1].hits[0].result.title, "Apollo 13");
+    }
+
+    fn term(term: &str, status: TermStatus) -> TermExplanation {
+        TermExplanation { term: term.to_string(), status }
+    }
+
+    #[test]
+    fn test_explain_terms() {
+        let stop_words = vec!["the".to_string(), "Of".to_string()];
+        let mut synonyms = HashMap::new();
+        synonyms.insert("Wolverine".to_string(), vec!["logan".to_string(), "xmen".to_string()]);
+
+        let report = explain_terms("The Lord of the Rings: Wolverine's  story!", &stop_words, &synonyms);
+        assert_eq!(report.terms, vec![
+            term("the", TermStatus::StopWord),
+            term("lord", TermStatus::Kept),
+            term("of", TermStatus::StopWord),
+            term("the", TermStatus::StopWord),
+            term("rings", TermStatus::Kept),
+            term("wolverine", TermStatus::Synonyms(vec!["logan".to_string(), "xmen".to_string()])),
+            term("s", TermStatus::Kept),
+            term("story", TermStatus::Kept),
+        ]);
+        assert_eq!(report.dropped().collect::<Vec<_>>(), vec!["the", "of", "the"]);
+        assert_eq!(report.surviving().collect::<Vec<_>>(), vec!["lord", "rings", "wolverine", "s", "story"]);
+        assert_eq!(report.with_synonyms().map(|(term, _)| term).collect::<Vec<_>>(), vec!["wolverine"]);
+    }
+
+    // A response of MeiliSearch v0.20, with highlighting and facets
+    const SEARCH_V0_20: &str = r#"{"hits":[{"id":3,"value":"Harry Potter and the Sorcerer's Stone","kind":"title","_formatted":{"id":"3","value":"<em>Harry</em> Potter and the Sorcerer's Stone","kind":"title"},"_matchesInfo":{"value":[{"start":0,"length":5}]}}],"offset":0,"limit":20,"nbHits":1,"exhaustiveNbHits":false,"facetsDistribution":{"kind":{"title":1}},"exhaustiveFacetsCount":true,"processingTimeMs":1,"query":"harry"}"#;
+    // A response of MeiliSearch v1.x, with ranking scores and facet stats
+    const SEARCH_V1: &str = r#"{"hits":[{"id":3,"value":"Harry Potter and the Sorcerer's Stone","kind":"title","_rankingScore":0.98,"_rankingScoreDetails":{"words":{"order":0,"matchingWords":1,"maxMatchingWords":1,"score":1.0},"id:asc":{"order":1,"value":3}},"_matchesPosition":{"value":[{"start":0,"length":5}]}}],"query":"harry","processingTimeMs":0,"limit":20,"offset":0,"estimatedTotalHits":1,"facetStats":{"id":{"min":3.0,"max":3.0}}}"#;
+
+    /// Deserialize `response`, and check that it is serialized back to `expected` and survives a second round trip.
+    fn assert_round_trip<R: serde::de::DeserializeOwned + Serialize>(response: &str, expected: serde_json::Value) {
+        let results: R = serde_json::from_str(response).unwrap();
+        let serialized = serde_json::to_value(&results).unwrap();
+        assert_eq!(serialized, expected);
+        let results: R = serde_json::from_value(serialized.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&results).unwrap(), serialized);
+    }
+
+    #[test]
+    fn test_search_results_round_trip() {
+        assert_round_trip::<SearchResults<Document>>(SEARCH_V0_20, serde_json::from_str(SEARCH_V0_20).unwrap());
+        assert_round_trip::<SearchResults<Document>>(SEARCH_V1, serde_json::from_str(SEARCH_V1).unwrap());
+    }
+
+    #[test]
+    fn test_multi_search_results_round_trip() {
+        let response = r#"{"results":[{"indexUid":"movies","hits":[{"id":1,"value":"Dune","kind":"title"}],"query":"dune","processingTimeMs":1,"limit":20,"offset":0,"estimatedTotalHits":1}]}"#;
+        assert_round_trip::<MultiSearchResponse<Document>>(response, serde_json::from_str(response).unwrap());
+
+        let response = r#"{"hits":[{"id":1,"value":"Dune","kind":"title","_federation":{"indexUid":"movies","queriesPosition":0,"weightedRankingScore":0.9}}],"processingTimeMs":2,"limit":20,"offset":0,"estimatedTotalHits":1}"#;
+        assert_round_trip::<FederatedSearchResults<Document>>(response, serde_json::from_str(response).unwrap());
+    }
+
+    // Facets of a federated search returned by index, then merged with `mergeFacets`
+    const FACETS_BY_INDEX: &str = r#"{"hits":[],"processingTimeMs":1,"limit":20,"offset":0,"estimatedTotalHits":3,"facetsByIndex":{"movies":{"distribution":{"genre":{"drama":2,"comedy":1}},"stats":{"year":{"min":1994.0,"max":2010.0}}},"series":{"distribution":{"genre":{"drama":1}},"stats":{"year":{"min":2008.0,"max":2019.0}}}}}"#;
+    const MERGED_FACETS: &str = r#"{"hits":[],"processingTimeMs":1,"limit":20,"offset":0,"estimatedTotalHits":3,"facetDistribution":{"genre":{"drama":3,"comedy":1}},"facetStats":{"year":{"min":1994.0,"max":2019.0}}}"#;
+
+    #[test]
+    fn test_federated_facets() {
+        let by_index: FederatedSearchResults<Document> = serde_json::from_str(FACETS_BY_INDEX).unwrap();
+        let facets_by_index = by_index.facets_by_index.as_ref().unwrap();
+        assert_eq!(facets_by_index["movies"].distribution["genre"]["drama"], 2);
+        assert_eq!(facets_by_index["series"].stats["year"], FacetStats { min: 2008.0, max: 2019.0 });
+        assert!(by_index.facet_distribution.is_none());
+
+        let merged: FederatedSearchResults<Document> = serde_json::from_str(MERGED_FACETS).unwrap();
+        assert!(merged.facets_by_index.is_none());
+        assert_eq!(merged.facet_distribution.as_ref().unwrap()["genre"]["drama"], 3);
+
+        // Both shapes give the same view
+        assert_eq!(by_index.facets(), merged.facets());
+        assert_eq!(merged.facets().stats["year"], FacetStats { min: 1994.0, max: 2019.0 });
+
+        assert_round_trip::<FederatedSearchResults<Document>>(FACETS_BY_INDEX, serde_json::from_str(FACETS_BY_INDEX).unwrap());
+        assert_round_trip::<FederatedSearchResults<Document>>(MERGED_FACETS, serde_json::from_str(MERGED_FACETS).unwrap());
+    }
+
+    #[test]
+    fn test_federation_facets_options() {
+        let mut facets_by_index = HashMap::new();
+        facets_by_index.insert("movies".to_string(), vec!["genre".to_string()]);
+        let options = FederationOptions {
+            facets_by_index: Some(facets_by_index),
+            merge_facets: Some(MergeFacets { max_values_per_facet: Some(10) }),
+            ..FederationOptions::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            serde_json::json!({"facetsByIndex": {"movies": ["genre"]}, "mergeFacets": {"maxValuesPerFacet": 10}})
+        );
+    }
+
+    #[test]
+    fn test_explain_terms_empty_query() {
+        let report = explain_terms("  ?! ", &["the".to_string()], &HashMap::new());
+        assert!(report.terms.is_empty());
+    }
+
+    #[async_test]
+    async fn test_explain_query_terms() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_explain_query_terms").await;
+        let mut synonyms = HashMap::new();
+        synonyms.insert("harry".to_string(), vec!["potter".to_string()]);
+        index.set_stop_words_and_wait(["the", "and"], None, None).await.unwrap();
+        index.set_synonyms_and_wait(&synonyms, None, None).await.unwrap();
+
+        let report = index.explain_query_terms("Harry and the Goblet").await.unwrap();
+        assert_eq!(report.terms, vec![
+            term("harry", TermStatus::Synonyms(vec!["potter".to_string()])),
+            term("and", TermStatus::StopWord),
+            term("the", TermStatus::StopWord),
+            term("goblet", TermStatus::Kept),
+        ]);
+    }
+
+    #[async_test]
+    async fn test_query_matches() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_matches").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("dolor text");
+        query.with_matches(true);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert_eq!(results.hits[0].matches_info.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            results.hits[0]
+                .matches_info
+                .as_ref()
+                .unwrap()
+                .get("value")
+                .unwrap(),
+            &vec![MatchRange {
+                start: 0,
+                length: 5
+            }]
+        );
+    }
 
-        client
-            .delete_index("test_query_facet_distribution")
-            .await
-            .unwrap();
+    #[async_test]
+    async fn test_query_show_matches_position() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_show_matches_position").await;
+
+        let mut query = Query::new(&index);
+        query.with_query("dolor text");
+        query.with_show_matches_position(true);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        let hit = &results.hits[0];
+        let positions = hit.matches_position.as_ref().unwrap();
+        for range in &positions["value"] {
+            assert_eq!(hit.result.value[range.start..range.start + range.length].to_lowercase(), "dolor");
+        }
+        for range in &positions["kind"] {
+            assert_eq!(&hit.result.kind[range.start..range.start + range.length], "text");
+        }
+
+        let results: SearchResults<Document> = index.search().with_query("dolor").execute().await.unwrap();
+        assert!(results.hits[0].matches_position.is_none());
+    }
+
+    /// Build a query in a function, without keeping the index borrowed.
+    fn owned_query(index: &Index) -> OwnedQuery {
+        let mut query = index.search();
+        query.with_query("lorem ipsum").with_limit(3).with_api_key_override("searchKey");
+        query.to_owned_query().unwrap()
+    }
+
+    #[test]
+    fn test_owned_query() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let query = owned_query(&client.assume_index("movies"));
+        assert_eq!(serde_json::Value::Object(query.body), serde_json::json!({"q": "lorem ipsum", "limit": 3}));
+        assert_eq!(query.api_key_override.as_deref(), Some("searchKey"));
+
+        let index = client.assume_index("movies");
+        let invalid = index.search().with_ranking_score_threshold(1.5).to_owned_query();
+        assert!(matches!(invalid, Err(Error::InvalidRequest)));
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key_override() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+        let mut query = index.search();
+        query.with_query("lorem ipsum").with_api_key_override("secretSearchKey");
+        let debug = format!("{:?}", query);
+        assert!(!debug.contains("secretSearchKey"), "{}", debug);
+        assert!(!debug.contains("masterKey"), "{}", debug);
+        assert!(debug.contains("lorem ipsum") && debug.contains(REDACTED), "{}", debug);
+        assert!(!format!("{:?}", Method::Post(&query)).contains("secretSearchKey"));
+
+        let owned = query.to_owned_query().unwrap();
+        let debug = format!("{:?}", owned);
+        assert!(!debug.contains("secretSearchKey") && debug.contains(REDACTED), "{}", debug);
     }
 
     #[async_test]
-    async fn test_query_attributes_to_retrieve() {
+    async fn test_owned_query_on_two_indexes() {
+        const RESULTS: &str = r#"{"hits":[],"offset":0,"limit":3,"nbHits":0,"exhaustiveNbHits":false,"processingTimeMs":0,"query":"lorem ipsum"}"#;
+        let (host, requests) = mock_server(vec![(200, RESULTS), (200, RESULTS)]);
+        let client = Client::new(host, "masterKey");
+        let query = owned_query(&client.assume_index("movies"));
+
+        for uid in ["books", "movies"] {
+            let results = query.execute::<Document>(&client.assume_index(uid)).await.unwrap();
+            assert_eq!(results.limit, 3);
+            let request = requests.recv().unwrap();
+            assert!(request.starts_with(&format!("POST /indexes/{}/search ", uid)));
+            assert!(request.to_lowercase().contains("authorization: bearer searchkey"));
+        }
+    }
+
+    #[async_test]
+    async fn test_execute_with_metadata() {
+        const RESULTS: &str = r#"{"hits":[{"id":1,"value":"Lorem","kind":"title"}],"offset":0,"limit":1,"nbHits":1,"exhaustiveNbHits":false,"processingTimeMs":0,"query":"lorem"}"#;
+        const DOCUMENTS: &str = r#"[{"id":1,"value":"Lorem","kind":"title"}]"#;
+        const SETTINGS: &str = r#"{"stopWords":["the"],"distinctAttribute":null}"#;
+        let (host, _requests) = mock_server(vec![(200, RESULTS), (200, DOCUMENTS), (200, SETTINGS)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let mut query = index.search();
+        query.with_query("lorem").with_limit(1);
+        let (results, metrics) = query.execute_with_metadata::<Document>().await.unwrap();
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(metrics.status, 200);
+        assert_eq!(metrics.request_bytes, r#"{"q":"lorem","limit":1}"#.len());
+        assert_eq!(metrics.response_bytes, RESULTS.len());
+
+        let params = crate::indexes::DocumentsQueryParams { limit: Some(1), ..Default::default() };
+        let (documents, metrics) = index.get_documents_with_metadata::<Document>(&params).await.unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!((metrics.request_bytes, metrics.response_bytes), (0, DOCUMENTS.len()));
+
+        let (settings, metrics) = index.get_settings_with_metadata().await.unwrap();
+        assert_eq!(settings.stop_words, Some(vec!["the".to_string()]));
+        assert_eq!(metrics.response_bytes, SETTINGS.len());
+    }
+
+    #[async_test]
+    async fn test_execute_owned_query() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_attributes_to_retrieve").await;
+        let first = setup_test_index(&client, "test_execute_owned_query_1").await;
+        let second = setup_test_index(&client, "test_execute_owned_query_2").await;
+        let mut query = first.search();
+        query.with_query("harry potter").with_limit(3);
+        let query = query.to_owned_query().unwrap();
 
-        let results: SearchResults<Document> = index
+        // Seven documents match, the limit of the query keeps three of them
+        for index in [&first, &second] {
+            let results: SearchResults<Document> = index.execute_owned_query(&query).await.unwrap();
+            assert_eq!(results.hits.len(), 3);
+            assert!(results.hits.iter().all(|hit| hit.result.value.starts_with("Harry Potter")));
+        }
+    }
+
+    #[async_test]
+    async fn test_query_request_timeout() {
+        let client = Client::new(slow_server(Duration::from_secs(10)), "masterKey");
+        let index = client.assume_index("movies");
+
+        let start = std::time::Instant::now();
+        let results = index.search().with_query("space").with_request_timeout(Duration::from_millis(200)).execute::<Movie>().await;
+        assert!(matches!(results, Err(Error::RequestTimeout { .. })));
+        let results = index
             .search()
-            .with_attributes_to_retrieve(Selectors::All)
-            .execute()
-            .await
-            .unwrap();
-        assert_eq!(results.hits.len(), 10);
+            .with_query("space")
+            .with_request_timeout(Duration::from_millis(200))
+            .execute_with_method::<Movie>(SearchMethod::Get)
+            .await;
+        assert!(matches!(results, Err(Error::RequestTimeout { .. })));
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
 
-        let mut query = Query::new(&index);
-        query.with_attributes_to_retrieve(Selectors::Some(&["kind", "id"])); // omit the "value" field
-        assert!(index.execute_query::<Document>(&query).await.is_err()); // error: missing "value" field
+    /// Property tests checking that the serialization of queries stays consistent as parameters are added.
+    #[cfg(not(target_arch = "wasm32"))]
+    mod serialization {
+        use super::*;
+        use proptest::{collection::vec, option, prelude::*};
 
-        client
-            .delete_index("test_query_attributes_to_retrieve")
-            .await
-            .unwrap();
+        /// `None` for no selectors, `Some(None)` for the wildcard.
+        type Selection<T = String> = Option<Option<Vec<T>>>;
+
+        /// The owned values of the parameters of a query, borrowed by the generated queries.
+        #[derive(Debug, Clone)]
+        struct Parameters {
+            query: Option<String>,
+            offset: Option<usize>,
+            limit: Option<usize>,
+            page: Option<usize>,
+            hits_per_page: Option<usize>,
+            filter: Option<String>,
+            facets_distribution: Selection,
+            facets: Selection,
+            sort: Option<Vec<String>>,
+            attributes_to_retrieve: Selection,
+            attributes_to_crop: Selection<(String, Option<usize>)>,
+            crop_length: Option<usize>,
+            crop_marker: Option<String>,
+            attributes_to_highlight: Selection,
+            highlight_pre_tag: Option<String>,
+            highlight_post_tag: Option<String>,
+            flags: [Option<bool>; 5],
+            ranking_score_threshold: Option<f64>,
+            hybrid: Option<(String, f32)>,
+            vector: Option<Vec<f32>>,
+            locales: Option<Vec<String>>,
+            distinct: Option<String>,
+        }
+
+        fn word() -> impl Strategy<Value = String> {
+            "[a-z][a-z_.]{0,8}"
+        }
+
+        fn selectors<T: Strategy>(item: T) -> impl Strategy<Value = Selection<T::Value>> {
+            option::of(option::of(vec(item, 0..4)))
+        }
+
+        prop_compose! {
+            fn pagination()(
+                query in option::of(".{0,12}"),
+                offset in option::of(0..1000usize),
+                limit in option::of(0..1000usize),
+                page in option::of(0..1000usize),
+                hits_per_page in option::of(0..1000usize),
+                filter in option::of("[a-z]{1,8} (=|>|<) [0-9]{1,4}"),
+                sort in option::of(vec("[a-z]{1,8}:(asc|desc)", 0..3)),
+                distinct in option::of(word()),
+            ) -> (Option<String>, [Option<usize>; 4], Option<String>, Option<Vec<String>>, Option<String>) {
+                (query, [offset, limit, page, hits_per_page], filter, sort, distinct)
+            }
+        }
+
+        prop_compose! {
+            fn formatting()(
+                facets_distribution in selectors(word()),
+                attributes_to_retrieve in selectors(word()),
+                attributes_to_crop in selectors((word(), option::of(0..100usize))),
+                crop_length in option::of(0..100usize),
+                crop_marker in option::of(".{0,3}"),
+                attributes_to_highlight in selectors(word()),
+                highlight_pre_tag in option::of(".{0,5}"),
+                highlight_post_tag in option::of(".{0,5}"),
+            ) -> (Selection, Selection, Selection<(String, Option<usize>)>, Option<usize>, Option<String>, Selection, Option<String>, Option<String>) {
+                (facets_distribution, attributes_to_retrieve, attributes_to_crop, crop_length, crop_marker, attributes_to_highlight, highlight_pre_tag, highlight_post_tag)
+            }
+        }
+
+        prop_compose! {
+            fn parameters()(
+                (query, [offset, limit, page, hits_per_page], filter, sort, distinct) in pagination(),
+                (facets_distribution, attributes_to_retrieve, attributes_to_crop, crop_length, crop_marker, attributes_to_highlight, highlight_pre_tag, highlight_post_tag) in formatting(),
+                flags in [option::of(any::<bool>()), option::of(any::<bool>()), option::of(any::<bool>()), option::of(any::<bool>()), option::of(any::<bool>())],
+                ranking_score_threshold in option::of(0.0..=1.0f64),
+                hybrid in option::of((word(), 0.0..=1.0f32)),
+                vector in option::of(vec(-1.0..1.0f32, 1..4)),
+                locales in option::of(vec("[a-z]{3}", 1..3)),
+                facets in selectors(word()),
+            ) -> Parameters {
+                Parameters {
+                    query, offset, limit, page, hits_per_page, filter, facets_distribution, facets, sort, attributes_to_retrieve, attributes_to_crop,
+                    crop_length, crop_marker, attributes_to_highlight, highlight_pre_tag, highlight_post_tag, flags, ranking_score_threshold,
+                    hybrid, vector, locales, distinct,
+                }
+            }
+        }
+
+        fn borrowed(selectors: &Selection) -> Option<Vec<&str>> {
+            selectors.as_ref()?.as_ref().map(|list| list.iter().map(String::as_str).collect())
+        }
+
+        fn borrowed_selectors<'a, T, U>(selectors: Option<&Option<Vec<U>>>, list: Option<&'a [T]>) -> Option<Selectors<&'a [T]>> {
+            selectors.map(|selectors| match (selectors, list) {
+                (Some(_), Some(list)) => Selectors::Some(list),
+                _ => Selectors::All,
+            })
+        }
+
+        /// The keys that should be serialized for `query`.
+        ///
+        /// The query is destructured without `..`, so that a new field doesn't compile until it is registered here and in the generators.
+        fn expected_keys(query: &Query) -> Vec<&'static str> {
+            let Query {
+                index: _,
+                query,
+                offset,
+                limit,
+                page,
+                hits_per_page,
+                filter,
+                facets_distribution,
+                facets,
+                sort,
+                attributes_to_retrieve,
+                attributes_to_crop,
+                crop_length,
+                crop_marker,
+                attributes_to_highlight,
+                highlight_pre_tag,
+                highlight_post_tag,
+                matches,
+                show_matches_position,
+                show_ranking_score,
+                show_ranking_score_details,
+                ranking_score_threshold,
+                hybrid,
+                vector,
+                retrieve_vectors,
+                locales,
+                distinct,
+                // Never serialized
+                federation_options: _,
+                api_key_override: _,
+                request_timeout: _,
+            } = query;
+            let keys = [
+                ("q", query.is_some()),
+                ("offset", offset.is_some()),
+                ("limit", limit.is_some()),
+                ("page", page.is_some()),
+                ("hitsPerPage", hits_per_page.is_some()),
+                ("filter", filter.is_some()),
+                ("facetsDistribution", facets_distribution.is_some()),
+                ("facets", facets.is_some()),
+                ("sort", sort.is_some()),
+                ("attributesToRetrieve", attributes_to_retrieve.is_some()),
+                ("attributesToCrop", attributes_to_crop.is_some()),
+                ("cropLength", crop_length.is_some()),
+                ("cropMarker", crop_marker.is_some()),
+                ("attributesToHighlight", attributes_to_highlight.is_some()),
+                ("highlightPreTag", highlight_pre_tag.is_some()),
+                ("highlightPostTag", highlight_post_tag.is_some()),
+                ("matches", matches.is_some()),
+                ("showMatchesPosition", show_matches_position.is_some()),
+                ("showRankingScore", show_ranking_score.is_some()),
+                ("showRankingScoreDetails", show_ranking_score_details.is_some()),
+                ("rankingScoreThreshold", ranking_score_threshold.is_some()),
+                ("hybrid", hybrid.is_some()),
+                ("vector", vector.is_some()),
+                ("retrieveVectors", retrieve_vectors.is_some()),
+                ("locales", locales.is_some()),
+                ("distinct", distinct.is_some()),
+            ];
+            let mut keys: Vec<&str> = keys.iter().filter(|(_, set)| *set).map(|(key, _)| *key).collect();
+            keys.sort_unstable();
+            keys
+        }
+
+        fn is_camel_case(key: &str) -> bool {
+            key.starts_with(|c: char| c.is_ascii_lowercase()) && key.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+
+        proptest! {
+            #[test]
+            fn test_query_serialization(parameters in parameters()) {
+                let client = Client::new("http://localhost:7700", "masterKey");
+                let index = client.assume_index("movies");
+                let facets_distribution = borrowed(&parameters.facets_distribution);
+                let facets = borrowed(&parameters.facets);
+                let attributes_to_retrieve = borrowed(&parameters.attributes_to_retrieve);
+                let attributes_to_highlight = borrowed(&parameters.attributes_to_highlight);
+                let attributes_to_crop: Option<Vec<AttributeToCrop>> = parameters.attributes_to_crop.as_ref().and_then(Option::as_ref).map(|attributes| {
+                    attributes.iter().map(|(name, length)| AttributeToCrop { name: name.as_str(), length: *length }).collect()
+                });
+                let [matches, show_matches_position, show_ranking_score, show_ranking_score_details, retrieve_vectors] = parameters.flags;
+
+                // No `..`: new fields have to be generated
+                let query = Query {
+                    index: &index,
+                    query: parameters.query.as_deref().map(Cow::Borrowed),
+                    offset: parameters.offset,
+                    limit: parameters.limit,
+                    page: parameters.page,
+                    hits_per_page: parameters.hits_per_page,
+                    filter: parameters.filter.as_deref().map(|filter| Filter::Expression(Cow::Borrowed(filter))),
+                    facets_distribution: borrowed_selectors(parameters.facets_distribution.as_ref(), facets_distribution.as_deref()),
+                    facets: borrowed_selectors(parameters.facets.as_ref(), facets.as_deref()),
+                    sort: parameters.sort.as_ref().map(|sort| sort.iter().map(|sort| Sort::from(sort.as_str())).collect()),
+                    attributes_to_retrieve: borrowed_selectors(parameters.attributes_to_retrieve.as_ref(), attributes_to_retrieve.as_deref()),
+                    attributes_to_crop: borrowed_selectors(parameters.attributes_to_crop.as_ref(), attributes_to_crop.as_deref()),
+                    crop_length: parameters.crop_length,
+                    crop_marker: parameters.crop_marker.as_deref(),
+                    attributes_to_highlight: borrowed_selectors(parameters.attributes_to_highlight.as_ref(), attributes_to_highlight.as_deref()),
+                    highlight_pre_tag: parameters.highlight_pre_tag.as_deref(),
+                    highlight_post_tag: parameters.highlight_post_tag.as_deref(),
+                    matches,
+                    show_matches_position,
+                    show_ranking_score,
+                    show_ranking_score_details,
+                    ranking_score_threshold: parameters.ranking_score_threshold,
+                    hybrid: parameters.hybrid.as_ref().map(|(embedder, semantic_ratio)| HybridSearch { embedder, semantic_ratio: *semantic_ratio }),
+                    vector: parameters.vector.as_deref().map(Cow::Borrowed),
+                    retrieve_vectors,
+                    locales: parameters.locales.clone(),
+                    distinct: parameters.distinct.as_deref(),
+                    federation_options: Some(QueryFederationOptions { weight: Some(0.5) }),
+                    api_key_override: Some("searchKey"),
+                    request_timeout: Some(Duration::from_secs(1)),
+                };
+
+                let body = match serde_json::to_value(&query).unwrap() {
+                    serde_json::Value::Object(body) => body,
+                    other => panic!("the query was serialized as {}", other),
+                };
+                let mut keys: Vec<&str> = body.keys().map(String::as_str).collect();
+                keys.sort_unstable();
+                prop_assert_eq!(keys, expected_keys(&query));
+                for (key, value) in &body {
+                    prop_assert!(is_camel_case(key), "`{}` is not in camelCase", key);
+                    prop_assert!(!value.is_null(), "`{}` is null", key);
+                }
+
+                // The owned query sends the same body
+                prop_assert_eq!(query.to_owned_query().unwrap().body, body);
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_position() {
+        let hit: SearchResult<Movie> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "The dark knight",
+            "genres": [],
+            "_matchesPosition": {"title": [{"start": 4, "length": 4}, {"start": 9, "length": 6}]}
+        })).unwrap();
+
+        let positions = &hit.matches_position.as_ref().unwrap()["title"];
+        let words: Vec<&str> = positions.iter().map(|range| &hit.result.title[range.start..range.start + range.length]).collect();
+        assert_eq!(words, ["dark", "knight"]);
     }
 
     #[async_test]
-    async fn test_query_sort() {
+    async fn test_query_show_ranking_score() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_sort").await;
+        let index = setup_test_index(&client, "test_query_show_ranking_score").await;
 
         let mut query = Query::new(&index);
         query.with_query("harry potter");
-        query.with_sort(&["title:desc"]);
         let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(results.hits.len(), 7);
+        assert!(results.hits.iter().all(|hit| hit.ranking_score.is_none()));
 
-        client
-            .delete_index("test_query_sort")
-            .await
-            .unwrap();
+        query.with_show_ranking_score(true);
+        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        let scores: Vec<f64> = results.hits.iter().map(|hit| hit.ranking_score.unwrap()).collect();
+        assert!(!scores.is_empty());
+        assert!(scores.iter().all(|score| (0.0..=1.0).contains(score)));
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
     }
 
     #[async_test]
-    async fn test_query_attributes_to_crop() {
+    async fn test_query_show_ranking_score_details() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_attributes_to_crop").await;
+        let index = setup_test_index(&client, "test_query_show_ranking_score_details").await;
+        index.set_sortable_attributes(["id"]).await.unwrap()
+            .wait_for_pending_update(None, None).await.unwrap().unwrap();
 
         let mut query = Query::new(&index);
-        query.with_query("lorem ipsum");
-        query.with_attributes_to_crop(Selectors::All);
+        query.with_query("harry potter");
+        query.with_sort(&["id:desc"]);
+        query.with_show_ranking_score_details(true);
         let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(results.hits[0].formatted_result.as_ref().unwrap(), &Document {
-            id: 0,
-            value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip".to_string(),
-            kind: "text".to_string()
-        });
+        let details = results.hits[0].ranking_score_details.as_ref().unwrap();
+        assert!(details.words.is_some());
+        assert!(details.other.contains_key("id:desc"));
+        assert!(results.hits[0].ranking_score.is_none());
+    }
+
+    #[async_test]
+    async fn test_query_ranking_score_threshold() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_ranking_score_threshold").await;
 
         let mut query = Query::new(&index);
-        query.with_query("lorem ipsum");
-        query.with_attributes_to_crop(Selectors::Some(&[("value", Some(50)), ("kind", None)]));
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(
-            results.hits[0].formatted_result.as_ref().unwrap(),
-            &Document {
-                id: 0,
-                value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit".to_string(),
-                kind: "text".to_string()
-            }
-        );
+        query.with_query("harry pottre");
+        query.with_ranking_score_threshold(0.0);
+        let fuzzy_results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert!(!fuzzy_results.hits.is_empty());
 
-        client
-            .delete_index("test_query_attributes_to_crop")
-            .await
-            .unwrap();
+        query.with_ranking_score_threshold(0.99);
+        let strict_results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert!(strict_results.hits.len() < fuzzy_results.hits.len());
     }
 
     #[async_test]
-    async fn test_query_crop_lenght() {
+    async fn test_query_invalid_ranking_score_threshold() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_crop_lenght").await;
+        let index = client.assume_index("test_query_invalid_ranking_score_threshold");
 
         let mut query = Query::new(&index);
-        query.with_query("lorem ipsum");
-        query.with_attributes_to_crop(Selectors::All);
-        query.with_crop_length(200);
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(results.hits[0].formatted_result.as_ref().unwrap(), &Document {
-            id: 0,
-            value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip".to_string(),
-            kind: "text".to_string()
-        });
+        query.with_ranking_score_threshold(1.5);
+        let results = index.execute_query::<Document>(&query).await;
+        assert!(matches!(results, Err(Error::InvalidRequest)));
+    }
+
+    #[test]
+    fn test_query_to_curl() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("test_query_to_curl");
 
         let mut query = Query::new(&index);
-        query.with_query("lorem ipsum");
-        query.with_attributes_to_crop(Selectors::All);
-        query.with_crop_length(50);
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(
-            results.hits[0].formatted_result.as_ref().unwrap(),
-            &Document {
-                id: 0,
-                value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit".to_string(),
-                kind: "text".to_string()
-            }
-        );
+        query.with_query("harry");
+        query.with_filter("value = 'Harry Potter'");
+        let command = query.to_curl();
 
-        client.delete_index("test_query_crop_lenght").await.unwrap();
+        assert!(!command.contains("masterKey"));
+        assert!(command.contains("X-Meili-API-Key: $MEILI_KEY"));
+        assert!(command.starts_with("curl -X POST 'http://localhost:7700/indexes/test_query_to_curl/search'"));
+        assert!(command.ends_with(r#"--data-binary '{"q":"harry","filter":"value = '\''Harry Potter'\''"}'"#));
     }
 
-    #[async_test]
-    async fn test_query_attributes_to_highlight() {
+    #[test]
+    fn test_query_hybrid_serialization() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_attributes_to_highlight").await;
+        let index = client.assume_index("test_query_hybrid_serialization");
 
         let mut query = Query::new(&index);
-        query.with_query("dolor text");
-        query.with_attributes_to_highlight(Selectors::All);
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        query.with_query("space");
+        query.with_hybrid("default", 0.5);
         assert_eq!(
-            results.hits[0].formatted_result.as_ref().unwrap(),
-            &Document {
-                id: 1,
-                value: "<em>dolor</em> sit amet, consectetur adipiscing elit".to_string(),
-                kind: "<em>text</em>".to_string()
-            }
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({
+                "q": "space",
+                "hybrid": {
+                    "embedder": "default",
+                    "semanticRatio": 0.5
+                }
+            })
         );
+    }
+
+    #[test]
+    fn test_query_vector_serialization() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("test_query_vector_serialization");
 
         let mut query = Query::new(&index);
-        query.with_query("dolor text");
-        query.with_attributes_to_highlight(Selectors::Some(&["value"]));
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
+        assert!(serde_json::to_value(&query).unwrap().get("vector").is_none());
+
+        let vector = vec![0.25, -1.5, 3.0];
+        let pointer = vector.as_ptr();
+        query.with_vector(vector);
+        // The vector is moved, not copied
+        assert!(matches!(&query.vector, Some(Cow::Owned(vector)) if vector.as_ptr() == pointer));
+
+        query.with_hybrid("default", 1.0);
         assert_eq!(
-            results.hits[0].formatted_result.as_ref().unwrap(),
-            &Document {
-                id: 1,
-                value: "<em>dolor</em> sit amet, consectetur adipiscing elit".to_string(),
-                kind: "text".to_string()
-            }
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({
+                "hybrid": {
+                    "embedder": "default",
+                    "semanticRatio": 1.0
+                },
+                "vector": [0.25, -1.5, 3.0]
+            })
         );
 
-        client
-            .delete_index("test_query_attributes_to_highlight")
+        let borrowed = [1.0, 2.0];
+        query.with_vector(&borrowed[..]);
+        assert_eq!(serde_json::to_value(&query).unwrap()["vector"], serde_json::json!([1.0, 2.0]));
+    }
+
+    /// A response captured from a search interrupted by `searchCutoffMs`.
+    const DEGRADED_RESPONSE: &str = r#"{
+        "hits": [
+            {"id": 1, "value": "Interstellar", "kind": "title", "_rankingScore": 0.0, "_rankingScoreDetails": {"degraded": true}},
+            {"id": 2, "value": "Inception", "kind": "title", "_rankingScore": 0.0, "_rankingScoreDetails": {"degraded": true}}
+        ],
+        "query": "space",
+        "processingTimeMs": 152,
+        "limit": 20,
+        "offset": 0,
+        "estimatedTotalHits": 2,
+        "degraded": true
+    }"#;
+
+    #[test]
+    fn test_get_parameters() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = client.assume_index("movies");
+
+        let parameters = index.search()
+            .with_query("harry & sally")
+            .with_limit(5)
+            .with_filter("genres = 'comedy, romance' AND year > 1980")
+            .with_sort(&["year:desc", "title:asc"])
+            .with_attributes_to_highlight(Selectors::All)
+            .with_show_ranking_score(true)
+            .with_hybrid("default", 0.5)
+            .with_facets(Selectors::Some(&["genres", "year"]))
+            .build()
+            .to_get_parameters()
+            .unwrap();
+        let mut parameters: Vec<(&str, &str)> = parameters.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        parameters.sort_unstable();
+        assert_eq!(parameters, vec![
+            ("attributesToHighlight", "*"),
+            ("facets", "genres,year"),
+            ("filter", "genres = 'comedy, romance' AND year > 1980"),
+            ("hybridEmbedder", "default"),
+            ("hybridSemanticRatio", "0.5"),
+            ("limit", "5"),
+            ("q", "harry & sally"),
+            ("showRankingScore", "true"),
+            ("sort", "year:desc,title:asc"),
+        ]);
+
+        let unsupported = |query: Query| match query.to_get_parameters() {
+            Err(Error::UnsupportedGetParameter { parameter }) => parameter,
+            result => panic!("unexpected result: {:?}", result),
+        };
+        assert_eq!(unsupported(index.search().with_vector(vec![0.1, 0.2]).build()), "vector");
+        assert_eq!(unsupported(index.search().with_array_filter(vec![vec!["a = 1"]]).build()), "filter");
+        assert_eq!(unsupported(index.search().with_sort(&["_geoPoint(1.0,2.0):asc"]).build()), "sort");
+    }
+
+    #[async_test]
+    async fn test_query_get_and_post() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_get_and_post").await;
+
+        let query = index.search()
+            .with_query("harry")
+            .with_filter("kind = title AND NOT value = \"Harry Potter and the Goblet of Fire\"")
+            .with_sort(&["title:asc"])
+            .with_attributes_to_highlight(Selectors::Some(&["value"]))
+            .with_limit(4)
+            .build();
+        let post: SearchResults<Document> = query.execute_with_method(SearchMethod::Post).await.unwrap();
+        let get: SearchResults<Document> = query.execute_with_method(SearchMethod::Get).await.unwrap();
+
+        assert_eq!(get.hits.len(), 4);
+        let ids = |results: &SearchResults<Document>| results.hits.iter().map(|hit| hit.result.id).collect::<Vec<_>>();
+        assert_eq!(ids(&get), ids(&post));
+        assert_eq!(get.hits[0].formatted_result, post.hits[0].formatted_result);
+    }
+
+    #[test]
+    fn test_raw_search_results() {
+        let response = serde_json::json!({
+            "hits": [
+                {
+                    "id": 1,
+                    "title": "Interstellar",
+                    "anything": {"nested": [1, 2, 3]},
+                    "_formatted": {"id": "1", "title": "<em>Interstellar</em>"},
+                    "_rankingScore": 0.9,
+                    "_futureField": "kept"
+                },
+                {"id": 2, "schemaless": true}
+            ],
+            "query": "interstellar",
+            "processingTimeMs": 1,
+            "limit": 20,
+            "offset": 0,
+            "estimatedTotalHits": 2,
+            "futureMetadata": {"kept": true}
+        });
+        let results: RawSearchResults = serde_json::from_value(response.clone()).unwrap();
+        assert_eq!(results.hits[0]["_formatted"]["title"], "<em>Interstellar</em>");
+        assert_eq!(results.hits[0]["_rankingScore"], 0.9);
+        assert_eq!(results.metadata["estimatedTotalHits"], 2);
+        assert_eq!(serde_json::to_value(&results).unwrap(), response);
+    }
+
+    #[async_test]
+    async fn test_query_execute_raw() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_execute_raw").await;
+
+        let results = index.search()
+            .with_query("harry")
+            .with_attributes_to_highlight(Selectors::All)
+            .with_show_ranking_score(true)
+            .execute_raw()
             .await
             .unwrap();
+        assert_eq!(results.hits.len(), 7);
+        for hit in &results.hits {
+            for field in ["id", "kind", "value", "_formatted", "_rankingScore"] {
+                assert!(hit.contains_key(field), "missing {} in {:?}", field, hit);
+            }
+        }
+        assert_eq!(results.metadata["query"], "harry");
+    }
+
+    #[test]
+    fn test_degraded_search() {
+        let results: SearchResults<Document> = serde_json::from_str(DEGRADED_RESPONSE).unwrap();
+        assert_eq!(results.degraded, Some(true));
+        assert_eq!(results.is_degraded(), Some(true));
+
+        // Older versions only flag the hits
+        let mut response: serde_json::Value = serde_json::from_str(DEGRADED_RESPONSE).unwrap();
+        response.as_object_mut().unwrap().remove("degraded");
+        let results: SearchResults<Document> = serde_json::from_value(response.clone()).unwrap();
+        assert_eq!(results.degraded, None);
+        assert_eq!(results.is_degraded(), Some(true));
+
+        response["hits"][0]["_rankingScoreDetails"]["degraded"] = serde_json::json!(false);
+        response["hits"][1]["_rankingScoreDetails"] = serde_json::json!({"words": {"order": 0, "matchingWords": 1, "maxMatchingWords": 1, "score": 1.0}});
+        let results: SearchResults<Document> = serde_json::from_value(response).unwrap();
+        assert_eq!(results.is_degraded(), Some(false));
+
+        // Servers that never send the flag
+        let results: SearchResults<Document> = serde_json::from_value(serde_json::json!({
+            "hits": [{"id": 1, "value": "Interstellar", "kind": "title"}],
+            "query": "space",
+            "processingTimeMs": 1
+        })).unwrap();
+        assert_eq!(results.is_degraded(), None);
+    }
+
+    #[test]
+    fn test_search_result_vectors() {
+        let hit: SearchResult<Document> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "value": "Interstellar",
+            "kind": "title",
+            "_vectors": {
+                "custom": [[0.5, 1.0], [0.25, 0.75]],
+                "default": {
+                    "embeddings": [[0.1, 0.2]],
+                    "regenerate": true
+                }
+            }
+        })).unwrap();
+        let vectors = hit.vectors.unwrap();
+        assert_eq!(vectors["custom"], serde_json::json!([[0.5, 1.0], [0.25, 0.75]]));
+        assert_eq!(vectors["default"]["regenerate"], serde_json::json!(true));
+        assert_eq!(vectors["default"]["embeddings"][0], serde_json::json!([0.1, 0.2]));
+
+        let hit: SearchResult<Document> = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "value": "Interstellar",
+            "kind": "title"
+        })).unwrap();
+        assert!(hit.vectors.is_none());
     }
 
     #[async_test]
-    async fn test_query_matches() {
+    async fn test_query_invalid_semantic_ratio() {
         let client = Client::new("http://localhost:7700", "masterKey");
-        let index = setup_test_index(&client, "test_query_matches").await;
+        let index = client.assume_index("test_query_invalid_semantic_ratio");
 
         let mut query = Query::new(&index);
-        query.with_query("dolor text");
-        query.with_matches(true);
-        let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
-        assert_eq!(results.hits[0].matches_info.as_ref().unwrap().len(), 2);
-        assert_eq!(
-            results.hits[0]
-                .matches_info
-                .as_ref()
-                .unwrap()
-                .get("value")
-                .unwrap(),
-            &vec![MatchRange {
-                start: 0,
-                length: 5
-            }]
-        );
+        query.with_hybrid("default", -0.1);
+        let results = index.execute_query::<Document>(&query).await;
+        assert!(matches!(results, Err(Error::InvalidRequest)));
+    }
+
+    #[cfg(feature = "experimental-vector-store")]
+    #[async_test]
+    async fn test_query_hybrid() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_query_hybrid").await;
 
-        client.delete_index("test_query_matches").await.unwrap();
+        // Without any embedder configured, the server rejects the query
+        let mut query = Query::new(&index);
+        query.with_query("harry potter");
+        query.with_hybrid("default", 0.5);
+        let results = index.execute_query::<Document>(&query).await;
+        assert!(matches!(results, Err(Error::MeiliSearchError { .. })));
     }
 
     #[async_test]
@@ -616,7 +3942,147 @@ mod tests {
         query.with_query("harry \"of Fire\"");
         let results: SearchResults<Document> = index.execute_query(&query).await.unwrap();
         assert_eq!(results.hits.len(), 1);
+    }
+
+    #[async_test]
+    async fn test_stable_pages_requests() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"{"filterableAttributes": ["kind", "id"], "sortableAttributes": ["id"]}"#),
+            (200, r#"{"hits": [{"id": 0, "value": "a", "kind": "title"}, {"id": 2, "value": "b", "kind": "title"}]}"#),
+            (200, r#"{"hits": [{"id": 5, "value": "c", "kind": "title"}]}"#),
+        ]);
+        let client = Client::new(host, "masterKey");
+        let index = client.assume_index("movies");
+        let query = index.search().with_filter("kind = title").with_offset(10).build();
+
+        let mut pages = query.execute_stable_pages::<Document>("id", 2).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings"));
+        let page = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(page.iter().map(|hit| hit.result.id).collect::<Vec<_>>(), [0, 2]);
+        let request = requests.recv().unwrap();
+        assert!(request.contains(r#""filter":"kind = title""#), "{}", request);
+        assert!(request.contains(r#""sort":["id:asc"]"#), "{}", request);
+        assert!(request.contains(r#""limit":2"#) && !request.contains("offset"), "{}", request);
 
-        client.delete_index("test_phrase_search").await.unwrap();
+        let page = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(page[0].result.id, 5);
+        let request = requests.recv().unwrap();
+        assert!(request.contains(r#""filter":"(kind = title) AND id > 2""#), "{}", request);
+        // The last page was not full, so nothing is sent anymore
+        assert!(pages.next_page().await.unwrap().is_none());
+    }
+
+    #[async_test]
+    async fn test_stable_pages_invalid_key() {
+        let (host, requests) = mock_server(vec![
+            (200, r#"{"filterableAttributes": ["id"], "sortableAttributes": []}"#),
+        ]);
+        let client = Client::new(host, "masterKey");
+        let index = client.assume_index("movies");
+        let query = index.search().build();
+
+        assert!(matches!(query.execute_stable_pages::<Document>("id", 0).await, Err(Error::InvalidRequest)));
+        assert!(matches!(
+            query.execute_stable_pages::<Document>("id", 10).await,
+            Err(Error::InvalidPaginationKey { key }) if key == "id"
+        ));
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings"));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[async_test]
+    async fn test_stable_pages_concurrent_inserts() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = setup_test_index(&client, "test_stable_pages_concurrent_inserts").await;
+        index.set_filterable_attributes_and_wait(["id", "kind"], None, None).await.unwrap();
+        index.set_sortable_attributes_and_wait(["id"], None, None).await.unwrap();
+
+        let query = index.search().build();
+        let mut pages = query.execute_stable_pages::<Document>("id", 3).await.unwrap();
+        let mut seen = Vec::new();
+        let mut next_id = 10;
+        while let Some(page) = pages.next_page().await.unwrap() {
+            seen.extend(page.into_iter().map(|hit| hit.result.id));
+            // Documents added meanwhile would shift the pages of an offset pagination
+            index.add_documents_and_wait(&[
+                Document { id: next_id, kind: "title".into(), value: "Inserted".to_string() },
+            ], None, None, None).await.unwrap();
+            next_id += 1;
+        }
+
+        let unique: HashSet<usize> = seen.iter().copied().collect();
+        assert_eq!(unique.len(), seen.len(), "duplicates in {:?}", seen);
+        assert!((0..10).all(|id| unique.contains(&id)), "gaps in {:?}", seen);
+    }
+
+    /// Property tests checking that escaped values can't alter the filters they are put in.
+    #[cfg(not(target_arch = "wasm32"))]
+    mod escaping {
+        use super::*;
+        use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+
+        /// Read a quoted value at the start of a filter as MeiliSearch does, returning it with the length of the literal.
+        fn parse_quoted(literal: &str) -> Option<(String, usize)> {
+            let quote = literal.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let mut escaped = false;
+            let mut chars = literal.char_indices().skip(1);
+            while let Some((i, c)) = chars.next() {
+                if c == quote {
+                    let raw = &literal[1..i];
+                    let value = if escaped { raw.replace(&format!("\\{}", quote), &quote.to_string()) } else { raw.to_string() };
+                    return Some((value, i + 1));
+                } else if c == '\\' {
+                    escaped |= chars.next()?.1 == quote;
+                }
+            }
+            None
+        }
+
+        /// Whether the value can be written exactly in a filter.
+        fn is_exact(value: &str) -> bool {
+            let trailing = value.chars().rev().take_while(|c| *c == '\\').count();
+            trailing % 2 == 0 && (quotable(value, '"') || quotable(value, '\''))
+        }
+
+        fn tricky_value() -> impl Strategy<Value = String> {
+            r#"[a\\"'](|[a \n\\"']{0,8}[a\\"'])"#
+        }
+
+        proptest! {
+            #[test]
+            fn test_escape_value_round_trip(value in prop_oneof![tricky_value(), any::<String>()]) {
+                let escaped = escape_value(&value);
+                let (parsed, length) = parse_quoted(&escaped).unwrap();
+                prop_assert_eq!(length, escaped.len(), "{} is not a single literal", escaped);
+                if is_exact(&value) {
+                    prop_assert_eq!(parsed, value);
+                }
+            }
+        }
+
+        #[async_test]
+        async fn test_filter_escaped_values() {
+            let mut runner = TestRunner::deterministic();
+            let mut values: Vec<String> = vec![r#"Rock" OR value = "Roll"#.to_string(), r#"a\"b"#.to_string(), "it's".to_string()];
+            while values.len() < 40 {
+                let value = tricky_value().new_tree(&mut runner).unwrap().current();
+                if is_exact(&value) && !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+
+            let client = Client::new("http://localhost:7700", "masterKey");
+            let index = TestIndex::new(&client, "test_filter_escaped_values").await;
+            let documents: Vec<Document> = values.iter().enumerate().map(|(id, value)| Document { id, kind: "fuzz".into(), value: value.clone() }).collect();
+            index.add_documents_and_wait(&documents, Some("id"), None, None).await.unwrap();
+            index.set_filterable_attributes_and_wait(["value"], None, None).await.unwrap();
+
+            for (id, value) in values.iter().enumerate() {
+                let filter = format!("value = {}", escape_value(value));
+                let results: SearchResults<Document> = index.search().with_filter(&filter).with_limit(100).execute().await.unwrap();
+                let ids: Vec<usize> = results.hits.iter().map(|hit| hit.result.id).collect();
+                assert_eq!(ids, [id], "{}", filter);
+            }
+        }
     }
 }
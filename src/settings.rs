@@ -1,10 +1,12 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{collections::{BTreeMap, HashMap, HashSet}, time::Duration};
 use crate::{
     indexes::Index,
     errors::Error,
-    request::{request, Method},
-    progress::{Progress, ProgressJson}
+    cache::cached_request,
+    request::{request, request_with_metrics, Method, RequestMetrics},
+    progress::{Progress, ProgressJson, ProcessedUpdateResult}
 };
 
 /// Struct reprensenting a set of settings.
@@ -31,11 +33,11 @@ use crate::{
 ///     ..Settings::new()
 /// };
 /// ```
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Settings {
     /// List of associated words treated similarly
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_sorted_option")]
     pub synonyms: Option<HashMap<String, Vec<String>>>,
     /// List of words ignored by MeiliSearch when present in search queries
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,6 +60,638 @@ pub struct Settings {
     /// Fields displayed in the returned documents
     #[serde(skip_serializing_if = "Option::is_none")]
     pub displayed_attributes: Option<Vec<String>>,
+    /// How [typos](https://docs.meilisearch.com/learn/configuration/typo_tolerance.html) are tolerated in search queries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typo_tolerance: Option<TypoToleranceSettings>,
+    /// Additional strings splitting the text into words, `"|"` for example
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator_tokens: Option<Vec<String>>,
+    /// Strings removed from the default separators, so that `"@"` is kept in words for example
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_separator_tokens: Option<Vec<String>>,
+    /// How precisely the distance between query words is computed by the `proximity` ranking rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proximity_precision: Option<ProximityPrecision>,
+    /// Maximum duration of a search, in milliseconds, after which the results found so far are returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_cutoff_ms: Option<u64>,
+    /// The embedders generating the vectors of the documents, by name
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_sorted_option")]
+    pub embedders: Option<HashMap<String, Embedder>>,
+    /// Settings unknown to this version of the SDK, by name, as returned by a newer version of MeiliSearch.
+    ///
+    /// They are sent back as they are, so that they survive a [get](../indexes/struct.Index.html#method.get_settings) then [set](../indexes/struct.Index.html#method.set_settings) round trip.
+    /// A setting known by the SDK must be set with its own field instead.
+    #[serde(flatten, serialize_with = "serialize_sorted")]
+    pub extra: HashMap<String, Value>,
+}
+
+/// How precisely the distance between query words is computed by the [proximity](enum.RankingRule.html#variant.Proximity) ranking rule.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProximityPrecision {
+    /// The exact distance between the words is computed. This is the default.
+    ByWord,
+    /// Only whether the words are in the same attribute is checked, making indexing faster.
+    ByAttribute,
+}
+
+/// An embedder generating the vectors used by [vector search](../client/enum.Capability.html#variant.VectorSearch).
+///
+/// The variant is serialized as the `source` of the embedder.
+/// Sources added by newer versions of MeiliSearch are deserialized as [Unknown](#variant.Unknown), and serialized back unchanged.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::settings::{Embedder, Settings};
+/// # use std::collections::HashMap;
+/// let mut embedders = HashMap::new();
+/// embedders.insert("default", Embedder::OpenAi {
+///     model: Some("text-embedding-3-small".to_string()),
+///     api_key: None,
+///     document_template: Some("A movie titled {{doc.title}}".to_string()),
+///     dimensions: Some(1536),
+/// });
+/// let settings = Settings::new().with_embedders(embedders);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "source", rename_all = "camelCase", remote = "Self")]
+pub enum Embedder {
+    /// Vectors computed by the OpenAI API.
+    #[serde(rename_all = "camelCase")]
+    OpenAi {
+        /// The OpenAI model, `text-embedding-3-small` by default
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// The OpenAI API key, read from the `OPENAI_API_KEY` environment variable of the server by default
+        #[serde(skip_serializing_if = "Option::is_none")]
+        api_key: Option<String>,
+        /// The [Liquid](https://shopify.github.io/liquid/) template turning a document into the text to embed
+        #[serde(skip_serializing_if = "Option::is_none")]
+        document_template: Option<String>,
+        /// The number of dimensions of the vectors, for the models supporting it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dimensions: Option<usize>,
+    },
+    /// Vectors computed by the server with a model from the Hugging Face Hub.
+    #[serde(rename_all = "camelCase")]
+    HuggingFace {
+        /// The name of the model on the Hugging Face Hub
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// The revision of the model, a commit hash for example
+        #[serde(skip_serializing_if = "Option::is_none")]
+        revision: Option<String>,
+        /// The [Liquid](https://shopify.github.io/liquid/) template turning a document into the text to embed
+        #[serde(skip_serializing_if = "Option::is_none")]
+        document_template: Option<String>,
+    },
+    /// Vectors computed by an [Ollama](https://ollama.com) server.
+    #[serde(rename_all = "camelCase")]
+    Ollama {
+        /// The embedding endpoint, `http://localhost:11434/api/embeddings` by default
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// The API key sent to the Ollama server
+        #[serde(skip_serializing_if = "Option::is_none")]
+        api_key: Option<String>,
+        /// The Ollama model
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// The [Liquid](https://shopify.github.io/liquid/) template turning a document into the text to embed
+        #[serde(skip_serializing_if = "Option::is_none")]
+        document_template: Option<String>,
+        /// The number of dimensions of the vectors
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dimensions: Option<usize>,
+    },
+    /// Vectors computed by any REST API.
+    #[serde(rename_all = "camelCase")]
+    Rest {
+        /// The embedding endpoint
+        url: String,
+        /// The body of the requests, where `{{text}}` is replaced by the text to embed
+        request: Value,
+        /// The body of the responses, where `{{embedding}}` marks the vector
+        response: Value,
+        /// Additional headers sent with the requests
+        #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_sorted_option")]
+        headers: Option<HashMap<String, String>>,
+    },
+    /// Vectors provided in the `_vectors` field of the documents.
+    #[serde(rename_all = "camelCase")]
+    UserProvided {
+        /// The number of dimensions of the vectors
+        dimensions: usize,
+    },
+    /// An embedder whose source is unknown to this version of the SDK. Contains the raw embedder, with its `source`.
+    #[serde(skip)]
+    Unknown(Value),
+}
+
+impl Embedder {
+    const KNOWN: [&'static str; 5] = ["openAi", "huggingFace", "ollama", "rest", "userProvided"];
+}
+
+impl Serialize for Embedder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Embedder::Unknown(raw) => raw.serialize(serializer),
+            // Calls the implementation generated by serde for known sources
+            _ => Embedder::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Embedder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Embedder, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        match value.get("source").and_then(|source| source.as_str()) {
+            Some(source) if !Embedder::KNOWN.contains(&source) => Ok(Embedder::Unknown(value)),
+            // Calls the implementation generated by serde for known sources
+            _ => Embedder::deserialize(value).map_err(D::Error::custom),
+        }
+    }
+}
+
+/// The minimum length of the words in which typos are tolerated.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MinWordSizeForTypos {
+    /// The minimum length of a word to accept one typo. Default: 5
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_typo: Option<u8>,
+    /// The minimum length of a word to accept two typos. Default: 9
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub two_typos: Option<u8>,
+}
+
+/// The [typo tolerance](https://docs.meilisearch.com/learn/configuration/typo_tolerance.html) settings of an index.
+/// Like [Settings](struct.Settings.html), updates are partial: fields set to `None` are left unchanged.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::settings::{MinWordSizeForTypos, TypoToleranceSettings};
+/// let typo_tolerance = TypoToleranceSettings::new()
+///     .with_min_word_size_for_typos(MinWordSizeForTypos { one_typo: Some(7), two_typos: None })
+///     .with_disable_on_attributes(["isbn"]);
+/// ```
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoToleranceSettings {
+    /// Whether typos are tolerated at all. Default: `true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The minimum length of the words in which typos are tolerated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_word_size_for_typos: Option<MinWordSizeForTypos>,
+    /// Words matched exactly, without typos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_on_words: Option<Vec<String>>,
+    /// Attributes in which words are matched exactly, without typos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_on_attributes: Option<Vec<String>>,
+}
+
+#[allow(missing_docs)]
+impl TypoToleranceSettings {
+    /// Create undefined typo tolerance settings
+    pub fn new() -> TypoToleranceSettings {
+        TypoToleranceSettings::default()
+    }
+    pub fn with_enabled(self, enabled: bool) -> TypoToleranceSettings {
+        TypoToleranceSettings {
+            enabled: Some(enabled),
+            ..self
+        }
+    }
+    pub fn with_min_word_size_for_typos(self, min_word_size_for_typos: MinWordSizeForTypos) -> TypoToleranceSettings {
+        TypoToleranceSettings {
+            min_word_size_for_typos: Some(min_word_size_for_typos),
+            ..self
+        }
+    }
+    pub fn with_disable_on_words<T: IntoVecString>(self, disable_on_words: T) -> TypoToleranceSettings {
+        TypoToleranceSettings {
+            disable_on_words: Some(disable_on_words.convert()),
+            ..self
+        }
+    }
+    pub fn with_disable_on_attributes<T: IntoVecString>(self, disable_on_attributes: T) -> TypoToleranceSettings {
+        TypoToleranceSettings {
+            disable_on_attributes: Some(disable_on_attributes.convert()),
+            ..self
+        }
+    }
+}
+
+/// A setting whose value on the server differs from the requested one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMismatch {
+    /// The name of the setting, as sent to MeiliSearch (`stopWords` for example)
+    pub field: String,
+    /// The requested value
+    pub expected: Value,
+    /// The value on the server, or `None` if the server doesn't know this setting
+    pub actual: Option<Value>,
+}
+
+/// The result of [Index::verify_settings](../indexes/struct.Index.html#method.verify_settings).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsMismatch {
+    /// The settings that differ, in alphabetical order
+    pub fields: Vec<FieldMismatch>,
+}
+
+impl SettingsMismatch {
+    /// Returns `true` if all the requested settings are applied.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// What can be done with the attributes of an index, returned by [Index::capabilities](../indexes/struct.Index.html#method.capabilities).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexCapabilities {
+    /// The filterable attributes, as set in the settings
+    pub filterable_attributes: HashSet<String>,
+    /// The sortable attributes, as set in the settings
+    pub sortable_attributes: HashSet<String>,
+    /// The distinct attribute, if any
+    pub distinct_attribute: Option<String>,
+    /// The ranking rules, by order of importance
+    pub ranking_rules: Vec<String>,
+}
+
+impl IndexCapabilities {
+    fn from_settings(settings: Settings) -> IndexCapabilities {
+        IndexCapabilities {
+            filterable_attributes: settings.filterable_attributes.unwrap_or_default().into_iter().collect(),
+            sortable_attributes: settings.sortable_attributes.unwrap_or_default().into_iter().collect(),
+            distinct_attribute: settings.distinct_attribute,
+            ranking_rules: settings.ranking_rules.unwrap_or_default(),
+        }
+    }
+
+    /// Returns `true` if `field` can be used in a filter.
+    ///
+    /// A field is filterable if it is listed, if `"*"` is listed, or if it is nested in a listed object:
+    /// `address.city` is filterable when `address` is.
+    pub fn can_filter_on(&self, field: &str) -> bool {
+        covers(&self.filterable_attributes, field)
+    }
+
+    /// Returns `true` if `field` can be used to sort the results, with the same rules as [can_filter_on](#method.can_filter_on).
+    pub fn can_sort_on(&self, field: &str) -> bool {
+        covers(&self.sortable_attributes, field)
+    }
+}
+
+/// Whether `field`, or one of the objects containing it, is in `attributes`.
+fn covers(attributes: &HashSet<String>, field: &str) -> bool {
+    if attributes.contains("*") || attributes.contains(field) {
+        return true;
+    }
+    field
+        .match_indices('.')
+        .any(|(position, _)| attributes.contains(&field[..position]))
+}
+
+/// A [ranking rule](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules).
+///
+/// It is sent as a string: `"words"` for the built-in rules, or `"release_date:asc"` for the custom rules.
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::settings::RankingRule;
+/// assert_eq!(RankingRule::from("typo"), RankingRule::Typo);
+/// assert_eq!(RankingRule::from("release_date:desc"), RankingRule::Desc(String::from("release_date")));
+/// assert_eq!(RankingRule::Asc(String::from("rank")).to_string(), "rank:asc");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum RankingRule {
+    /// Sort the documents by decreasing number of matched query terms
+    Words,
+    /// Sort the documents by increasing number of typos
+    Typo,
+    /// Sort the documents by increasing distance between the matched query terms
+    Proximity,
+    /// Sort the documents by the importance of the attributes containing the query terms
+    Attribute,
+    /// Sort the documents by the `sort` parameter of the query
+    Sort,
+    /// Sort the documents by the similarity of the matched words with the query words
+    Exactness,
+    /// Sort the documents by increasing values of an attribute
+    Asc(String),
+    /// Sort the documents by decreasing values of an attribute
+    Desc(String),
+    /// A rule unknown to this version of the SDK, kept as is
+    Other(String),
+}
+
+impl RankingRule {
+    /// Returns `true` for the rules sorting by an attribute.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, RankingRule::Asc(_) | RankingRule::Desc(_))
+    }
+}
+
+impl From<&str> for RankingRule {
+    fn from(rule: &str) -> RankingRule {
+        match rule {
+            "words" => RankingRule::Words,
+            "typo" => RankingRule::Typo,
+            "proximity" => RankingRule::Proximity,
+            "attribute" => RankingRule::Attribute,
+            "sort" => RankingRule::Sort,
+            "exactness" => RankingRule::Exactness,
+            rule => {
+                if let Some(attribute) = rule.strip_suffix(":asc") {
+                    RankingRule::Asc(attribute.to_string())
+                } else if let Some(attribute) = rule.strip_suffix(":desc") {
+                    RankingRule::Desc(attribute.to_string())
+                } else {
+                    RankingRule::Other(rule.to_string())
+                }
+            }
+        }
+    }
+}
+
+impl From<String> for RankingRule {
+    fn from(rule: String) -> RankingRule {
+        RankingRule::from(rule.as_str())
+    }
+}
+
+impl From<RankingRule> for String {
+    fn from(rule: RankingRule) -> String {
+        rule.to_string()
+    }
+}
+
+impl std::fmt::Display for RankingRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RankingRule::Words => write!(f, "words"),
+            RankingRule::Typo => write!(f, "typo"),
+            RankingRule::Proximity => write!(f, "proximity"),
+            RankingRule::Attribute => write!(f, "attribute"),
+            RankingRule::Sort => write!(f, "sort"),
+            RankingRule::Exactness => write!(f, "exactness"),
+            RankingRule::Asc(attribute) => write!(f, "{}:asc", attribute),
+            RankingRule::Desc(attribute) => write!(f, "{}:desc", attribute),
+            RankingRule::Other(rule) => write!(f, "{}", rule),
+        }
+    }
+}
+
+/// The ranking rules of an index, by order of importance.
+///
+/// See [Index::update_ranking_rules_with](../indexes/struct.Index.html#method.update_ranking_rules_with).
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::settings::{RankingRule, RankingRules};
+/// let mut rules = RankingRules::from(["words", "typo", "proximity", "attribute", "sort", "exactness"]);
+/// rules.move_before(&RankingRule::Sort, &RankingRule::Typo).unwrap();
+/// rules.insert_custom(RankingRule::Desc(String::from("rank")), 6).unwrap();
+/// assert_eq!(rules.position_of(&RankingRule::Sort), Some(1));
+/// assert_eq!(rules.to_strings(), ["words", "sort", "typo", "proximity", "attribute", "exactness", "rank:desc"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RankingRules(pub Vec<RankingRule>);
+
+impl RankingRules {
+    /// The position of `rule`, if it is present.
+    pub fn position_of(&self, rule: &RankingRule) -> Option<usize> {
+        self.0.iter().position(|candidate| candidate == rule)
+    }
+
+    /// Move `rule` just before `other`.
+    ///
+    /// Returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) if one of them is missing.
+    pub fn move_before(&mut self, rule: &RankingRule, other: &RankingRule) -> Result<(), Error> {
+        let from = self.position_of(rule).ok_or(Error::InvalidRequest)?;
+        let to = self.position_of(other).ok_or(Error::InvalidRequest)?;
+        if from > to {
+            let rule = self.0.remove(from);
+            self.0.insert(to, rule);
+        } else if from + 1 < to {
+            let rule = self.0.remove(from);
+            self.0.insert(to - 1, rule);
+        }
+        Ok(())
+    }
+
+    /// Insert a custom rule ([Asc](enum.RankingRule.html#variant.Asc) or [Desc](enum.RankingRule.html#variant.Desc)) at position `at`.
+    ///
+    /// Returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) if the rule isn't custom,
+    /// if it is already present, or if `at` is after the end of the rules.
+    pub fn insert_custom(&mut self, rule: RankingRule, at: usize) -> Result<(), Error> {
+        if !rule.is_custom() || self.0.contains(&rule) || at > self.0.len() {
+            return Err(Error::InvalidRequest);
+        }
+        self.0.insert(at, rule);
+        Ok(())
+    }
+
+    /// Check the rules before sending them.
+    ///
+    /// Each built-in rule must appear at most once.
+    /// When `capabilities` are provided, the attributes of the custom rules must also be [sortable](struct.IndexCapabilities.html#method.can_sort_on).
+    /// Returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) otherwise.
+    pub fn validate(&self, capabilities: Option<&IndexCapabilities>) -> Result<(), Error> {
+        let mut seen = HashSet::new();
+        for rule in &self.0 {
+            match rule {
+                RankingRule::Asc(attribute) | RankingRule::Desc(attribute) => {
+                    if let Some(capabilities) = capabilities {
+                        if !capabilities.can_sort_on(attribute) {
+                            return Err(Error::InvalidRequest);
+                        }
+                    }
+                }
+                RankingRule::Other(_) => {}
+                rule => {
+                    if !seen.insert(rule) {
+                        return Err(Error::InvalidRequest);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The rules, as sent to MeiliSearch.
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl From<Vec<String>> for RankingRules {
+    fn from(rules: Vec<String>) -> RankingRules {
+        RankingRules(rules.into_iter().map(RankingRule::from).collect())
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for RankingRules {
+    fn from(rules: [&str; N]) -> RankingRules {
+        RankingRules(rules.iter().map(|rule| RankingRule::from(*rule)).collect())
+    }
+}
+
+impl IntoVecString for RankingRules {
+    fn convert(self) -> Vec<String> {
+        self.to_strings()
+    }
+}
+
+/// Settings whose order is not kept by MeiliSearch.
+const UNORDERED_SETTINGS: [&str; 3] = ["filterableAttributes", "sortableAttributes", "stopWords"];
+
+fn sorted(values: &[Value]) -> Vec<Value> {
+    let mut values = values.to_vec();
+    values.sort_by_key(|value| value.to_string());
+    values
+}
+
+/// Serialize a map with its keys in order, so that equal maps are always serialized the same way.
+fn serialize_sorted<S: Serializer, V: Serialize>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+fn serialize_sorted_option<S: Serializer, V: Serialize>(
+    map: &Option<HashMap<String, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.as_ref().map(|map| map.iter().collect::<BTreeMap<_, _>>()).serialize(serializer)
+}
+
+/// Put a setting in a canonical form, so that equivalent values compare equal.
+fn normalize_setting(field: &str, value: &Value) -> Value {
+    match value {
+        // Any list containing the wildcard selects all the attributes
+        Value::Array(values) if values.iter().any(|value| value == "*") => Value::Array(vec![Value::from("*")]),
+        Value::Array(values) if UNORDERED_SETTINGS.contains(&field) => Value::Array(sorted(values)),
+        Value::Object(synonyms) if field == "synonyms" => Value::Object(
+            synonyms
+                .iter()
+                .map(|(word, synonyms)| {
+                    let synonyms = match synonyms {
+                        Value::Array(synonyms) => Value::Array(sorted(synonyms)),
+                        other => other.clone(),
+                    };
+                    (word.clone(), synonyms)
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Returns `wanted` unless it is equivalent to `current`, as defined by [normalize_setting].
+fn changed_setting<T: Serialize + Clone>(field: &str, wanted: &Option<T>, current: &Option<T>) -> Option<T> {
+    let equivalent = |wanted: &T, current: &T| match (serde_json::to_value(wanted), serde_json::to_value(current)) {
+        (Ok(wanted), Ok(current)) => normalize_setting(field, &wanted) == normalize_setting(field, &current),
+        _ => false,
+    };
+    match (wanted, current) {
+        (Some(wanted), Some(current)) if equivalent(wanted, current) => None,
+        (wanted, _) => wanted.clone(),
+    }
+}
+
+/// Compare serialized settings, field by field. Only the fields present in `expected` are compared.
+pub(crate) fn compare_settings(expected: &Value, actual: &Value) -> SettingsMismatch {
+    let mut fields = Vec::new();
+    if let Value::Object(expected) = expected {
+        let mut names: Vec<&String> = expected.keys().collect();
+        names.sort();
+        for name in names {
+            let expected = &expected[name];
+            let actual = actual.get(name);
+            let matches = match actual {
+                Some(actual) => normalize_setting(name, expected) == normalize_setting(name, actual),
+                None => expected.is_null(),
+            };
+            if !matches {
+                fields.push(FieldMismatch {
+                    field: name.clone(),
+                    expected: expected.clone(),
+                    actual: actual.cloned(),
+                });
+            }
+        }
+    }
+    SettingsMismatch { fields }
+}
+
+/// A builder of [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html), for [set_synonyms](../indexes/struct.Index.html#method.set_synonyms) or [add_synonyms](../indexes/struct.Index.html#method.add_synonyms).
+///
+/// # Example
+///
+/// ```
+/// # use meilisearch_sdk::settings::Synonyms;
+/// let synonyms = Synonyms::symmetric(["wolverine", "logan", "xmen"]).with_one_way("phone", ["iphone"]).into_map();
+/// assert_eq!(synonyms["logan"], ["wolverine", "xmen"]);
+/// assert_eq!(synonyms["phone"], ["iphone"]);
+/// assert!(!synonyms.contains_key("iphone"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Synonyms {
+    synonyms: HashMap<String, Vec<String>>,
+}
+
+#[allow(missing_docs)]
+impl Synonyms {
+    /// Create empty synonyms
+    pub fn new() -> Synonyms {
+        Synonyms::default()
+    }
+    /// Synonyms making each of the words a synonym of all the others
+    pub fn symmetric(words: impl IntoVecString) -> Synonyms {
+        Synonyms::new().with_symmetric(words)
+    }
+    pub fn with_symmetric(mut self, words: impl IntoVecString) -> Synonyms {
+        let words = words.convert();
+        for word in &words {
+            let others: Vec<String> = words.iter().filter(|other| *other != word).cloned().collect();
+            merge_synonyms(&mut self.synonyms, word, others);
+        }
+        self
+    }
+    /// Add synonyms to `word` only: searching for the synonyms doesn't return the documents containing `word`
+    pub fn with_one_way(mut self, word: impl Into<String>, synonyms: impl IntoVecString) -> Synonyms {
+        merge_synonyms(&mut self.synonyms, &word.into(), synonyms.convert());
+        self
+    }
+    pub fn into_map(self) -> HashMap<String, Vec<String>> {
+        self.synonyms
+    }
+}
+
+impl From<Synonyms> for HashMap<String, Vec<String>> {
+    fn from(synonyms: Synonyms) -> HashMap<String, Vec<String>> {
+        synonyms.into_map()
+    }
+}
+
+/// Add synonyms to `word`, keeping its current synonyms first and skipping the duplicates.
+fn merge_synonyms(into: &mut HashMap<String, Vec<String>>, word: &str, synonyms: Vec<String>) {
+    let current = into.entry(word.to_string()).or_default();
+    for synonym in synonyms {
+        if !current.contains(&synonym) {
+            current.push(synonym);
+        }
+    }
 }
 
 pub trait IntoVecString: Sized {
@@ -168,6 +802,13 @@ impl Settings {
             distinct_attribute: None,
             searchable_attributes: None,
             displayed_attributes: None,
+            typo_tolerance: None,
+            separator_tokens: None,
+            non_separator_tokens: None,
+            proximity_precision: None,
+            search_cutoff_ms: None,
+            embedders: None,
+            extra: HashMap::new(),
         }
     }
     pub fn with_synonyms<T: Into<String>, U: IntoVecString>(self, synonyms: HashMap<T, U>) -> Settings {
@@ -226,13 +867,103 @@ impl Settings {
             ..self
         }
     }
+    pub fn with_typo_tolerance(self, typo_tolerance: TypoToleranceSettings) -> Settings {
+        Settings {
+            typo_tolerance: Some(typo_tolerance),
+            ..self
+        }
+    }
+    pub fn with_separator_tokens<T: IntoVecString>(self, separator_tokens: T) -> Settings {
+        Settings {
+            separator_tokens: Some(separator_tokens.convert()),
+            ..self
+        }
+    }
+    pub fn with_non_separator_tokens<T: IntoVecString>(self, non_separator_tokens: T) -> Settings {
+        Settings {
+            non_separator_tokens: Some(non_separator_tokens.convert()),
+            ..self
+        }
+    }
+    pub fn with_proximity_precision(self, proximity_precision: ProximityPrecision) -> Settings {
+        Settings {
+            proximity_precision: Some(proximity_precision),
+            ..self
+        }
+    }
+    pub fn with_search_cutoff_ms(self, search_cutoff_ms: u64) -> Settings {
+        Settings {
+            search_cutoff_ms: Some(search_cutoff_ms),
+            ..self
+        }
+    }
+    pub fn with_embedders<T: Into<String>>(self, embedders: HashMap<T, Embedder>) -> Settings {
+        Settings {
+            embedders: Some(embedders.into_iter().map(|(name, embedder)| (name.into(), embedder)).collect()),
+            ..self
+        }
+    }
+
+    /// Serialize the settings to JSON, in a stable order.
+    ///
+    /// Maps such as the synonyms are serialized with their keys sorted, so equal settings always give the same string, whatever the order in which they were built.
+    /// The result can be hashed to know whether the settings of an index need to be updated.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::settings::Settings;
+    /// # use std::collections::HashMap;
+    /// let mut synonyms = HashMap::new();
+    /// synonyms.insert("wolverine", vec!["logan"]);
+    /// synonyms.insert("logan", vec!["wolverine"]);
+    /// let settings = Settings::new().with_synonyms(synonyms);
+    /// assert_eq!(settings.canonical_json().unwrap(), r#"{"synonyms":{"logan":["wolverine"],"wolverine":["logan"]}}"#);
+    /// ```
+    pub fn canonical_json(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(Error::ParseError)
+    }
+
+    /// Keep only the settings that would change `current` if they were applied.
+    ///
+    /// The settings that are not set (`None`) are not compared: they don't mean "reset" but "leave as is".
+    /// Equivalent values are not considered as changes, as in [verify_settings](../indexes/struct.Index.html#method.verify_settings).
+    ///
+    /// ```
+    /// # use meilisearch_sdk::settings::Settings;
+    /// let current = Settings::new().with_stop_words(["the", "of"]).with_distinct_attribute("id");
+    /// let wanted = Settings::new().with_stop_words(["of", "the"]).with_distinct_attribute("title");
+    /// assert_eq!(wanted.diff(&current), Settings::new().with_distinct_attribute("title"));
+    /// ```
+    pub fn diff(&self, current: &Settings) -> Settings {
+        Settings {
+            synonyms: changed_setting("synonyms", &self.synonyms, &current.synonyms),
+            stop_words: changed_setting("stopWords", &self.stop_words, &current.stop_words),
+            ranking_rules: changed_setting("rankingRules", &self.ranking_rules, &current.ranking_rules),
+            filterable_attributes: changed_setting("filterableAttributes", &self.filterable_attributes, &current.filterable_attributes),
+            sortable_attributes: changed_setting("sortableAttributes", &self.sortable_attributes, &current.sortable_attributes),
+            distinct_attribute: changed_setting("distinctAttribute", &self.distinct_attribute, &current.distinct_attribute),
+            searchable_attributes: changed_setting("searchableAttributes", &self.searchable_attributes, &current.searchable_attributes),
+            displayed_attributes: changed_setting("displayedAttributes", &self.displayed_attributes, &current.displayed_attributes),
+            typo_tolerance: changed_setting("typoTolerance", &self.typo_tolerance, &current.typo_tolerance),
+            separator_tokens: changed_setting("separatorTokens", &self.separator_tokens, &current.separator_tokens),
+            non_separator_tokens: changed_setting("nonSeparatorTokens", &self.non_separator_tokens, &current.non_separator_tokens),
+            proximity_precision: changed_setting("proximityPrecision", &self.proximity_precision, &current.proximity_precision),
+            search_cutoff_ms: changed_setting("searchCutoffMs", &self.search_cutoff_ms, &current.search_cutoff_ms),
+            embedders: changed_setting("embedders", &self.embedders, &current.embedders),
+            extra: self
+                .extra
+                .iter()
+                .filter(|(name, value)| current.extra.get(*name) != Some(value))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        }
+    }
 }
 
 impl Index {
     /// Get [settings](../settings/struct.Settings.html) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -240,18 +971,87 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_settings(&self) -> Result<Settings, Error> {
-        Ok(request::<(), Settings>(
+        cached_request::<Settings>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Same as [get_settings](#method.get_settings), also returning the [measures](../client/struct.RequestMetrics.html) of the request.
+    ///
+    /// The settings are always fetched from the server, even if the [cache](../client/struct.Client.html#method.with_cache) is enabled.
+    pub async fn get_settings_with_metadata(&self) -> Result<(Settings, RequestMetrics), Error> {
+        request_with_metrics::<(), Settings>(
             &format!("{}/indexes/{}/settings", self.host, self.uid),
             &self.api_key,
             Method::Get,
             200,
-        ).await?)
+            self.max_response_bytes,
+            None,
+        ).await
+    }
+
+    /// Check that the settings of the index match the given [settings](../settings/struct.Settings.html), **without applying anything**.
+    ///
+    /// Only the settings set in `settings` are compared. Equivalent values are not reported:
+    /// the order of filterable attributes, sortable attributes, stop words and synonyms is ignored, and any list containing `"*"` matches `["*"]`.
+    /// The current settings are always fetched from the server, even if the [cache](../client/struct.Client.html#method.with_cache) is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_verify_settings").await.unwrap();
+    /// let settings = Settings::new().with_stop_words(["the", "of"]);
+    ///
+    /// movies.set_settings_and_wait(&settings, None, None).await.unwrap();
+    /// assert!(movies.verify_settings(&settings).await.unwrap().is_empty());
+    /// # client.delete_index("movies_verify_settings").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn verify_settings(&self, settings: &Settings) -> Result<SettingsMismatch, Error> {
+        let expected = serde_json::to_value(settings).map_err(Error::ParseError)?;
+        let actual = request::<(), Value>(
+            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        Ok(compare_settings(&expected, &actual))
+    }
+
+    /// Get the [capabilities](../settings/struct.IndexCapabilities.html) of the index: what can be filtered, sorted, and how results are ranked.
+    ///
+    /// The settings are fetched once, through the [cache](../client/struct.Client.html#method.with_cache) if it is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_capabilities").await.unwrap();
+    /// movies.set_filterable_attributes_and_wait(["release.year"], None, None).await.unwrap();
+    ///
+    /// let capabilities = movies.capabilities().await.unwrap();
+    /// assert!(capabilities.can_filter_on("release.year"));
+    /// assert!(!capabilities.can_sort_on("release.year"));
+    /// # client.delete_index("movies_capabilities").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn capabilities(&self) -> Result<IndexCapabilities, Error> {
+        Ok(IndexCapabilities::from_settings(self.get_settings().await?))
     }
 
     /// Get [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -259,18 +1059,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_synonyms(&self) -> Result<HashMap<String, Vec<String>>, Error> {
-        Ok(request::<(), HashMap<String, Vec<String>>>(
+        cached_request::<HashMap<String, Vec<String>>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/synonyms", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [stop-words](https://docs.meilisearch.com/reference/features/stop_words.html) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -278,18 +1078,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_stop_words(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/stop-words", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [ranking rules](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -297,18 +1097,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_ranking_rules(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/ranking-rules", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [filterable attributes](https://docs.meilisearch.com/reference/features/filtering_and_faceted_search.html) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -316,18 +1116,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_filterable_attributes(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/filterable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [sortable attributes](https://docs.meilisearch.com/reference/features/sorting.html) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -335,18 +1135,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_sortable_attributes(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/sortable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get the [distinct attribute](https://docs.meilisearch.com/reference/features/settings.html#distinct-attribute) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -354,18 +1154,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_distinct_attribute(&self) -> Result<Option<String>, Error> {
-        Ok(request::<(), Option<String>>(
+        cached_request::<Option<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/distinct-attribute", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [searchable attributes](https://docs.meilisearch.com/reference/features/field_properties.html#searchable-fields) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -373,18 +1173,18 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_searchable_attributes(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/searchable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
     }
 
     /// Get [displayed attributes](https://docs.meilisearch.com/reference/features/settings.html#displayed-attributes) of the Index.
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let movie_index = client.get_or_create("movies").await.unwrap();
@@ -392,12 +1192,127 @@ impl Index {
     /// # });
     /// ```
     pub async fn get_displayed_attributes(&self) -> Result<Vec<String>, Error> {
-        Ok(request::<(), Vec<String>>(
+        cached_request::<Vec<String>>(
+            &self.cache,
             &format!("{}/indexes/{}/settings/displayed-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Get,
-            200,
-        ).await?)
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get [typo tolerance](https://docs.meilisearch.com/learn/configuration/typo_tolerance.html) settings of the Index.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let typo_tolerance = movie_index.get_typo_tolerance().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_typo_tolerance(&self) -> Result<TypoToleranceSettings, Error> {
+        cached_request::<TypoToleranceSettings>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/typo-tolerance", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get the separator tokens of the Index, splitting the text into words in addition to the default separators.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let separator_tokens = movie_index.get_separator_tokens().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_separator_tokens(&self) -> Result<Vec<String>, Error> {
+        cached_request::<Vec<String>>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/separator-tokens", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get the non-separator tokens of the Index, removed from the default separators.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let non_separator_tokens = movie_index.get_non_separator_tokens().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_non_separator_tokens(&self) -> Result<Vec<String>, Error> {
+        cached_request::<Vec<String>>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/non-separator-tokens", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get the proximity precision of the Index.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let proximity_precision = movie_index.get_proximity_precision().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_proximity_precision(&self) -> Result<ProximityPrecision, Error> {
+        cached_request::<ProximityPrecision>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/proximity-precision", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get the search cutoff of the Index, in milliseconds.
+    /// Returns `None` if no cutoff has been set, in which case the server uses its default.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let search_cutoff_ms = movie_index.get_search_cutoff_ms().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_search_cutoff_ms(&self) -> Result<Option<u64>, Error> {
+        cached_request::<Option<u64>>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/search-cutoff-ms", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
+    }
+
+    /// Get the [embedders](enum.Embedder.html) of the Index, by name.
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies").await.unwrap();
+    /// let embedders = movie_index.get_embedders().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_embedders(&self) -> Result<HashMap<String, Embedder>, Error> {
+        cached_request::<HashMap<String, Embedder>>(
+            &self.cache,
+            &format!("{}/indexes/{}/settings/embedders", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await
     }
 
     /// Update [settings](../settings/struct.Settings.html) of the index.
@@ -406,7 +1321,7 @@ impl Index {
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
@@ -421,21 +1336,73 @@ impl Index {
     /// # });
     /// ```
     pub async fn set_settings(&self, settings: &Settings) -> Result<Progress, Error> {
+        self.check_writable("set_settings")?;
         Ok(request::<&Settings, ProgressJson>(
             &format!("{}/indexes/{}/settings", self.host, self.uid),
             &self.api_key,
             Method::Post(settings),
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Update only the [settings](../settings/struct.Settings.html) that differ from the current settings of the index, as computed by [Settings::diff](../settings/struct.Settings.html#method.diff).
+    ///
+    /// Returns `None` without sending any update if nothing would change, so that the documents are not reindexed for nothing.
+    /// The current settings are always fetched from the server, even if the [cache](../client/struct.Client.html#method.with_cache) is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_update_settings_if_changed").await.unwrap();
+    /// let settings = Settings::new().with_stop_words(["the", "of"]);
+    ///
+    /// if let Some(progress) = movies.update_settings_if_changed(&settings).await.unwrap() {
+    ///     progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// }
+    /// assert!(movies.update_settings_if_changed(&settings).await.unwrap().is_none());
+    /// # client.delete_index("movies_update_settings_if_changed").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn update_settings_if_changed(&self, settings: &Settings) -> Result<Option<Progress>, Error> {
+        self.check_writable("update_settings_if_changed")?;
+        let current = request::<(), Settings>(
+            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await?;
+        let changes = settings.diff(&current);
+        if changes == Settings::new() {
+            return Ok(None);
+        }
+        Ok(Some(self.set_settings(&changes).await?))
+    }
+
+    /// Same as [set_settings](#method.set_settings), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_settings_and_wait(
+        &self,
+        settings: &Settings,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_settings(settings).await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Update [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
@@ -451,21 +1418,112 @@ impl Index {
     /// # });
     /// ```
     pub async fn set_synonyms(&self, synonyms: &HashMap<String, Vec<String>>) -> Result<Progress, Error> {
+        self.check_writable("set_synonyms")?;
         Ok(request::<&HashMap<String, Vec<String>>, ProgressJson>(
             &format!("{}/indexes/{}/settings/synonyms", self.host, self.uid),
             &self.api_key,
             Method::Post(synonyms),
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [set_synonyms](#method.set_synonyms), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_synonyms_and_wait(
+        &self,
+        synonyms: &HashMap<String, Vec<String>>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_synonyms(synonyms).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Add [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) to the index, keeping the current ones.
+    ///
+    /// The current synonyms are fetched from the server, even if the [cache](../client/struct.Client.html#method.with_cache) is enabled,
+    /// then the synonyms of each word are added to its current ones, and the result is sent with [set_synonyms](#method.set_synonyms).
+    /// The synonyms updated by someone else between both requests are overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, settings::Synonyms};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_add_synonyms").await.unwrap();
+    ///
+    /// let progress = movies.add_synonyms(&Synonyms::symmetric(["wolverine", "logan", "xmen"]).into_map()).await.unwrap();
+    /// # progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// # client.delete_index("movies_add_synonyms").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn add_synonyms(&self, synonyms: &HashMap<String, Vec<String>>) -> Result<Progress, Error> {
+        self.check_writable("add_synonyms")?;
+        let mut merged = self.get_current_synonyms().await?;
+        for (word, synonyms) in synonyms {
+            merge_synonyms(&mut merged, word, synonyms.clone());
+        }
+        self.set_synonyms(&merged).await
+    }
+
+    /// Same as [add_synonyms](#method.add_synonyms), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn add_synonyms_and_wait(
+        &self,
+        synonyms: &HashMap<String, Vec<String>>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.add_synonyms(synonyms).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Remove the [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) of `word`, keeping the other ones.
+    ///
+    /// Like [add_synonyms](#method.add_synonyms), the current synonyms are fetched, then sent back without `word`.
+    /// `word` is still a synonym of the words that list it.
+    pub async fn remove_synonym(&self, word: &str) -> Result<Progress, Error> {
+        self.check_writable("remove_synonym")?;
+        let mut synonyms = self.get_current_synonyms().await?;
+        synonyms.remove(word);
+        self.set_synonyms(&synonyms).await
+    }
+
+    /// Same as [remove_synonym](#method.remove_synonym), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn remove_synonym_and_wait(
+        &self,
+        word: &str,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.remove_synonym(word).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// The synonyms of the index, bypassing the cache since they are about to be modified.
+    async fn get_current_synonyms(&self) -> Result<HashMap<String, Vec<String>>, Error> {
+        request::<(), HashMap<String, Vec<String>>>(
+            &format!("{}/indexes/{}/settings/synonyms", self.host, self.uid),
+            &self.api_key,
+            Method::Get,
+            200,
+            self.max_response_bytes,
+        ).await
+    }
+
     /// Update [stop-words](https://docs.meilisearch.com/reference/features/stop_words.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
@@ -477,21 +1535,36 @@ impl Index {
     /// # });
     /// ```
     pub async fn set_stop_words(&self, stop_words: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_stop_words")?;
         Ok(request::<Vec<String>, ProgressJson>(
             &format!("{}/indexes/{}/settings/stop-words", self.host, self.uid),
             &self.api_key,
             Method::Post(stop_words.convert()),
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [set_stop_words](#method.set_stop_words), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_stop_words_and_wait(
+        &self,
+        stop_words: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_stop_words(stop_words).await?.wait_until_processed(interval, timeout).await
+    }
+
     /// Update [ranking rules](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
@@ -512,21 +1585,65 @@ impl Index {
     /// # });
     /// ```
     pub async fn set_ranking_rules(&self, ranking_rules: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_ranking_rules")?;
         Ok(request::<Vec<String>, ProgressJson>(
             &format!("{}/indexes/{}/settings/ranking-rules", self.host, self.uid),
             &self.api_key,
             Method::Post(ranking_rules.convert()),
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
+    /// Same as [set_ranking_rules](#method.set_ranking_rules), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_ranking_rules_and_wait(
+        &self,
+        ranking_rules: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_ranking_rules(ranking_rules).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the [ranking rules](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules) of the index from their current value.
+    ///
+    /// The rules are fetched, modified by `update` and sent back.
+    /// Returns [Error::InvalidRequest](../errors/enum.Error.html#variant.InvalidRequest) without sending anything if a built-in rule appears twice,
+    /// see [RankingRules::validate](../settings/struct.RankingRules.html#method.validate).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, indexes::*, settings::RankingRule};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movie_index = client.get_or_create("movies_update_ranking_rules_with").await.unwrap();
+    ///
+    /// let progress = movie_index.update_ranking_rules_with(|rules| {
+    ///     rules.move_before(&RankingRule::Sort, &RankingRule::Typo).unwrap();
+    /// }).await.unwrap();
+    /// # progress.wait_for_pending_update(None, None).await.unwrap().unwrap();
+    /// # client.delete_index("movies_update_ranking_rules_with").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn update_ranking_rules_with(&self, update: impl FnOnce(&mut RankingRules)) -> Result<Progress, Error> {
+        self.check_writable("set_ranking_rules")?;
+        let mut ranking_rules = RankingRules::from(self.get_ranking_rules().await?);
+        update(&mut ranking_rules);
+        ranking_rules.validate(None)?;
+        self.set_ranking_rules(ranking_rules).await
+    }
+
     /// Update [filterable attributes](https://docs.meilisearch.com/reference/features/filtering_and_faceted_search.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
@@ -538,340 +1655,1707 @@ impl Index {
     /// # });
     /// ```
     pub async fn set_filterable_attributes(&self, filterable_attributes: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_filterable_attributes")?;
         Ok(request::<Vec<String>, ProgressJson>(
             &format!("{}/indexes/{}/settings/filterable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Post(filterable_attributes.convert()),
+            Method::Post(filterable_attributes.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_filterable_attributes](#method.set_filterable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_filterable_attributes_and_wait(
+        &self,
+        filterable_attributes: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_filterable_attributes(filterable_attributes).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update [sortable attributes](https://docs.meilisearch.com/reference/features/sorting.html) of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let sortable_attributes = ["genre", "director"];
+    /// let progress = movie_index.set_sortable_attributes(&sortable_attributes).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_sortable_attributes(&self, sortable_attributes: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_sortable_attributes")?;
+        Ok(request::<Vec<String>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/sortable-attributes", self.host, self.uid),
+            &self.api_key,
+            Method::Post(sortable_attributes.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_sortable_attributes](#method.set_sortable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_sortable_attributes_and_wait(
+        &self,
+        sortable_attributes: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_sortable_attributes(sortable_attributes).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the [distinct attribute](https://docs.meilisearch.com/reference/features/settings.html#distinct-attribute) of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_distinct_attribute("movie_id").await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_distinct_attribute(&self, distinct_attribute: impl Into<String>) -> Result<Progress, Error> {
+        self.check_writable("set_distinct_attribute")?;
+        Ok(request::<String, ProgressJson>(
+            &format!("{}/indexes/{}/settings/distinct-attribute", self.host, self.uid),
+            &self.api_key,
+            Method::Post(distinct_attribute.into()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_distinct_attribute](#method.set_distinct_attribute), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_distinct_attribute_and_wait(
+        &self,
+        distinct_attribute: impl Into<String>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_distinct_attribute(distinct_attribute).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update [searchable attributes](https://docs.meilisearch.com/reference/features/field_properties.html#searchable-fields) of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_searchable_attributes(["title", "description", "uid"]).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_searchable_attributes(&self, searchable_attributes: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_searchable_attributes")?;
+        Ok(request::<Vec<String>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/searchable-attributes", self.host, self.uid),
+            &self.api_key,
+            Method::Post(searchable_attributes.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_searchable_attributes](#method.set_searchable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_searchable_attributes_and_wait(
+        &self,
+        searchable_attributes: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_searchable_attributes(searchable_attributes).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update [displayed attributes](https://docs.meilisearch.com/reference/features/settings.html#displayed-attributes) of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_displayed_attributes(["title", "description", "release_date", "rank", "poster"]).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_displayed_attributes(&self, displayed_attributes: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_displayed_attributes")?;
+        Ok(request::<Vec<String>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/displayed-attributes", self.host, self.uid),
+            &self.api_key,
+            Method::Post(displayed_attributes.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_displayed_attributes](#method.set_displayed_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_displayed_attributes_and_wait(
+        &self,
+        displayed_attributes: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_displayed_attributes(displayed_attributes).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update [typo tolerance](https://docs.meilisearch.com/learn/configuration/typo_tolerance.html) settings of the index.
+    /// The update is partial: the fields set to `None` are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{client::*, indexes::*, settings::{MinWordSizeForTypos, TypoToleranceSettings}};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let typo_tolerance = TypoToleranceSettings::new()
+    ///     .with_min_word_size_for_typos(MinWordSizeForTypos { one_typo: Some(7), two_typos: None });
+    /// let progress = movie_index.set_typo_tolerance(&typo_tolerance).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_typo_tolerance(&self, typo_tolerance: &TypoToleranceSettings) -> Result<Progress, Error> {
+        self.check_writable("set_typo_tolerance")?;
+        Ok(request::<&TypoToleranceSettings, ProgressJson>(
+            &format!("{}/indexes/{}/settings/typo-tolerance", self.host, self.uid),
+            &self.api_key,
+            Method::Patch(typo_tolerance),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_typo_tolerance](#method.set_typo_tolerance), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_typo_tolerance_and_wait(
+        &self,
+        typo_tolerance: &TypoToleranceSettings,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_typo_tolerance(typo_tolerance).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the separator tokens of the index, splitting the text into words in addition to the default separators.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_separator_tokens(["|", "&hellip;"]).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_separator_tokens(&self, separator_tokens: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_separator_tokens")?;
+        Ok(request::<Vec<String>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/separator-tokens", self.host, self.uid),
+            &self.api_key,
+            Method::Put(separator_tokens.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_separator_tokens](#method.set_separator_tokens), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_separator_tokens_and_wait(
+        &self,
+        separator_tokens: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_separator_tokens(separator_tokens).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the non-separator tokens of the index, removed from the default separators.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_non_separator_tokens(["@", "#"]).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_non_separator_tokens(&self, non_separator_tokens: impl IntoVecString) -> Result<Progress, Error> {
+        self.check_writable("set_non_separator_tokens")?;
+        Ok(request::<Vec<String>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/non-separator-tokens", self.host, self.uid),
+            &self.api_key,
+            Method::Put(non_separator_tokens.convert()),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_non_separator_tokens](#method.set_non_separator_tokens), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_non_separator_tokens_and_wait(
+        &self,
+        non_separator_tokens: impl IntoVecString,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_non_separator_tokens(non_separator_tokens).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the proximity precision of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, settings::ProximityPrecision};
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_proximity_precision(ProximityPrecision::ByAttribute).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_proximity_precision(&self, proximity_precision: ProximityPrecision) -> Result<Progress, Error> {
+        self.check_writable("set_proximity_precision")?;
+        Ok(request::<ProximityPrecision, ProgressJson>(
+            &format!("{}/indexes/{}/settings/proximity-precision", self.host, self.uid),
+            &self.api_key,
+            Method::Put(proximity_precision),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_proximity_precision](#method.set_proximity_precision), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_proximity_precision_and_wait(
+        &self,
+        proximity_precision: ProximityPrecision,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_proximity_precision(proximity_precision).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the search cutoff of the index, in milliseconds.
+    /// Searches taking longer than that return the results found so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.set_search_cutoff_ms(150).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_search_cutoff_ms(&self, search_cutoff_ms: u64) -> Result<Progress, Error> {
+        self.check_writable("set_search_cutoff_ms")?;
+        Ok(request::<u64, ProgressJson>(
+            &format!("{}/indexes/{}/settings/search-cutoff-ms", self.host, self.uid),
+            &self.api_key,
+            Method::Put(search_cutoff_ms),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_search_cutoff_ms](#method.set_search_cutoff_ms), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_search_cutoff_ms_and_wait(
+        &self,
+        search_cutoff_ms: u64,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_search_cutoff_ms(search_cutoff_ms).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Update the [embedders](enum.Embedder.html) of the index.
+    /// The update is partial: the embedders that are not given are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::{prelude::*, settings::Embedder};
+    /// # use std::collections::HashMap;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let mut embedders = HashMap::new();
+    /// embedders.insert("default".to_string(), Embedder::UserProvided { dimensions: 3 });
+    /// let progress = movie_index.set_embedders(&embedders).await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_embedders(&self, embedders: &HashMap<String, Embedder>) -> Result<Progress, Error> {
+        self.check_writable("set_embedders")?;
+        Ok(request::<&HashMap<String, Embedder>, ProgressJson>(
+            &format!("{}/indexes/{}/settings/embedders", self.host, self.uid),
+            &self.api_key,
+            Method::Patch(embedders),
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [set_embedders](#method.set_embedders), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn set_embedders_and_wait(
+        &self,
+        embedders: &HashMap<String, Embedder>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.set_embedders(embedders).await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [settings](../settings/struct.Settings.html) of the index.
+    /// All settings will be reset to their [default value](https://docs.meilisearch.com/reference/api/settings.html#reset-settings).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.reset_settings().await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn reset_settings(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_settings")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &self.api_key,
+            Method::Delete,
+            202,
+            self.max_response_bytes,
+        ).await?
+        .into_progress(self))
+    }
+
+    /// Same as [reset_settings](#method.reset_settings), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_settings_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_settings().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let mut movie_index = client.get_or_create("movies").await.unwrap();
+    ///
+    /// let progress = movie_index.reset_synonyms().await.unwrap();
+    /// # std::thread::sleep(std::time::Duration::from_secs(2));
+    /// # progress.get_status().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn reset_synonyms(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_synonyms")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings/synonyms", self.host, self.uid),
+            &self.api_key,
+            Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Update [sortable attributes](https://docs.meilisearch.com/reference/features/sorting.html) of the index.
+    /// Same as [reset_synonyms](#method.reset_synonyms), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_synonyms_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_synonyms().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [stop-words](https://docs.meilisearch.com/reference/features/stop_words.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let sortable_attributes = ["genre", "director"];
-    /// let progress = movie_index.set_sortable_attributes(&sortable_attributes).await.unwrap();
+    /// let progress = movie_index.reset_stop_words().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn set_sortable_attributes(&self, sortable_attributes: impl IntoVecString) -> Result<Progress, Error> {
-        Ok(request::<Vec<String>, ProgressJson>(
-            &format!("{}/indexes/{}/settings/sortable-attributes", self.host, self.uid),
+    pub async fn reset_stop_words(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_stop_words")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings/stop-words", self.host, self.uid),
             &self.api_key,
-            Method::Post(sortable_attributes.convert()),
+            Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Update the [distinct attribute](https://docs.meilisearch.com/reference/features/settings.html#distinct-attribute) of the index.
+    /// Same as [reset_stop_words](#method.reset_stop_words), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_stop_words_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_stop_words().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [ranking rules](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules) of the index to default value.
+    /// Default value: ["words", "typo", "proximity", "attribute", "sort", "exactness"].
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.set_distinct_attribute("movie_id").await.unwrap();
+    /// let progress = movie_index.reset_ranking_rules().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn set_distinct_attribute(&self, distinct_attribute: impl Into<String>) -> Result<Progress, Error> {
-        Ok(request::<String, ProgressJson>(
-            &format!("{}/indexes/{}/settings/distinct-attribute", self.host, self.uid),
+    pub async fn reset_ranking_rules(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_ranking_rules")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings/ranking-rules", self.host, self.uid),
             &self.api_key,
-            Method::Post(distinct_attribute.into()),
+            Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Update [searchable attributes](https://docs.meilisearch.com/reference/features/field_properties.html#searchable-fields) of the index.
+    /// Same as [reset_ranking_rules](#method.reset_ranking_rules), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_ranking_rules_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_ranking_rules().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [filterable attributes]https://docs.meilisearch.com/reference/features/filtering_and_faceted_search.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.set_searchable_attributes(["title", "description", "uid"]).await.unwrap();
+    /// let progress = movie_index.reset_filterable_attributes().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn set_searchable_attributes(&self, searchable_attributes: impl IntoVecString) -> Result<Progress, Error> {
-        Ok(request::<Vec<String>, ProgressJson>(
-            &format!("{}/indexes/{}/settings/searchable-attributes", self.host, self.uid),
+    pub async fn reset_filterable_attributes(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_filterable_attributes")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings/filterable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Post(searchable_attributes.convert()),
+            Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Update [displayed attributes](https://docs.meilisearch.com/reference/features/settings.html#displayed-attributes) of the index.
+    /// Same as [reset_filterable_attributes](#method.reset_filterable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_filterable_attributes_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_filterable_attributes().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [sortable attributes]https://docs.meilisearch.com/reference/features/sorting.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.set_displayed_attributes(["title", "description", "release_date", "rank", "poster"]).await.unwrap();
+    /// let progress = movie_index.reset_sortable_attributes().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn set_displayed_attributes(&self, displayed_attributes: impl IntoVecString) -> Result<Progress, Error> {
-        Ok(request::<Vec<String>, ProgressJson>(
-            &format!("{}/indexes/{}/settings/displayed-attributes", self.host, self.uid),
+    pub async fn reset_sortable_attributes(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_sortable_attributes")?;
+        Ok(request::<(), ProgressJson>(
+            &format!("{}/indexes/{}/settings/sortable-attributes", self.host, self.uid),
             &self.api_key,
-            Method::Post(displayed_attributes.convert()),
+            Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [settings](../settings/struct.Settings.html) of the index.
-    /// All settings will be reset to their [default value](https://docs.meilisearch.com/reference/api/settings.html#reset-settings).
+    /// Same as [reset_sortable_attributes](#method.reset_sortable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_sortable_attributes_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_sortable_attributes().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset the [distinct attribute](https://docs.meilisearch.com/reference/features/settings.html#distinct-attribute) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_settings().await.unwrap();
+    /// let progress = movie_index.reset_distinct_attribute().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_settings(&self) -> Result<Progress, Error> {
+    pub async fn reset_distinct_attribute(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_distinct_attribute")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/distinct-attribute", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [synonyms](https://docs.meilisearch.com/reference/features/synonyms.html) of the index.
+    /// Same as [reset_distinct_attribute](#method.reset_distinct_attribute), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_distinct_attribute_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_distinct_attribute().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [searchable attributes](https://docs.meilisearch.com/reference/features/field_properties.html#searchable-fields) of the index (enable all attributes).
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_synonyms().await.unwrap();
+    /// let progress = movie_index.reset_searchable_attributes().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_synonyms(&self) -> Result<Progress, Error> {
+    pub async fn reset_searchable_attributes(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_searchable_attributes")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/synonyms", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/searchable-attributes", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [stop-words](https://docs.meilisearch.com/reference/features/stop_words.html) of the index.
+    /// Same as [reset_searchable_attributes](#method.reset_searchable_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_searchable_attributes_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_searchable_attributes().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [displayed attributes](https://docs.meilisearch.com/reference/features/settings.html#displayed-attributes) of the index (enable all attributes).
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_stop_words().await.unwrap();
+    /// let progress = movie_index.reset_displayed_attributes().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_stop_words(&self) -> Result<Progress, Error> {
+    pub async fn reset_displayed_attributes(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_displayed_attributes")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/stop-words", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/displayed-attributes", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [ranking rules](https://docs.meilisearch.com/learn/core_concepts/relevancy.html#ranking-rules) of the index to default value.
-    /// Default value: ["words", "typo", "proximity", "attribute", "sort", "exactness"].
+    /// Same as [reset_displayed_attributes](#method.reset_displayed_attributes), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_displayed_attributes_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_displayed_attributes().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset [typo tolerance](https://docs.meilisearch.com/learn/configuration/typo_tolerance.html) settings of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_ranking_rules().await.unwrap();
+    /// let progress = movie_index.reset_typo_tolerance().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_ranking_rules(&self) -> Result<Progress, Error> {
+    pub async fn reset_typo_tolerance(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_typo_tolerance")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/ranking-rules", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/typo-tolerance", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [filterable attributes]https://docs.meilisearch.com/reference/features/filtering_and_faceted_search.html) of the index.
+    /// Same as [reset_typo_tolerance](#method.reset_typo_tolerance), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_typo_tolerance_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_typo_tolerance().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset the separator tokens of the index, leaving only the default separators.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_filterable_attributes().await.unwrap();
+    /// let progress = movie_index.reset_separator_tokens().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_filterable_attributes(&self) -> Result<Progress, Error> {
+    pub async fn reset_separator_tokens(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_separator_tokens")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/filterable-attributes", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/separator-tokens", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [sortable attributes]https://docs.meilisearch.com/reference/features/sorting.html) of the index.
+    /// Same as [reset_separator_tokens](#method.reset_separator_tokens), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_separator_tokens_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_separator_tokens().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset the non-separator tokens of the index, restoring all the default separators.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_sortable_attributes().await.unwrap();
+    /// let progress = movie_index.reset_non_separator_tokens().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_sortable_attributes(&self) -> Result<Progress, Error> {
+    pub async fn reset_non_separator_tokens(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_non_separator_tokens")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/sortable-attributes", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/non-separator-tokens", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset the [distinct attribute](https://docs.meilisearch.com/reference/features/settings.html#distinct-attribute) of the index.
+    /// Same as [reset_non_separator_tokens](#method.reset_non_separator_tokens), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_non_separator_tokens_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_non_separator_tokens().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset the proximity precision of the index to [ByWord](enum.ProximityPrecision.html#variant.ByWord).
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_distinct_attribute().await.unwrap();
+    /// let progress = movie_index.reset_proximity_precision().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_distinct_attribute(&self) -> Result<Progress, Error> {
+    pub async fn reset_proximity_precision(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_proximity_precision")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/distinct-attribute", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/proximity-precision", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [searchable attributes](https://docs.meilisearch.com/reference/features/field_properties.html#searchable-fields) of the index (enable all attributes).
+    /// Same as [reset_proximity_precision](#method.reset_proximity_precision), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_proximity_precision_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_proximity_precision().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Reset the search cutoff of the index, so that the server default is used.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_searchable_attributes().await.unwrap();
+    /// let progress = movie_index.reset_search_cutoff_ms().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_searchable_attributes(&self) -> Result<Progress, Error> {
+    pub async fn reset_search_cutoff_ms(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_search_cutoff_ms")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/searchable-attributes", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/search-cutoff-ms", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
 
-    /// Reset [displayed attributes](https://docs.meilisearch.com/reference/features/settings.html#displayed-attributes) of the index (enable all attributes).
+    /// Same as [reset_search_cutoff_ms](#method.reset_search_cutoff_ms), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_search_cutoff_ms_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_search_cutoff_ms().await?.wait_until_processed(interval, timeout).await
+    }
+
+    /// Remove all the [embedders](enum.Embedder.html) of the index.
     ///
     /// # Example
     ///
     /// ```
-    /// # use meilisearch_sdk::{client::*, indexes::*, document::*, settings::Settings};
+    /// # use meilisearch_sdk::prelude::*;
     /// # futures::executor::block_on(async move {
     /// let client = Client::new("http://localhost:7700", "masterKey");
     /// let mut movie_index = client.get_or_create("movies").await.unwrap();
     ///
-    /// let progress = movie_index.reset_displayed_attributes().await.unwrap();
+    /// let progress = movie_index.reset_embedders().await.unwrap();
     /// # std::thread::sleep(std::time::Duration::from_secs(2));
     /// # progress.get_status().await.unwrap();
     /// # });
     /// ```
-    pub async fn reset_displayed_attributes(&self) -> Result<Progress, Error> {
+    pub async fn reset_embedders(&self) -> Result<Progress, Error> {
+        self.check_writable("reset_embedders")?;
         Ok(request::<(), ProgressJson>(
-            &format!("{}/indexes/{}/settings/displayed-attributes", self.host, self.uid),
+            &format!("{}/indexes/{}/settings/embedders", self.host, self.uid),
             &self.api_key,
             Method::Delete,
             202,
+            self.max_response_bytes,
         ).await?
         .into_progress(self))
     }
+
+    /// Same as [reset_embedders](#method.reset_embedders), but waits until MeiliSearch processes the update.
+    ///
+    /// `interval` and `timeout` are the same as in [wait_for_pending_update](../progress/struct.Progress.html#method.wait_for_pending_update).
+    /// Returns an error if the update failed or if it was not processed before `timeout`.
+    pub async fn reset_embedders_and_wait(
+        &self,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> Result<ProcessedUpdateResult, Error> {
+        self.reset_embedders().await?.wait_until_processed(interval, timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::*, tests::TestIndex};
+    use futures_await_test::async_test;
+    use serde_json::json;
+
+    fn server_settings() -> Value {
+        json!({
+            "displayedAttributes": ["*"],
+            "searchableAttributes": ["*"],
+            "filterableAttributes": ["genre", "year"],
+            "sortableAttributes": [],
+            "rankingRules": ["words", "typo", "proximity", "attribute", "sort", "exactness"],
+            "stopWords": ["of", "the"],
+            "synonyms": {"wolverine": ["logan", "xmen"]},
+            "distinctAttribute": null
+        })
+    }
+
+    #[test]
+    fn test_capabilities_wildcard() {
+        let settings: Settings = serde_json::from_value(json!({"filterableAttributes": ["*"], "sortableAttributes": []})).unwrap();
+        let capabilities = IndexCapabilities::from_settings(settings);
+        assert!(capabilities.can_filter_on("genre"));
+        assert!(capabilities.can_filter_on("release.year"));
+        assert!(!capabilities.can_sort_on("genre"));
+        assert!(capabilities.distinct_attribute.is_none());
+    }
+
+    #[test]
+    fn test_capabilities_nested_paths() {
+        let settings: Settings = serde_json::from_value(server_settings()).unwrap();
+        let mut capabilities = IndexCapabilities::from_settings(settings);
+        assert_eq!(capabilities.ranking_rules[0], "words");
+        assert!(capabilities.can_filter_on("genre"));
+        assert!(!capabilities.can_filter_on("genres"));
+        assert!(!capabilities.can_filter_on("title"));
+
+        capabilities.filterable_attributes = ["address", "release.year"].iter().map(|s| s.to_string()).collect();
+        // Fields nested in a filterable object are filterable
+        assert!(capabilities.can_filter_on("address"));
+        assert!(capabilities.can_filter_on("address.city"));
+        assert!(capabilities.can_filter_on("address.city.zip"));
+        assert!(!capabilities.can_filter_on("addresses.city"));
+        assert!(!capabilities.can_filter_on("addressbook"));
+        // But the parents of a filterable field are not
+        assert!(capabilities.can_filter_on("release.year"));
+        assert!(!capabilities.can_filter_on("release"));
+        assert!(!capabilities.can_filter_on("release.month"));
+    }
+
+    #[async_test]
+    async fn test_capabilities() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_capabilities").await;
+        let settings = Settings::new()
+            .with_filterable_attributes(["genre", "release"])
+            .with_sortable_attributes(["year"])
+            .with_distinct_attribute("title");
+        index.set_settings_and_wait(&settings, None, None).await.unwrap();
+
+        let capabilities = index.capabilities().await.unwrap();
+        assert!(capabilities.can_filter_on("release.year"));
+        assert!(capabilities.can_sort_on("year"));
+        assert!(!capabilities.can_sort_on("genre"));
+        assert_eq!(capabilities.distinct_attribute.as_deref(), Some("title"));
+        assert!(capabilities.ranking_rules.contains(&"sort".to_string()));
+    }
+
+    fn default_rules() -> RankingRules {
+        RankingRules::from(["words", "typo", "proximity", "attribute", "sort", "exactness"])
+    }
+
+    #[test]
+    fn test_ranking_rule_strings() {
+        let rules = RankingRules::from(["words", "release_date:asc", "rank:desc", "attributeRank"]);
+        assert_eq!(rules.0, vec![
+            RankingRule::Words,
+            RankingRule::Asc("release_date".to_string()),
+            RankingRule::Desc("rank".to_string()),
+            RankingRule::Other("attributeRank".to_string()),
+        ]);
+        assert_eq!(rules.to_strings(), ["words", "release_date:asc", "rank:desc", "attributeRank"]);
+        assert_eq!(serde_json::to_value(&rules).unwrap(), json!(["words", "release_date:asc", "rank:desc", "attributeRank"]));
+        assert_eq!(serde_json::from_value::<RankingRules>(json!(["words", "rank:desc"])).unwrap(), RankingRules::from(["words", "rank:desc"]));
+    }
+
+    #[test]
+    fn test_move_ranking_rule() {
+        let mut rules = default_rules();
+        rules.move_before(&RankingRule::Sort, &RankingRule::Typo).unwrap();
+        assert_eq!(rules.to_strings(), ["words", "sort", "typo", "proximity", "attribute", "exactness"]);
+
+        // Moving a rule down
+        rules.move_before(&RankingRule::Words, &RankingRule::Exactness).unwrap();
+        assert_eq!(rules.to_strings(), ["sort", "typo", "proximity", "attribute", "words", "exactness"]);
+
+        // Already in place, or before itself
+        rules.move_before(&RankingRule::Words, &RankingRule::Exactness).unwrap();
+        rules.move_before(&RankingRule::Typo, &RankingRule::Typo).unwrap();
+        assert_eq!(rules.to_strings(), ["sort", "typo", "proximity", "attribute", "words", "exactness"]);
+
+        let rank = RankingRule::Desc("rank".to_string());
+        assert!(matches!(rules.move_before(&rank, &RankingRule::Typo), Err(Error::InvalidRequest)));
+        assert!(matches!(rules.move_before(&RankingRule::Typo, &rank), Err(Error::InvalidRequest)));
+    }
+
+    #[test]
+    fn test_insert_custom_ranking_rule() {
+        let mut rules = default_rules();
+        rules.insert_custom(RankingRule::Asc("price".to_string()), 0).unwrap();
+        rules.insert_custom(RankingRule::Desc("rank".to_string()), 7).unwrap();
+        assert_eq!(rules.position_of(&RankingRule::Asc("price".to_string())), Some(0));
+        assert_eq!(rules.position_of(&RankingRule::Desc("rank".to_string())), Some(7));
+        assert_eq!(rules.position_of(&RankingRule::Desc("price".to_string())), None);
+
+        assert!(matches!(rules.insert_custom(RankingRule::Words, 0), Err(Error::InvalidRequest)));
+        assert!(matches!(rules.insert_custom(RankingRule::Asc("price".to_string()), 1), Err(Error::InvalidRequest)));
+        assert!(matches!(rules.insert_custom(RankingRule::Asc("year".to_string()), 9), Err(Error::InvalidRequest)));
+        assert_eq!(rules.0.len(), 8);
+    }
+
+    #[test]
+    fn test_validate_ranking_rules() {
+        let mut rules = default_rules();
+        rules.insert_custom(RankingRule::Desc("year".to_string()), 6).unwrap();
+        rules.validate(None).unwrap();
+
+        let settings: Settings = serde_json::from_value(json!({"sortableAttributes": ["release"]})).unwrap();
+        let capabilities = IndexCapabilities::from_settings(settings);
+        assert!(matches!(rules.validate(Some(&capabilities)), Err(Error::InvalidRequest)));
+        let settings: Settings = serde_json::from_value(json!({"sortableAttributes": ["year"]})).unwrap();
+        rules.validate(Some(&IndexCapabilities::from_settings(settings))).unwrap();
+
+        rules.0.push(RankingRule::Typo);
+        assert!(matches!(rules.validate(None), Err(Error::InvalidRequest)));
+    }
+
+    #[async_test]
+    async fn test_update_ranking_rules_with() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_update_ranking_rules_with").await;
+        index.set_sortable_attributes_and_wait(["year"], None, None).await.unwrap();
+
+        let progress = index.update_ranking_rules_with(|rules| {
+            rules.move_before(&RankingRule::Sort, &RankingRule::Typo).unwrap();
+            rules.insert_custom(RankingRule::Desc("year".to_string()), rules.0.len()).unwrap();
+        }).await.unwrap();
+        progress.wait_until_processed(None, None).await.unwrap();
+
+        let rules = index.get_ranking_rules().await.unwrap();
+        assert_eq!(rules, ["words", "sort", "typo", "proximity", "attribute", "exactness", "year:desc"]);
+
+        // Invalid rules are not sent
+        let result = index.update_ranking_rules_with(|rules| rules.0.push(RankingRule::Words)).await;
+        assert!(matches!(result, Err(Error::InvalidRequest)));
+    }
+
+    /// Property tests checking that settings survive a serialization round trip.
+    #[cfg(not(target_arch = "wasm32"))]
+    mod serialization {
+        use super::*;
+        use proptest::{collection::{hash_map, vec}, option, prelude::*};
+
+        fn words() -> impl Strategy<Value = Option<Vec<String>>> {
+            option::of(vec("[a-z][a-z_.]{0,8}|\\*", 0..4))
+        }
+
+        prop_compose! {
+            fn settings()(
+                synonyms in option::of(hash_map("[a-z]{1,8}", vec("[a-z]{1,8}", 0..3), 0..3)),
+                stop_words in words(),
+                ranking_rules in option::of(vec("words|typo|proximity|attribute|sort|exactness|[a-z]{1,8}:(asc|desc)", 0..6)),
+                filterable_attributes in words(),
+                sortable_attributes in words(),
+                distinct_attribute in option::of("[a-z]{1,8}"),
+                searchable_attributes in words(),
+                displayed_attributes in words(),
+                typo_tolerance in option::of(typo_tolerance()),
+                separator_tokens in option::of(vec("[|&;:/-]{1,3}", 0..3)),
+                non_separator_tokens in option::of(vec("[@#._-]{1,3}", 0..3)),
+                proximity_precision in option::of(prop_oneof![Just(ProximityPrecision::ByWord), Just(ProximityPrecision::ByAttribute)]),
+                search_cutoff_ms in option::of(any::<u64>()),
+                embedders in option::of(hash_map("[a-z]{1,8}", embedder(), 0..3)),
+                extra in hash_map("future[A-Z][a-z]{1,8}", prop_oneof![any::<bool>().prop_map(Value::from), "[a-z]{0,8}".prop_map(Value::from)], 0..3),
+            ) -> Settings {
+                // No `..`: new fields have to be generated
+                Settings {
+                    synonyms,
+                    stop_words,
+                    ranking_rules,
+                    filterable_attributes,
+                    sortable_attributes,
+                    distinct_attribute,
+                    searchable_attributes,
+                    displayed_attributes,
+                    typo_tolerance,
+                    separator_tokens,
+                    non_separator_tokens,
+                    proximity_precision,
+                    search_cutoff_ms,
+                    embedders,
+                    extra,
+                }
+            }
+        }
+
+        fn embedder() -> impl Strategy<Value = Embedder> {
+            let text = || option::of("[a-z{}. -]{0,12}");
+            prop_oneof![
+                (text(), text(), text(), option::of(1..4096usize)).prop_map(|(model, api_key, document_template, dimensions)| {
+                    Embedder::OpenAi { model, api_key, document_template, dimensions }
+                }),
+                (text(), text(), text()).prop_map(|(model, revision, document_template)| {
+                    Embedder::HuggingFace { model, revision, document_template }
+                }),
+                (text(), text(), text(), text(), option::of(1..4096usize)).prop_map(|(url, api_key, model, document_template, dimensions)| {
+                    Embedder::Ollama { url, api_key, model, document_template, dimensions }
+                }),
+                ("[a-z:/.]{1,12}", option::of(hash_map("[A-Za-z-]{1,8}", "[a-z ]{0,8}", 0..3))).prop_map(|(url, headers)| {
+                    Embedder::Rest { url, request: json!({"input": "{{text}}"}), response: json!({"embedding": "{{embedding}}"}), headers }
+                }),
+                (1..4096usize).prop_map(|dimensions| Embedder::UserProvided { dimensions }),
+            ]
+        }
+
+        prop_compose! {
+            fn typo_tolerance()(
+                enabled in option::of(any::<bool>()),
+                min_word_size_for_typos in option::of((option::of(0..10u8), option::of(0..20u8))),
+                disable_on_words in words(),
+                disable_on_attributes in words(),
+            ) -> TypoToleranceSettings {
+                TypoToleranceSettings {
+                    enabled,
+                    min_word_size_for_typos: min_word_size_for_typos.map(|(one_typo, two_typos)| MinWordSizeForTypos { one_typo, two_typos }),
+                    disable_on_words,
+                    disable_on_attributes,
+                }
+            }
+        }
+
+        /// The keys that should be serialized for `settings`.
+        ///
+        /// The settings are destructured without `..`, so that a new field doesn't compile until it is registered here.
+        fn expected_keys(settings: &Settings) -> Vec<&str> {
+            let Settings {
+                synonyms,
+                stop_words,
+                ranking_rules,
+                filterable_attributes,
+                sortable_attributes,
+                distinct_attribute,
+                searchable_attributes,
+                displayed_attributes,
+                typo_tolerance,
+                separator_tokens,
+                non_separator_tokens,
+                proximity_precision,
+                search_cutoff_ms,
+                embedders,
+                extra,
+            } = settings;
+            let keys = [
+                ("synonyms", synonyms.is_some()),
+                ("stopWords", stop_words.is_some()),
+                ("rankingRules", ranking_rules.is_some()),
+                ("filterableAttributes", filterable_attributes.is_some()),
+                ("sortableAttributes", sortable_attributes.is_some()),
+                ("distinctAttribute", distinct_attribute.is_some()),
+                ("searchableAttributes", searchable_attributes.is_some()),
+                ("displayedAttributes", displayed_attributes.is_some()),
+                ("typoTolerance", typo_tolerance.is_some()),
+                ("separatorTokens", separator_tokens.is_some()),
+                ("nonSeparatorTokens", non_separator_tokens.is_some()),
+                ("proximityPrecision", proximity_precision.is_some()),
+                ("searchCutoffMs", search_cutoff_ms.is_some()),
+                ("embedders", embedders.is_some()),
+            ];
+            let mut keys: Vec<&str> = keys.iter().filter(|(_, set)| *set).map(|(key, _)| *key).collect();
+            keys.extend(extra.keys().map(String::as_str));
+            keys.sort_unstable();
+            keys
+        }
+
+        proptest! {
+            #[test]
+            fn test_settings_serialization(settings in settings()) {
+                let value = serde_json::to_value(&settings).unwrap();
+                let body = value.as_object().unwrap();
+                let mut keys: Vec<&str> = body.keys().map(String::as_str).collect();
+                keys.sort_unstable();
+                prop_assert_eq!(keys, expected_keys(&settings));
+                for (key, value) in body {
+                    prop_assert!(key.chars().all(|c| c.is_ascii_alphanumeric()), "`{}` is not in camelCase", key);
+                    prop_assert!(!value.is_null(), "`{}` is null", key);
+                }
+
+                let deserialized: Settings = serde_json::from_value(value).unwrap();
+                prop_assert_eq!(deserialized, settings);
+            }
+        }
+    }
+
+    #[test]
+    fn test_typo_tolerance_serialization() {
+        let settings = Settings::new().with_typo_tolerance(
+            TypoToleranceSettings::new()
+                .with_min_word_size_for_typos(MinWordSizeForTypos { one_typo: Some(7), two_typos: None })
+                .with_disable_on_words(["skywalker"]),
+        );
+        assert_eq!(
+            serde_json::to_value(&settings).unwrap(),
+            json!({"typoTolerance": {"minWordSizeForTypos": {"oneTypo": 7}, "disableOnWords": ["skywalker"]}})
+        );
+
+        let typo_tolerance: TypoToleranceSettings = serde_json::from_value(json!({
+            "enabled": true,
+            "minWordSizeForTypos": {"oneTypo": 5, "twoTypos": 9},
+            "disableOnWords": [],
+            "disableOnAttributes": []
+        })).unwrap();
+        assert_eq!(typo_tolerance.min_word_size_for_typos, Some(MinWordSizeForTypos { one_typo: Some(5), two_typos: Some(9) }));
+    }
+
+    #[async_test]
+    async fn test_typo_tolerance() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_typo_tolerance").await;
+
+        let typo_tolerance = TypoToleranceSettings::new()
+            .with_min_word_size_for_typos(MinWordSizeForTypos { one_typo: Some(7), two_typos: None })
+            .with_disable_on_attributes(["isbn"]);
+        index.set_typo_tolerance_and_wait(&typo_tolerance, None, None).await.unwrap();
+        let typo_tolerance = index.get_typo_tolerance().await.unwrap();
+        assert_eq!(typo_tolerance.enabled, Some(true));
+        assert_eq!(typo_tolerance.min_word_size_for_typos, Some(MinWordSizeForTypos { one_typo: Some(7), two_typos: Some(9) }));
+        assert_eq!(typo_tolerance.disable_on_attributes, Some(vec!["isbn".to_string()]));
+
+        index.reset_typo_tolerance_and_wait(None, None).await.unwrap();
+        let typo_tolerance = index.get_typo_tolerance().await.unwrap();
+        assert_eq!(typo_tolerance.min_word_size_for_typos, Some(MinWordSizeForTypos { one_typo: Some(5), two_typos: Some(9) }));
+        assert_eq!(typo_tolerance.disable_on_attributes, Some(vec![]));
+    }
+
+    #[async_test]
+    async fn test_separator_tokens() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_separator_tokens").await;
+
+        index.set_separator_tokens_and_wait(["|"], None, None).await.unwrap();
+        assert_eq!(index.get_separator_tokens().await.unwrap(), ["|"]);
+
+        index.reset_separator_tokens_and_wait(None, None).await.unwrap();
+        assert!(index.get_separator_tokens().await.unwrap().is_empty());
+    }
+
+    #[async_test]
+    async fn test_non_separator_tokens() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_non_separator_tokens").await;
+
+        index.set_non_separator_tokens_and_wait(["@"], None, None).await.unwrap();
+        assert_eq!(index.get_non_separator_tokens().await.unwrap(), ["@"]);
+
+        index.reset_non_separator_tokens_and_wait(None, None).await.unwrap();
+        assert!(index.get_non_separator_tokens().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_proximity_precision_serialization() {
+        assert_eq!(serde_json::to_value(ProximityPrecision::ByWord).unwrap(), "byWord");
+        assert_eq!(serde_json::to_value(ProximityPrecision::ByAttribute).unwrap(), "byAttribute");
+        let settings = Settings::new().with_proximity_precision(ProximityPrecision::ByAttribute);
+        assert_eq!(serde_json::to_value(&settings).unwrap(), serde_json::json!({"proximityPrecision": "byAttribute"}));
+    }
+
+    #[async_test]
+    async fn test_proximity_precision() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_proximity_precision").await;
+
+        index.set_proximity_precision_and_wait(ProximityPrecision::ByAttribute, None, None).await.unwrap();
+        assert_eq!(index.get_proximity_precision().await.unwrap(), ProximityPrecision::ByAttribute);
+
+        index.reset_proximity_precision_and_wait(None, None).await.unwrap();
+        assert_eq!(index.get_proximity_precision().await.unwrap(), ProximityPrecision::ByWord);
+    }
+
+    #[test]
+    fn test_embedder_serialization() {
+        let embedder = Embedder::OpenAi {
+            model: Some("text-embedding-3-small".to_string()),
+            api_key: Some("sk-key".to_string()),
+            document_template: Some("{{doc.title}}".to_string()),
+            dimensions: Some(512),
+        };
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), json!({
+            "source": "openAi",
+            "model": "text-embedding-3-small",
+            "apiKey": "sk-key",
+            "documentTemplate": "{{doc.title}}",
+            "dimensions": 512
+        }));
+
+        let embedder = Embedder::HuggingFace { model: Some("BAAI/bge-base-en-v1.5".to_string()), revision: None, document_template: None };
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), json!({"source": "huggingFace", "model": "BAAI/bge-base-en-v1.5"}));
+
+        let embedder = Embedder::Ollama {
+            url: Some("http://localhost:11434/api/embeddings".to_string()),
+            api_key: None,
+            model: Some("nomic-embed-text".to_string()),
+            document_template: None,
+            dimensions: None,
+        };
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), json!({
+            "source": "ollama",
+            "url": "http://localhost:11434/api/embeddings",
+            "model": "nomic-embed-text"
+        }));
+
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer token".to_string());
+        let embedder = Embedder::Rest {
+            url: "http://localhost:8080/embed".to_string(),
+            request: json!({"input": "{{text}}"}),
+            response: json!({"data": "{{embedding}}"}),
+            headers: Some(headers),
+        };
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), json!({
+            "source": "rest",
+            "url": "http://localhost:8080/embed",
+            "request": {"input": "{{text}}"},
+            "response": {"data": "{{embedding}}"},
+            "headers": {"Authorization": "Bearer token"}
+        }));
+
+        let embedder = Embedder::UserProvided { dimensions: 3 };
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), json!({"source": "userProvided", "dimensions": 3}));
+        // Fields added by newer servers are ignored
+        let deserialized: Embedder = serde_json::from_value(json!({"source": "userProvided", "dimensions": 3, "binaryQuantized": false})).unwrap();
+        assert_eq!(deserialized, embedder);
+
+        // Sources added by newer servers are kept as is
+        let raw = json!({"source": "composite", "searchEmbedder": {"source": "userProvided", "dimensions": 3}, "indexingEmbedder": {"source": "userProvided", "dimensions": 3}});
+        let embedder: Embedder = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(embedder, Embedder::Unknown(raw.clone()));
+        assert_eq!(serde_json::to_value(&embedder).unwrap(), raw);
+
+        // Known sources with an invalid content are still errors
+        assert!(serde_json::from_value::<Embedder>(json!({"source": "userProvided"})).is_err());
+    }
+
+    #[async_test]
+    async fn test_embedders() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        if !client.check_compatibility(&[Capability::VectorSearch]).await.unwrap().is_compatible() {
+            return;
+        }
+        let index = TestIndex::new(&client, "test_embedders").await;
+        assert!(index.get_embedders().await.unwrap().is_empty());
+
+        let mut embedders = HashMap::new();
+        embedders.insert("default".to_string(), Embedder::UserProvided { dimensions: 3 });
+        index.set_embedders_and_wait(&embedders, None, None).await.unwrap();
+        assert_eq!(index.get_embedders().await.unwrap(), embedders);
+
+        index.reset_embedders_and_wait(None, None).await.unwrap();
+        assert!(index.get_embedders().await.unwrap().is_empty());
+    }
+
+    #[async_test]
+    async fn test_search_cutoff_ms() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_search_cutoff_ms").await;
+        assert_eq!(index.get_search_cutoff_ms().await.unwrap(), None);
+
+        index.set_search_cutoff_ms_and_wait(150, None, None).await.unwrap();
+        assert_eq!(index.get_search_cutoff_ms().await.unwrap(), Some(150));
+
+        index.reset_search_cutoff_ms_and_wait(None, None).await.unwrap();
+        assert_eq!(index.get_search_cutoff_ms().await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_symmetric_synonyms() {
+        let synonyms = Synonyms::symmetric(["wolverine", "logan", "xmen"]).into_map();
+        let mut expected = HashMap::new();
+        expected.insert("wolverine".to_string(), vec!["logan".to_string(), "xmen".to_string()]);
+        expected.insert("logan".to_string(), vec!["wolverine".to_string(), "xmen".to_string()]);
+        expected.insert("xmen".to_string(), vec!["wolverine".to_string(), "logan".to_string()]);
+        assert_eq!(synonyms, expected);
+
+        // Groups sharing a word are merged, without duplicates
+        let synonyms: HashMap<String, Vec<String>> =
+            Synonyms::symmetric(["xmen", "wolverine"]).with_symmetric(["xmen", "cyclops"]).with_one_way("xmen", ["wolverine", "storm"]).into();
+        assert_eq!(synonyms["xmen"], ["wolverine", "cyclops", "storm"]);
+        assert_eq!(synonyms["wolverine"], ["xmen"]);
+        assert_eq!(synonyms["cyclops"], ["xmen"]);
+        assert!(!synonyms.contains_key("storm"));
+    }
+
+    #[async_test]
+    async fn test_add_and_remove_synonyms_requests() {
+        fn body(request: &str) -> Value {
+            serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap()
+        }
+
+        const CURRENT: &str = r#"{"wolverine":["logan","xmen"],"logan":["wolverine","xmen"],"phone":["iphone"]}"#;
+        let (host, requests) =
+            crate::request::tests::mock_server(vec![(200, CURRENT), (202, r#"{"updateId":1}"#), (200, CURRENT), (202, r#"{"updateId":2}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        index.add_synonyms(&Synonyms::symmetric(["wolverine", "hugh"]).into_map()).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings/synonyms "));
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /indexes/movies/settings/synonyms "));
+        assert_eq!(
+            body(&request),
+            json!({"wolverine": ["logan", "xmen", "hugh"], "logan": ["wolverine", "xmen"], "hugh": ["wolverine"], "phone": ["iphone"]})
+        );
+
+        index.remove_synonym("phone").await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings/synonyms "));
+        assert_eq!(body(&requests.recv().unwrap()), json!({"wolverine": ["logan", "xmen"], "logan": ["wolverine", "xmen"]}));
+    }
+
+    #[test]
+    fn test_diff() {
+        let current: Settings = serde_json::from_value(server_settings()).unwrap();
+        let mut synonyms = HashMap::new();
+        synonyms.insert("wolverine", vec!["xmen", "logan"]);
+        let unchanged = Settings::new()
+            .with_stop_words(["the", "of"])
+            .with_filterable_attributes(["year", "genre"])
+            .with_synonyms(synonyms)
+            .with_displayed_attributes(["*", "title"]);
+        assert_eq!(unchanged.diff(&current), Settings::new());
+        assert_eq!(Settings::new().diff(&current), Settings::new());
+
+        let changed = unchanged.clone().with_sortable_attributes(["year"]).with_search_cutoff_ms(150);
+        let diff = changed.diff(&current);
+        assert_eq!(diff, Settings::new().with_sortable_attributes(["year"]).with_search_cutoff_ms(150));
+        assert_eq!(serde_json::to_value(&diff).unwrap(), json!({"sortableAttributes": ["year"], "searchCutoffMs": 150}));
+
+        // A setting unset on the server is a change, unknown settings are compared too
+        let mut wanted = Settings::new().with_distinct_attribute("id");
+        wanted.extra.insert("futureSetting".to_string(), json!(true));
+        let mut current = current;
+        current.extra.insert("futureSetting".to_string(), json!(false));
+        assert_eq!(serde_json::to_value(wanted.diff(&current)).unwrap(), json!({"distinctAttribute": "id", "futureSetting": true}));
+        current.extra.insert("futureSetting".to_string(), json!(true));
+        assert_eq!(serde_json::to_value(wanted.diff(&current)).unwrap(), json!({"distinctAttribute": "id"}));
+    }
+
+    #[async_test]
+    async fn test_update_settings_if_changed_requests() {
+        const SETTINGS: &str = r#"{"displayedAttributes":["*"],"searchableAttributes":["*"],"filterableAttributes":["genre","year"],"sortableAttributes":[],"rankingRules":["words","typo","proximity","attribute","sort","exactness"],"stopWords":["of","the"],"synonyms":{},"distinctAttribute":null}"#;
+        let (host, requests) = crate::request::tests::mock_server(vec![(200, SETTINGS), (200, SETTINGS), (202, r#"{"updateId":3}"#)]);
+        let index = Client::new(host, "masterKey").assume_index("movies");
+
+        let settings = Settings::new().with_stop_words(["the", "of"]);
+        assert!(index.update_settings_if_changed(&settings).await.unwrap().is_none());
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings "));
+        assert!(requests.try_recv().is_err());
+
+        let settings = settings.with_filterable_attributes(["genre"]);
+        let progress = index.update_settings_if_changed(&settings).await.unwrap().unwrap();
+        assert_eq!(progress.get_update_id(), 3);
+        assert!(requests.recv().unwrap().starts_with("GET /indexes/movies/settings "));
+        assert!(requests.recv().unwrap().starts_with("POST /indexes/movies/settings "));
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+
+        fn hash(settings: &Settings) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            settings.canonical_json().unwrap().hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let words: Vec<String> = (0..32).map(|i| format!("word{}", i)).collect();
+        let mut first = Settings::new();
+        let mut second = Settings::new();
+        first.synonyms = Some(words.iter().map(|word| (word.clone(), vec![word.to_uppercase()])).collect());
+        second.synonyms = Some(words.iter().rev().map(|word| (word.clone(), vec![word.to_uppercase()])).collect());
+        let embedders: Vec<(String, Embedder)> =
+            words.iter().map(|word| (word.clone(), Embedder::UserProvided { dimensions: word.len() })).collect();
+        first.embedders = Some(embedders.iter().cloned().collect());
+        second.embedders = Some(embedders.iter().rev().cloned().collect());
+        for word in &words {
+            first.extra.insert(format!("future{}", word), json!(word));
+        }
+        for word in words.iter().rev() {
+            second.extra.insert(format!("future{}", word), json!(word));
+        }
+        assert_eq!(first, second);
+        assert_eq!(first.canonical_json().unwrap(), second.canonical_json().unwrap());
+        assert_eq!(hash(&first), hash(&second));
+
+        // Deserialization accepts any order, the serialization is sorted
+        let settings: Settings = serde_json::from_str(
+            r#"{"futureB":1,"synonyms":{"b":["c"],"a":["d"]},"futureA":2,"embedders":{"y":{"source":"userProvided","dimensions":2},"x":{"source":"userProvided","dimensions":1}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings.canonical_json().unwrap(),
+            r#"{"synonyms":{"a":["d"],"b":["c"]},"embedders":{"x":{"source":"userProvided","dimensions":1},"y":{"source":"userProvided","dimensions":2}},"futureA":2,"futureB":1}"#
+        );
+    }
+
+    #[test]
+    fn test_unknown_settings_preserved() {
+        let mut response = server_settings();
+        response["futureSetting"] = json!({"enabled": true, "threshold": 3});
+        let mut settings: Settings = serde_json::from_value(response).unwrap();
+        assert_eq!(settings.extra.len(), 1);
+        assert_eq!(settings.extra["futureSetting"], json!({"enabled": true, "threshold": 3}));
+
+        // Modified then sent back, the unknown setting is kept
+        settings.stop_words = Some(vec!["a".to_string()]);
+        let body = serde_json::to_value(&settings).unwrap();
+        assert_eq!(body["futureSetting"], json!({"enabled": true, "threshold": 3}));
+        assert_eq!(body["stopWords"], json!(["a"]));
+        assert!(body.get("extra").is_none());
+    }
+
+    #[test]
+    fn test_matching_settings() {
+        let settings = Settings::new()
+            .with_filterable_attributes(["year", "genre"])
+            .with_stop_words(["the", "of"])
+            .with_displayed_attributes(["*", "title"])
+            .with_synonyms(vec![("wolverine".to_string(), vec!["xmen".to_string(), "logan".to_string()])].into_iter().collect());
+
+        let mismatch = compare_settings(&serde_json::to_value(&settings).unwrap(), &server_settings());
+        assert!(mismatch.is_empty());
+    }
+
+    #[test]
+    fn test_mismatching_settings() {
+        let settings = Settings::new()
+            .with_ranking_rules(["typo", "words"])
+            .with_distinct_attribute("id");
+
+        let mismatch = compare_settings(&serde_json::to_value(&settings).unwrap(), &server_settings());
+        assert_eq!(mismatch.fields, vec![
+            FieldMismatch { field: "distinctAttribute".to_string(), expected: json!("id"), actual: Some(Value::Null) },
+            FieldMismatch {
+                field: "rankingRules".to_string(),
+                expected: json!(["typo", "words"]),
+                actual: Some(json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_misnamed_setting() {
+        // What a wrong serde rename would send
+        let mismatch = compare_settings(&json!({"sortableAttribute": ["year"]}), &server_settings());
+        assert_eq!(mismatch.fields, vec![
+            FieldMismatch { field: "sortableAttribute".to_string(), expected: json!(["year"]), actual: None },
+        ]);
+    }
+
+    #[async_test]
+    async fn test_verify_settings() {
+        let client = Client::new("http://localhost:7700", "masterKey");
+        let index = TestIndex::new(&client, "test_verify_settings").await;
+        let settings = Settings::new()
+            .with_stop_words(["the", "of"])
+            .with_filterable_attributes(["year", "genre"]);
+        index.set_settings_and_wait(&settings, None, None).await.unwrap();
+
+        assert!(index.verify_settings(&settings).await.unwrap().is_empty());
+        let mismatch = index.verify_settings(&settings.with_sortable_attributes(["year"])).await.unwrap();
+        assert_eq!(mismatch.fields.len(), 1);
+        assert_eq!(mismatch.fields[0].field, "sortableAttributes");
+    }
 }
@@ -0,0 +1,428 @@
+//! [Search queries](../search/struct.Query.html) can be checked the same way with [Index::lint_query].
+//!
+//! The checks are heuristic: they only look at the documents you are about to send and at the settings of the index.
+//! They can't tell whether the documents already in the index are consistent with the new ones.
+//!
+//! # Example
+//!
+//! ```
+//! # use meilisearch_sdk::{client::*, document::*, validation::*};
+//! # use serde::{Serialize, Deserialize};
+//! #
+//! # #[derive(Serialize, Deserialize, Debug)]
+//! # struct Movie {
+//! #    id: usize,
+//! #    title: String,
+//! # }
+//! #
+//! # impl Document for Movie {
+//! #    type UIDType = usize;
+//! #    fn get_uid(&self) -> &Self::UIDType {
+//! #        &self.id
+//! #    }
+//! # }
+//! #
+//! # futures::executor::block_on(async move {
+//! let client = Client::new("http://localhost:7700", "masterKey");
+//! let movies = client.get_or_create("movies_validation").await.unwrap();
+//!
+//! let report = movies.validate_documents(&[
+//!     Movie { id: 1, title: String::from("Interstellar") },
+//! ]).await.unwrap();
+//!
+//! for finding in &report.findings {
+//!     println!("{:?}: {}", finding.severity(), finding);
+//! }
+//! # client.delete_index("movies_validation").await.unwrap();
+//! # });
+//! ```
+
+use crate::{
+    cache::cached_request,
+    document::Document,
+    errors::Error,
+    indexes::*,
+    search::{explain_terms, Filter, Query, TermStatus},
+    settings::Settings,
+};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// How likely a [Finding] is to cause a problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The documents will be accepted, but probably won't behave as expected.
+    Warning,
+    /// MeiliSearch will most likely reject the documents.
+    Critical,
+}
+
+/// A potential problem found by [Index::validate_documents].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Finding {
+    /// A document has no value for the primary key of the index.
+    MissingPrimaryKey {
+        /// The position of the document in the slice
+        document: usize,
+        /// The primary key of the index
+        primary_key: String,
+    },
+    /// An attribute used in the settings is absent from all the documents.
+    MissingAttribute {
+        /// The name of the attribute
+        attribute: String,
+        /// The setting referencing the attribute (`filterableAttributes` or `sortableAttributes`)
+        setting: &'static str,
+    },
+    /// An attribute used for filtering or sorting doesn't have the same type in all the documents.
+    TypeDrift {
+        /// The name of the attribute
+        attribute: String,
+        /// The JSON types found for this attribute (`null` is ignored)
+        types: BTreeSet<&'static str>,
+    },
+}
+
+impl Finding {
+    /// The severity of the finding.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::MissingPrimaryKey { .. } => Severity::Critical,
+            Finding::MissingAttribute { .. } | Finding::TypeDrift { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Finding::MissingPrimaryKey { document, primary_key } => {
+                write!(fmt, "document {} has no value for the primary key `{}`", document, primary_key)
+            }
+            Finding::MissingAttribute { attribute, setting } => {
+                write!(fmt, "`{}` is listed in {} but no document contains it", attribute, setting)
+            }
+            Finding::TypeDrift { attribute, types } => {
+                let types: Vec<&str> = types.iter().copied().collect();
+                write!(fmt, "`{}` has several types across documents: {}", attribute, types.join(", "))
+            }
+        }
+    }
+}
+
+/// The result of [Index::validate_documents].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// The potential problems found in the documents
+    pub findings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if nothing suspicious was found.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The highest severity among the findings, if any.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(Finding::severity).max()
+    }
+}
+
+/// A potential problem found by [Index::lint_query].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum QueryFinding {
+    /// All the terms of the query are stop words, so no document can match them.
+    OnlyStopWords {
+        /// The terms of the query
+        terms: Vec<String>,
+    },
+    /// The filter references the distinct attribute: it is applied before the deduplication,
+    /// so the documents kept for each distinct value may not be the ones expected.
+    FilterOnDistinctAttribute {
+        /// The distinct attribute of the index
+        attribute: String,
+    },
+}
+
+impl QueryFinding {
+    /// The severity of the finding.
+    pub fn severity(&self) -> Severity {
+        match self {
+            QueryFinding::OnlyStopWords { .. } | QueryFinding::FilterOnDistinctAttribute { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for QueryFinding {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryFinding::OnlyStopWords { terms } => {
+                write!(fmt, "the query only contains stop words ({}) and can't match any document", terms.join(", "))
+            }
+            QueryFinding::FilterOnDistinctAttribute { attribute } => {
+                write!(fmt, "the filter references the distinct attribute `{}`, which may hide results", attribute)
+            }
+        }
+    }
+}
+
+/// Check the text and the filter of a query against the stop words and the distinct attribute of an index.
+pub(crate) fn inspect_query(
+    query: Option<&str>,
+    filter: Option<&Filter>,
+    stop_words: &[String],
+    distinct_attribute: Option<&str>,
+) -> Vec<QueryFinding> {
+    let mut findings = Vec::new();
+
+    if let Some(query) = query {
+        let report = explain_terms(query, stop_words, &HashMap::new());
+        if !report.terms.is_empty() && report.terms.iter().all(|explanation| explanation.status == TermStatus::StopWord) {
+            let terms = report.terms.into_iter().map(|explanation| explanation.term).collect();
+            findings.push(QueryFinding::OnlyStopWords { terms });
+        }
+    }
+
+    if let (Some(filter), Some(attribute)) = (filter, distinct_attribute) {
+        if filter.referenced_fields().iter().any(|field| field == attribute) {
+            findings.push(QueryFinding::FilterOnDistinctAttribute { attribute: attribute.to_string() });
+        }
+    }
+
+    findings
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Collect the values of an attribute, following dots into nested objects (and arrays of objects).
+fn collect_values<'a>(value: &'a Value, path: &str, values: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(found) = map.get(path) {
+                values.push(found);
+            }
+            if let Some((head, tail)) = path.split_once('.') {
+                if let Some(nested) = map.get(head) {
+                    collect_values(nested, tail, values);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_values(item, path, values);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Check serialized documents against the primary key and the settings of an index.
+pub(crate) fn inspect_documents(
+    documents: &[Value],
+    primary_key: Option<&str>,
+    settings: &Settings,
+) -> ValidationReport {
+    let mut findings = Vec::new();
+
+    if let Some(primary_key) = primary_key {
+        for (position, document) in documents.iter().enumerate() {
+            let mut values = Vec::new();
+            collect_values(document, primary_key, &mut values);
+            if values.iter().all(|value| value.is_null()) {
+                findings.push(Finding::MissingPrimaryKey {
+                    document: position,
+                    primary_key: primary_key.to_string(),
+                });
+            }
+        }
+    }
+
+    let mut checked = BTreeSet::new();
+    let referenced = [
+        ("filterableAttributes", &settings.filterable_attributes),
+        ("sortableAttributes", &settings.sortable_attributes),
+    ];
+    for &(setting, attributes) in referenced.iter() {
+        // `*` makes every attribute filterable or sortable, it is not an attribute of the documents
+        for attribute in attributes.iter().flatten().filter(|attribute| attribute.as_str() != "*") {
+            let mut values = Vec::new();
+            for document in documents {
+                collect_values(document, attribute, &mut values);
+            }
+
+            if values.is_empty() && !documents.is_empty() {
+                findings.push(Finding::MissingAttribute {
+                    attribute: attribute.clone(),
+                    setting,
+                });
+            }
+
+            if checked.insert(attribute.as_str()) {
+                let types: BTreeSet<&'static str> = values
+                    .iter()
+                    .map(|value| json_type(value))
+                    .filter(|kind| *kind != "null")
+                    .collect();
+                if types.len() > 1 {
+                    findings.push(Finding::TypeDrift {
+                        attribute: attribute.clone(),
+                        types,
+                    });
+                }
+            }
+        }
+    }
+
+    ValidationReport { findings }
+}
+
+impl Index {
+    /// Check [documents](../document/trait.Document.html) against the settings of the index **without sending them**.
+    ///
+    /// The settings and the primary key of the index are fetched, then the documents are inspected locally.
+    /// See the [validation](index.html) module for an example.
+    pub async fn validate_documents<T: Document>(&self, documents: &[T]) -> Result<ValidationReport, Error> {
+        let index = cached_request::<JsonIndex>(
+            &self.cache,
+            &format!("{}/indexes/{}", self.host, self.uid),
+            &self.api_key,
+            self.max_response_bytes,
+        ).await?;
+        let settings = self.get_settings().await?;
+
+        let documents = documents
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<Value>, _>>()
+            .map_err(Error::ParseError)?;
+
+        Ok(inspect_documents(&documents, index.primaryKey.as_deref(), &settings))
+    }
+
+    /// Check a [query](../search/struct.Query.html) for common mistakes **without executing it**.
+    ///
+    /// The stop words are fetched if the query has a text, and the distinct attribute if it has a filter,
+    /// through the [cache](../client/struct.Client.html#method.with_cache) if it is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use meilisearch_sdk::prelude::*;
+    /// # futures::executor::block_on(async move {
+    /// let client = Client::new("http://localhost:7700", "masterKey");
+    /// let movies = client.get_or_create("movies_lint_query").await.unwrap();
+    /// movies.set_stop_words_and_wait(["the", "of"], None, None).await.unwrap();
+    ///
+    /// let query = movies.search().with_query("The Of").build();
+    /// for finding in movies.lint_query(&query).await.unwrap() {
+    ///     println!("{:?}: {}", finding.severity(), finding);
+    /// }
+    /// # client.delete_index("movies_lint_query").await.unwrap();
+    /// # });
+    /// ```
+    pub async fn lint_query(&self, query: &Query<'_>) -> Result<Vec<QueryFinding>, Error> {
+        let stop_words = match &query.query {
+            Some(_) => self.get_stop_words().await?,
+            None => Vec::new(),
+        };
+        let distinct_attribute = match &query.filter {
+            Some(_) => self.get_distinct_attribute().await?,
+            None => None,
+        };
+        Ok(inspect_query(query.query.as_deref(), query.filter.as_ref(), &stop_words, distinct_attribute.as_deref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_missing_primary_key() {
+        let documents = [json!({"id": 1, "title": "Carol"}), json!({"title": "Wonder Woman"}), json!({"id": null})];
+        let report = inspect_documents(&documents, Some("id"), &Settings::new());
+
+        assert_eq!(report.findings, vec![
+            Finding::MissingPrimaryKey { document: 1, primary_key: "id".to_string() },
+            Finding::MissingPrimaryKey { document: 2, primary_key: "id".to_string() },
+        ]);
+        assert_eq!(report.max_severity(), Some(Severity::Critical));
+    }
+
+    #[test]
+    fn test_missing_attribute() {
+        let documents = [json!({"id": 1, "genre": "drama", "info": {"year": 1999}})];
+        let settings = Settings::new()
+            .with_filterable_attributes(["genre", "info.year", "director"])
+            .with_sortable_attributes(["release_date"]);
+        let report = inspect_documents(&documents, Some("id"), &settings);
+
+        assert_eq!(report.findings, vec![
+            Finding::MissingAttribute { attribute: "director".to_string(), setting: "filterableAttributes" },
+            Finding::MissingAttribute { attribute: "release_date".to_string(), setting: "sortableAttributes" },
+        ]);
+        assert_eq!(report.max_severity(), Some(Severity::Warning));
+
+        let settings = Settings::new().with_filterable_attributes(["*"]).with_sortable_attributes(["*"]);
+        assert!(inspect_documents(&documents, Some("id"), &settings).findings.is_empty());
+    }
+
+    #[test]
+    fn test_type_drift() {
+        let documents = [json!({"id": 1, "year": 1999}), json!({"id": 2, "year": "2001"}), json!({"id": 3, "year": null})];
+        let settings = Settings::new()
+            .with_filterable_attributes(["year"])
+            .with_sortable_attributes(["year"]);
+        let report = inspect_documents(&documents, Some("id"), &settings);
+
+        assert_eq!(report.findings, vec![
+            Finding::TypeDrift { attribute: "year".to_string(), types: ["number", "string"].iter().copied().collect() },
+        ]);
+    }
+
+    #[test]
+    fn test_only_stop_words() {
+        let stop_words = ["the".to_string(), "of".to_string()];
+        assert_eq!(inspect_query(Some("The of, the"), None, &stop_words, None), vec![
+            QueryFinding::OnlyStopWords { terms: vec!["the".to_string(), "of".to_string(), "the".to_string()] },
+        ]);
+        assert!(inspect_query(Some("the lord of the rings"), None, &stop_words, None).is_empty());
+        assert!(inspect_query(Some(""), None, &stop_words, None).is_empty());
+        assert!(inspect_query(Some("the"), None, &[], None).is_empty());
+    }
+
+    #[test]
+    fn test_filter_on_distinct_attribute() {
+        let filter = Filter::Expression("genre = drama AND (sku = 42 OR year > 2000)".into());
+        let findings = inspect_query(None, Some(&filter), &[], Some("sku"));
+        assert_eq!(findings, vec![QueryFinding::FilterOnDistinctAttribute { attribute: "sku".to_string() }]);
+        assert_eq!(findings[0].severity(), Severity::Warning);
+
+        let filter = Filter::Array(vec![vec!["genre = sku", "'title' = \"sku\""]]);
+        assert!(inspect_query(None, Some(&filter), &[], Some("sku")).is_empty());
+        assert!(inspect_query(None, Some(&filter), &[], None).is_empty());
+    }
+
+    #[test]
+    fn test_clean_documents() {
+        let documents = [json!({"id": 1, "year": 1999}), json!({"id": 2, "year": 2001})];
+        let settings = Settings::new().with_filterable_attributes(["year"]);
+        let report = inspect_documents(&documents, Some("id"), &settings);
+
+        assert!(report.is_clean());
+        assert_eq!(report.max_severity(), None);
+    }
+}